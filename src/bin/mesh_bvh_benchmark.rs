@@ -0,0 +1,62 @@
+use rust_maths::*;
+use std::time::Instant;
+
+// a sphere made of rings of triangles
+fn sphere_mesh(radius: f32, rings: u32, segments: u32) -> MeshCollider {
+    let mut vertices = Vec::new();
+    for ring in 0..=rings {
+        let theta = ring as f32 / rings as f32 * std::f32::consts::PI;
+        for segment in 0..segments {
+            let phi = segment as f32 / segments as f32 * std::f32::consts::TAU;
+            vertices.push(Vector3::new(theta.sin() * phi.cos(), theta.cos(), theta.sin() * phi.sin()) * radius);
+        }
+    }
+
+    let mut indices = Vec::new();
+    for ring in 0..rings {
+        for segment in 0..segments {
+            let (a, b) = (ring * segments + segment, ring * segments + (segment + 1) % segments);
+            let (c, d) = (a + segments, b + segments);
+            indices.extend([a, c, b, b, c, d]);
+        }
+    }
+    MeshCollider::new(vertices, indices)
+}
+
+// the old way of checking a mesh, sorting every triangle by distance and checking them in order
+fn sorted_linear_check(mesh: &MeshCollider, root: Vector3, direction: Vector3) -> Option<RayHitInfo> {
+    let tris = mesh.get_triangles();
+    let distances = tris.iter().enumerate().map(|(i, tri)| (tri.centre_dist_to(root), i)).collect();
+    quicksort(distances).into_iter().find_map(|(_, i)| tris[i].check_ray(root, direction, None))
+}
+
+fn main() {
+    let mut rng = thread_rng();
+
+    for (rings, segments) in [(16, 32), (64, 128), (128, 256)] {
+        let build_start = Instant::now();
+        let mesh = sphere_mesh(10.0, rings, segments);
+        let build_time = build_start.elapsed();
+
+        let rays: Vec<(Vector3, Vector3)> = (0..200).map(|_| {
+            let root = Vector3::new(rng.gen::<f32>() - 0.5, rng.gen::<f32>() - 0.5, rng.gen::<f32>() - 0.5).normalised() * 30.0;
+            let target = Vector3::new(rng.gen::<f32>() - 0.5, rng.gen::<f32>() - 0.5, rng.gen::<f32>() - 0.5) * 20.0;
+            (root, (target - root).normalised())
+        }).collect();
+
+        let bvh_start = Instant::now();
+        let bvh_hits = rays.iter().filter(|(root, direction)| mesh.check_ray(*root, *direction, None).is_some()).count();
+        let bvh_time = bvh_start.elapsed();
+
+        let linear_start = Instant::now();
+        let linear_hits = rays.iter().filter(|(root, direction)| sorted_linear_check(&mesh, *root, *direction).is_some()).count();
+        let linear_time = linear_start.elapsed();
+
+        println!(
+            "{} triangles (built in {:?}, depth {}): bvh {:?} for {} hits, sorted linear {:?} for {} hits, {:.1}x faster",
+            mesh.get_triangles().len(), build_time, mesh.get_bvh_depth(),
+            bvh_time, bvh_hits, linear_time, linear_hits,
+            linear_time.as_secs_f64() / bvh_time.as_secs_f64()
+        );
+    }
+}
@@ -2,23 +2,7 @@
 use std::{collections::HashMap, hash::Hash};
 
 // use winit::event::VirtualKeyCode;
-use super::{Vector3, Matrix3, Matrix4};
-
-
-pub struct Camera<MovementControlVariable: Eq + Hash> {
-    pub position: Vector3, 
-    pub direction: Vector3,
-    pub up: Vector3,
-    pub move_speed: f32,
-    pub rotate_speed: f32,
-    pub movement: [bool; 10], // forward, back, left, right, up, down, spin right, spin left, spin forward, spin backward
-    is_controlled: bool,
-
-
-    pub movement_map: HashMap<MovementControlVariable, CameraDirections>
-}
-
-
+use super::{Vector3, Matrix3, Matrix4, DVector3, DMatrix3, DMatrix4};
 
 
 pub enum CameraDirections {
@@ -34,149 +18,198 @@ pub enum CameraDirections {
     SpinBackward
 }
 
-impl<T: Eq + Hash> Camera<T> {
 
-    pub fn new(start_pos: Option<[f32; 3]>, start_dir: Option<[f32; 3]>, move_speed: Option<f32>, rotate_speed: Option<f32>, movement_map: Option<HashMap<T, CameraDirections>>) -> Self{
-        let position: Vector3 = {
-            if start_pos.is_some() {
-                start_pos.unwrap().into()
-            } else {
-                Vector3::ZERO
+macro_rules! camera {
+    ($(#[$attr:meta])* $name:ident, $scalar:ident, $vector3:ident, $matrix3:ident, $matrix4:ident) => {
+        $(#[$attr])*
+        pub struct $name<MovementControlVariable: Eq + Hash> {
+            pub position: $vector3, 
+            pub direction: $vector3,
+            pub up: $vector3,
+            pub move_speed: $scalar,
+            pub rotate_speed: $scalar,
+            pub movement: [bool; 10], // forward, back, left, right, up, down, spin right, spin left, spin forward, spin backward
+            is_controlled: bool,
+
+
+            pub movement_map: HashMap<MovementControlVariable, CameraDirections>
+        }
+
+
+        impl<T: Eq + Hash> $name<T> {
+
+            pub fn new(start_pos: Option<[$scalar; 3]>, start_dir: Option<[$scalar; 3]>, move_speed: Option<$scalar>, rotate_speed: Option<$scalar>, movement_map: Option<HashMap<T, CameraDirections>>) -> Self{
+                let position: $vector3 = {
+                    if start_pos.is_some() {
+                        start_pos.unwrap().into()
+                    } else {
+                        $vector3::ZERO
+                    }
+                };
+
+                let direction: $vector3 = {
+                    if start_dir.is_some() && start_dir.unwrap() != [0.0; 3] {
+                        start_dir.unwrap().into()
+                    } else {
+                        $vector3::X
+                    }
+                };
+
+                let control_map = if movement_map.is_some() {movement_map.unwrap()} else {HashMap::new()};
+
+                $name {
+                    position,
+                    direction,
+                    move_speed: move_speed.unwrap_or(3.0),
+                    rotate_speed: rotate_speed.unwrap_or(1.0),
+                    movement: [false; 10],
+                    up: -$vector3::Y,
+                    is_controlled: false,
+                    movement_map: control_map
+                }
             }
-        };
 
-        let direction: Vector3 = {
-            if start_dir.is_some() && start_dir.unwrap() != [0.0; 3] {
-                start_dir.unwrap().into()
-            } else {
-                Vector3::X
+            pub fn controllable(&mut self) {
+                self.is_controlled = true;
             }
-        };
-
-        let control_map = if movement_map.is_some() {movement_map.unwrap()} else {HashMap::new()};
-
-        Camera {
-            position,
-            direction,
-            move_speed: move_speed.unwrap_or(3.0),
-            rotate_speed: rotate_speed.unwrap_or(1.0),
-            movement: [false; 10],
-            up: -Vector3::Y,
-            is_controlled: false,
-            movement_map: control_map
-        }
-    }
 
-    pub fn controllable(&mut self) {
-        self.is_controlled = true;
-    }
+            pub fn toggle_controlled(&mut self) {
+                self.is_controlled ^= true;
+            }
 
-    pub fn toggle_controlled(&mut self) {
-        self.is_controlled ^= true;
-    }
+            pub fn get_view_matrix(&self) -> $matrix4 {
+                let f = self.direction.normalised();
 
-    pub fn get_view_matrix(&self) -> Matrix4 {
-        let f = self.direction.normalised();
-    
-        let mut s = self.up.cross(f);
-
-        s.normalise();
-    
-        let u = f.cross(s);
-    
-        Matrix4::new(
-            s.x, u.x, -f.x, 0.0,
-            s.y, u.y, -f.y, 0.0,
-            s.z, u.z, -f.z, 0.0,
-            -self.position.dot(s), -self.position.dot(u), self.position.dot(f), 1.0
-        )
-    }
+                let mut s = self.up.cross(f);
 
-    pub fn look_at(&mut self, target: Vector3) {
-        self.direction = (target - self.position).normalised();
-    }
+                s.normalise();
 
-    pub fn process_input(&mut self, control: T, state: bool) {
-        match self.movement_map.get(&control) {
-            Some(CameraDirections::Forward) => {
-                self.movement[0] = state;
-            }
-            Some(CameraDirections::Backwards) => {
-                self.movement[1] = state;
-            }
-            Some(CameraDirections::Left) => {
-                self.movement[2] = state;
-            }
-            Some(CameraDirections::Right) => {
-                self.movement[3] = state;
-            }
-            Some(CameraDirections::Up) => {
-                self.movement[4] = state;
-            }
-            Some(CameraDirections::Down) => {
-                self.movement[5] = state;
-            }
-            Some(CameraDirections::SpinLeft) => {
-                self.movement[6] = state;
-            }
-            Some(CameraDirections::SpinRight) => {
-                self.movement[7] = state;
-            }
-            Some(CameraDirections::SpinForward) => {
-                self.movement[8] = state;
-            }
-            Some(CameraDirections::SpinBackward) => {
-                self.movement[9] = state;
-            }
-            _ => ()
-        }
-    }
+                let u = f.cross(s);
 
+                $matrix4::new(
+                    s.x, u.x, -f.x, 0.0,
+                    s.y, u.y, -f.y, 0.0,
+                    s.z, u.z, -f.z, 0.0,
+                    -self.position.dot(s), -self.position.dot(u), self.position.dot(f), 1.0
+                )
+            }
 
+            pub fn look_at(&mut self, target: $vector3) {
+                self.direction = (target - self.position).normalised();
+            }
 
+            pub fn process_input(&mut self, control: T, state: bool) {
+                match self.movement_map.get(&control) {
+                    Some(CameraDirections::Forward) => {
+                        self.movement[0] = state;
+                    }
+                    Some(CameraDirections::Backwards) => {
+                        self.movement[1] = state;
+                    }
+                    Some(CameraDirections::Left) => {
+                        self.movement[2] = state;
+                    }
+                    Some(CameraDirections::Right) => {
+                        self.movement[3] = state;
+                    }
+                    Some(CameraDirections::Up) => {
+                        self.movement[4] = state;
+                    }
+                    Some(CameraDirections::Down) => {
+                        self.movement[5] = state;
+                    }
+                    Some(CameraDirections::SpinLeft) => {
+                        self.movement[6] = state;
+                    }
+                    Some(CameraDirections::SpinRight) => {
+                        self.movement[7] = state;
+                    }
+                    Some(CameraDirections::SpinForward) => {
+                        self.movement[8] = state;
+                    }
+                    Some(CameraDirections::SpinBackward) => {
+                        self.movement[9] = state;
+                    }
+                    _ => ()
+                }
+            }
 
-    pub fn do_move(&mut self, delta_time: f32) {
-        if !self.is_controlled {return;}
 
-        // take cross of direction and up to get left
-        let mut left = self.direction.cross(self.up);
 
-        let forward = left.cross(self.up);
-        // forward/back
-        if self.movement[0] {self.position -= forward * self.move_speed * delta_time}
-        if self.movement[1] {self.position += forward * self.move_speed * delta_time}
-        // left/right
-        if self.movement[2] {self.position += left * self.move_speed * delta_time}
-        if self.movement[3] {self.position -= left * self.move_speed * delta_time}
-        // up/down
-        if self.movement[4] {self.position -= self.up * self.move_speed * delta_time}
-        if self.movement[5] {self.position += self.up * self.move_speed * delta_time}
 
-        // spin around up
-        // normalise up
-        self.up.normalise();
-        // rotate
-        if self.movement[6] {
-            let rotation = Matrix3::from_angle_and_axis(self.rotate_speed * delta_time, self.up);
-            self.direction = rotation * self.direction;
-        }
-        if self.movement[7] {
-            let rotation = Matrix3::from_angle_and_axis(-self.rotate_speed * delta_time, self.up);
-            self.direction = rotation * self.direction;
+            pub fn do_move(&mut self, delta_time: $scalar) {
+                if !self.is_controlled {return;}
+
+                // take cross of direction and up to get left
+                let mut left = self.direction.cross(self.up);
+
+                let forward = left.cross(self.up);
+                // forward/back
+                if self.movement[0] {self.position -= forward * self.move_speed * delta_time}
+                if self.movement[1] {self.position += forward * self.move_speed * delta_time}
+                // left/right
+                if self.movement[2] {self.position += left * self.move_speed * delta_time}
+                if self.movement[3] {self.position -= left * self.move_speed * delta_time}
+                // up/down
+                if self.movement[4] {self.position -= self.up * self.move_speed * delta_time}
+                if self.movement[5] {self.position += self.up * self.move_speed * delta_time}
+
+                // spin around up
+                // normalise up
+                self.up.normalise();
+                // rotate
+                if self.movement[6] {
+                    let rotation = $matrix3::from_angle_and_axis(self.rotate_speed * delta_time, self.up);
+                    self.direction = rotation * self.direction;
+                }
+                if self.movement[7] {
+                    let rotation = $matrix3::from_angle_and_axis(-self.rotate_speed * delta_time, self.up);
+                    self.direction = rotation * self.direction;
+                }
+
+                // spin around left
+                // normalise left
+                left.normalise();
+                // rotate
+                if self.movement[8] {
+                    let rotation = $matrix3::from_angle_and_axis(self.rotate_speed * delta_time, left);
+                    self.direction = rotation * self.direction;
+                }
+                if self.movement[9] {
+                    let rotation = $matrix3::from_angle_and_axis(-self.rotate_speed * delta_time, left);
+                    self.direction = rotation * self.direction;
+                }
+            }
         }
+    };
+}
 
-        // spin around left
-        // normalise left
-        left.normalise();
-        // rotate
-        if self.movement[8] {
-            let rotation = Matrix3::from_angle_and_axis(self.rotate_speed * delta_time, left);
-            self.direction = rotation * self.direction;
-        }
-        if self.movement[9] {
-            let rotation = Matrix3::from_angle_and_axis(-self.rotate_speed * delta_time, left);
-            self.direction = rotation * self.direction;
+camera!(Camera, f32, Vector3, Matrix3, Matrix4);
+camera!(
+    /// a camera with an f64 position, for flying round worlds too big for `Camera`
+    ///
+    /// use `to_relative_camera` to get an f32 camera near the origin for rendering
+    DCamera, f64, DVector3, DMatrix3, DMatrix4
+);
+
+
+impl<T: Eq + Hash> DCamera<T> {
+    /// converts to a single precision camera, moving the camera so that `origin` becomes the origin.
+    ///
+    /// rendering relative to a nearby origin keeps the view matrix precise even when the camera is far out
+    pub fn to_relative_camera(self, origin: impl Into<DVector3>) -> Camera<T> {
+        let mut camera = Camera::new(
+            Some((self.position - origin.into()).to_single().into()),
+            Some(self.direction.to_single().into()),
+            Some(self.move_speed as f32),
+            Some(self.rotate_speed as f32),
+            Some(self.movement_map)
+        );
+        camera.up = self.up.to_single();
+        camera.movement = self.movement;
+        if self.is_controlled {
+            camera.controllable();
         }
+        camera
     }
 }
-
@@ -134,6 +134,55 @@ impl AABoundingBox {
         AABoundingBox::new(min_corner, max_corner)
     }
 
+    /// the closest point in the box to the given point, which is the point itself if it is inside
+    pub fn closest_point(&self, point: impl Into<Vector3>) -> Vector3 {
+        let point = point.into();
+        Vector3::new(
+            point.x.clamp(self.min_corner.x, self.max_corner.x),
+            point.y.clamp(self.min_corner.y, self.max_corner.y),
+            point.z.clamp(self.min_corner.z, self.max_corner.z),
+        )
+    }
+
+    pub fn corners(&self) -> [Vector3; 8] {
+        let (min, max) = (self.min_corner, self.max_corner);
+        [
+            Vector3::new(min.x, min.y, min.z),
+            Vector3::new(max.x, min.y, min.z),
+            Vector3::new(min.x, max.y, min.z),
+            Vector3::new(max.x, max.y, min.z),
+            Vector3::new(min.x, min.y, max.z),
+            Vector3::new(max.x, min.y, max.z),
+            Vector3::new(min.x, max.y, max.z),
+            Vector3::new(max.x, max.y, max.z),
+        ]
+    }
+
+    pub fn centre(&self) -> Vector3 {
+        (self.min_corner + self.max_corner) * 0.5
+    }
+
+    pub fn surface_area(&self) -> f32 {
+        let size = self.max_corner - self.min_corner;
+        2.0 * (size.x * size.y + size.y * size.z + size.z * size.x)
+    }
+
+    /// the smallest box containing both boxes
+    pub fn merged(&self, other: AABoundingBox) -> AABoundingBox {
+        AABoundingBox::new(
+            Vector3::new(self.min_corner.x.min(other.min_corner.x), self.min_corner.y.min(other.min_corner.y), self.min_corner.z.min(other.min_corner.z)),
+            Vector3::new(self.max_corner.x.max(other.max_corner.x), self.max_corner.y.max(other.max_corner.y), self.max_corner.z.max(other.max_corner.z)),
+        )
+    }
+
+    /// the box covered by both boxes, only makes sense if they are intersecting
+    pub fn overlap(&self, other: AABoundingBox) -> AABoundingBox {
+        AABoundingBox::new(
+            Vector3::new(self.min_corner.x.max(other.min_corner.x), self.min_corner.y.max(other.min_corner.y), self.min_corner.z.max(other.min_corner.z)),
+            Vector3::new(self.max_corner.x.min(other.max_corner.x), self.max_corner.y.min(other.max_corner.y), self.max_corner.z.min(other.max_corner.z)),
+        )
+    }
+
     pub fn is_intersecting_box(&self, other: AABoundingBox) -> bool {
         self.min_corner.x <= other.max_corner.x &&
         self.max_corner.x >= other.min_corner.x &&
@@ -161,7 +210,8 @@ impl Collider for AABoundingBox {
         if self.contains_point(root_position) {return Some(RayHitInfo::new(root_position, 0.0, Vector3::ZERO));}
 
         let inv_dir = Vector3::ONE / direction;
-        let (mut max_norm, mut min_norm) = (Vector3::X, -Vector3::X);
+        // the normal of the face the ray enters through faces back against the ray
+        let (mut max_norm, mut min_norm) = if inv_dir.x < 0.0 {(-Vector3::X, Vector3::X)} else {(Vector3::X, -Vector3::X)};
 
         let (mut tmax, mut tmin) = if inv_dir.x < 0.0 {
             ((self.min_corner.x - root_position.x) * inv_dir.x, (self.max_corner.x - root_position.x) * inv_dir.x)
@@ -178,8 +228,8 @@ impl Collider for AABoundingBox {
 
         if (tmin > t_ymax) || (t_ymin > tmax) {return None;}
 
-        if t_ymin > tmin {tmin = t_ymin; min_norm = -Vector3::Y}
-        if t_ymax < tmax {tmax = t_ymax; max_norm = Vector3::Y}
+        if t_ymin > tmin {tmin = t_ymin; min_norm = if inv_dir.y < 0.0 {Vector3::Y} else {-Vector3::Y}}
+        if t_ymax < tmax {tmax = t_ymax; max_norm = if inv_dir.y < 0.0 {-Vector3::Y} else {Vector3::Y}}
 
         let (t_zmax, t_zmin) = if inv_dir.z < 0.0 {
             ((self.min_corner.z - root_position.z) * inv_dir.z, (self.max_corner.z - root_position.z) * inv_dir.z)
@@ -189,8 +239,8 @@ impl Collider for AABoundingBox {
 
         if (tmin > t_zmax) || (t_zmin > tmax) {return None;}
 
-        if t_zmin > tmin {tmin = t_zmin; min_norm = -Vector3::Z}
-        if t_zmax < tmax {tmax = t_zmax; max_norm = Vector3::Z}
+        if t_zmin > tmin {tmin = t_zmin; min_norm = if inv_dir.z < 0.0 {Vector3::Z} else {-Vector3::Z}}
+        if t_zmax < tmax {tmax = t_zmax; max_norm = if inv_dir.z < 0.0 {-Vector3::Z} else {Vector3::Z}}
 
         let dist = if tmin < 0.0 {tmax} else if tmax < 0.0 {return None} else {tmin};
 
@@ -0,0 +1,54 @@
+use super::{Vector3, DVector3, Collider, PointQuery, RayHitInfo};
+
+
+/// a collider placed in a double precision world
+///
+/// colliders only work in f32, which can't place things accurately far from the origin, so the collider is kept
+/// relative to an f64 anchor near it and queries are moved next to the anchor before they are checked
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct AnchoredCollider<C> {
+    pub anchor: DVector3,
+    pub collider: C,
+}
+
+impl<C> AnchoredCollider<C> {
+    pub fn new(anchor: impl Into<DVector3>, collider: C) -> Self {
+        AnchoredCollider {
+            anchor: anchor.into(),
+            collider
+        }
+    }
+
+    /// the point relative to the anchor, which is what the collider works with
+    pub fn to_local(&self, point: impl Into<DVector3>) -> Vector3 {
+        (point.into() - self.anchor).to_single()
+    }
+
+    /// a point relative to the anchor back in the world
+    pub fn to_world(&self, point: Vector3) -> DVector3 {
+        self.anchor + DVector3::from(point)
+    }
+}
+
+impl<C: Collider> AnchoredCollider<C> {
+    /// the positions in the hit are relative to the anchor, `to_world` puts them back in the world
+    pub fn check_ray(&self, root_position: impl Into<DVector3>, direction: impl Into<DVector3>, max_distance: Option<f64>) -> Option<RayHitInfo> {
+        let direction: DVector3 = direction.into();
+        self.collider.check_ray(self.to_local(root_position), direction.normalised().to_single(), max_distance.map(|max| max as f32))
+    }
+}
+
+impl<C: PointQuery> AnchoredCollider<C> {
+    pub fn closest_point(&self, point: impl Into<DVector3>) -> DVector3 {
+        self.to_world(self.collider.closest_point(self.to_local(point)))
+    }
+
+    pub fn contains_point(&self, point: impl Into<DVector3>) -> bool {
+        self.collider.contains_point(self.to_local(point))
+    }
+
+    pub fn distance_to_point(&self, point: impl Into<DVector3>) -> f64 {
+        let point = point.into();
+        (self.closest_point(point) - point).magnitude()
+    }
+}
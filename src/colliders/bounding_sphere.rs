@@ -174,6 +174,13 @@ impl BoundingSphere {
         }
     }
 
+    /// the closest point in the sphere to the given point, which is the point itself if it is inside
+    pub fn closest_point(&self, point: impl Into<Vector3>) -> Vector3 {
+        let offset = point.into() - self.centre;
+        if offset.sqr_magnitude() <= self.radius * self.radius {return self.centre + offset;}
+        self.centre + offset.normalised() * self.radius
+    }
+
     pub fn is_intersecting_sphere(&self, other: BoundingSphere) -> bool {
         let distance_between = (self.centre - other.centre).magnitude();
         let radii_sum = self.radius + other.radius;
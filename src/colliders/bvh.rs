@@ -0,0 +1,248 @@
+use super::{Vector3, AABoundingBox, Bounded, Collider, Ray, RayHitInfo, TriangleCollider};
+
+
+// how many buckets the triangles are sorted into along an axis when looking for the best split
+const SAH_BUCKETS: usize = 12;
+// the cost of checking a node's box compared to checking a triangle
+const TRAVERSAL_COST: f32 = 1.0;
+const MAX_LEAF_SIZE: usize = 8;
+
+
+/// a node is either a leaf holding `count` triangles from `start` in the hierarchy's triangle order,
+/// or a branch with a count of 0 and its two children at `start` and `start + 1`
+#[derive(Default, Debug, PartialEq, Clone, Copy)]
+struct BvhNode {
+    bounds: AABoundingBox,
+    start: usize,
+    count: usize,
+}
+
+/// a bounding volume hierarchy over a list of triangles, built with the surface area heuristic
+///
+/// the triangles themselves are not stored, only the order to visit them in
+#[derive(Default, Debug, PartialEq, Clone)]
+pub(super) struct Bvh {
+    nodes: Vec<BvhNode>,
+    order: Vec<usize>,
+}
+
+
+impl Bvh {
+    pub fn new(tris: &[TriangleCollider]) -> Self {
+        let mut bvh = Bvh {
+            nodes: Vec::with_capacity(tris.len() * 2),
+            order: (0..tris.len()).collect()
+        };
+        if tris.is_empty() {return bvh;}
+
+        let bounds: Vec<AABoundingBox> = tris.iter().map(|tri| tri.bounding_box()).collect();
+        let centres: Vec<Vector3> = bounds.iter().map(|bound| bound.centre()).collect();
+
+        bvh.nodes.push(BvhNode {bounds: AABoundingBox::default(), start: 0, count: tris.len()});
+        bvh.subdivide(0, &bounds, &centres);
+        bvh
+    }
+
+    // fits the node's box around its triangles and splits it if that is cheaper to check than the triangles
+    fn subdivide(&mut self, node_index: usize, bounds: &[AABoundingBox], centres: &[Vector3]) {
+        let BvhNode {start, count, ..} = self.nodes[node_index];
+        let triangles = &self.order[start..start + count];
+
+        let node_bounds = triangles.iter().skip(1).fold(bounds[triangles[0]], |total, &tri| total.merged(bounds[tri]));
+        self.nodes[node_index].bounds = node_bounds;
+        if count <= 2 {return;}
+
+        let centre_bounds = AABoundingBox::from_points(triangles.iter().map(|&tri| centres[tri]).collect());
+        let Some((axis, split, cost)) = best_split(triangles, bounds, centres, centre_bounds) else {return;};
+
+        // a leaf costs checking every triangle, so only split when that is worse
+        let leaf_cost = count as f32;
+        let split_cost = TRAVERSAL_COST + cost / node_bounds.surface_area().max(f32::EPSILON);
+        if split_cost >= leaf_cost && count <= MAX_LEAF_SIZE {return;}
+
+        // put the triangles before the split first
+        let mut middle = start;
+        for i in start..start + count {
+            if axis_value(centres[self.order[i]], axis) < split {
+                self.order.swap(i, middle);
+                middle += 1;
+            }
+        }
+        if middle == start || middle == start + count {return;}
+
+        let left = self.nodes.len();
+        self.nodes.push(BvhNode {bounds: AABoundingBox::default(), start, count: middle - start});
+        self.nodes.push(BvhNode {bounds: AABoundingBox::default(), start: middle, count: start + count - middle});
+        self.nodes[node_index] = BvhNode {bounds: node_bounds, start: left, count: 0};
+
+        self.subdivide(left, bounds, centres);
+        self.subdivide(left + 1, bounds, centres);
+    }
+
+    /// the closest hit on any of the triangles, which should be the same list the hierarchy was built with
+    pub fn check_ray(&self, tris: &[TriangleCollider], ray: &Ray) -> Option<RayHitInfo> {
+        if self.nodes.is_empty() {return None;}
+
+        let mut ray = *ray;
+        let mut closest: Option<RayHitInfo> = None;
+
+        let mut stack = vec![0];
+        while let Some(node_index) = stack.pop() {
+            let node = self.nodes[node_index];
+            if ray.box_distance(&node.bounds).is_none() {continue;}
+
+            if node.count > 0 {
+                for &tri in &self.order[node.start..node.start + node.count] {
+                    if let Some(hit) = tris[tri].cast_ray(&ray) {
+                        // anything further than this hit can be skipped from now on
+                        ray.t_max = hit.hit_distance;
+                        closest = Some(hit);
+                    }
+                }
+                continue;
+            }
+
+            // visit the nearer child first so the further one can be skipped once something is hit
+            let (left, right) = (node.start, node.start + 1);
+            let left_dist = ray.box_distance(&self.nodes[left].bounds).unwrap_or(f32::INFINITY);
+            let right_dist = ray.box_distance(&self.nodes[right].bounds).unwrap_or(f32::INFINITY);
+            if left_dist < right_dist {
+                stack.push(right);
+                stack.push(left);
+            } else {
+                stack.push(left);
+                stack.push(right);
+            }
+        }
+        closest
+    }
+
+    /// every hit on the triangles, in no particular order
+    pub fn check_ray_all(&self, tris: &[TriangleCollider], ray: &Ray) -> Vec<RayHitInfo> {
+        let mut hits = Vec::new();
+        let mut stack: Vec<usize> = if self.nodes.is_empty() {Vec::new()} else {vec![0]};
+        while let Some(node_index) = stack.pop() {
+            let node = self.nodes[node_index];
+            if ray.box_distance(&node.bounds).is_none() {continue;}
+
+            if node.count > 0 {
+                hits.extend(self.order[node.start..node.start + node.count].iter().filter_map(|&tri| tris[tri].cast_ray(ray)));
+            } else {
+                stack.extend([node.start, node.start + 1]);
+            }
+        }
+        hits
+    }
+
+    /// the closest point on any of the triangles, skipping boxes further away than the closest point found so far
+    pub fn closest_point(&self, tris: &[TriangleCollider], point: Vector3) -> Option<Vector3> {
+        let mut closest: Option<(Vector3, f32)> = None;
+        let mut stack: Vec<usize> = if self.nodes.is_empty() {Vec::new()} else {vec![0]};
+
+        while let Some(node_index) = stack.pop() {
+            let node = self.nodes[node_index];
+            let box_dist = (node.bounds.closest_point(point) - point).sqr_magnitude();
+            if closest.is_some_and(|(_, best)| box_dist > best) {continue;}
+
+            if node.count > 0 {
+                for &tri in &self.order[node.start..node.start + node.count] {
+                    let candidate = tris[tri].closest_point(point);
+                    let dist = (candidate - point).sqr_magnitude();
+                    if closest.is_none_or(|(_, best)| dist < best) {closest = Some((candidate, dist));}
+                }
+                continue;
+            }
+
+            // the nearer child goes on the stack last so it is looked at first
+            let (left, right) = (node.start, node.start + 1);
+            let left_dist = (self.nodes[left].bounds.closest_point(point) - point).sqr_magnitude();
+            let right_dist = (self.nodes[right].bounds.closest_point(point) - point).sqr_magnitude();
+            if left_dist < right_dist {
+                stack.extend([right, left]);
+            } else {
+                stack.extend([left, right]);
+            }
+        }
+        closest.map(|(point, _)| point)
+    }
+
+    /// every triangle with bounds overlapping the box
+    pub fn query_box(&self, bounds: AABoundingBox, mut found: impl FnMut(usize)) {
+        let mut stack: Vec<usize> = if self.nodes.is_empty() {Vec::new()} else {vec![0]};
+        while let Some(node_index) = stack.pop() {
+            let node = self.nodes[node_index];
+            if !node.bounds.is_intersecting_box(bounds) {continue;}
+
+            if node.count > 0 {
+                self.order[node.start..node.start + node.count].iter().for_each(|&tri| found(tri));
+            } else {
+                stack.extend([node.start, node.start + 1]);
+            }
+        }
+    }
+
+    /// how many levels deep the hierarchy goes
+    pub fn depth(&self) -> usize {
+        fn node_depth(nodes: &[BvhNode], index: usize) -> usize {
+            let node = nodes[index];
+            if node.count > 0 {return 1;}
+            1 + node_depth(nodes, node.start).max(node_depth(nodes, node.start + 1))
+        }
+        if self.nodes.is_empty() {0} else {node_depth(&self.nodes, 0)}
+    }
+}
+
+
+fn axis_value(vector: Vector3, axis: usize) -> f32 {
+    match axis {
+        0 => vector.x,
+        1 => vector.y,
+        _ => vector.z,
+    }
+}
+
+// sorts the triangle centres into buckets along each axis and finds the bucket boundary with the lowest surface area cost,
+// giving the axis, the position of the split on it and the cost
+fn best_split(triangles: &[usize], bounds: &[AABoundingBox], centres: &[Vector3], centre_bounds: AABoundingBox) -> Option<(usize, f32, f32)> {
+    let mut best: Option<(usize, f32, f32)> = None;
+
+    for axis in 0..3 {
+        let (min, max) = (axis_value(centre_bounds.min_corner, axis), axis_value(centre_bounds.max_corner, axis));
+        if max - min <= f32::EPSILON {continue;}
+
+        let scale = SAH_BUCKETS as f32 / (max - min);
+        let mut buckets: [(Option<AABoundingBox>, usize); SAH_BUCKETS] = [(None, 0); SAH_BUCKETS];
+        for &tri in triangles {
+            let bucket = (((axis_value(centres[tri], axis) - min) * scale) as usize).min(SAH_BUCKETS - 1);
+            let (bucket_bounds, count) = &mut buckets[bucket];
+            *bucket_bounds = Some(bucket_bounds.map_or(bounds[tri], |total| total.merged(bounds[tri])));
+            *count += 1;
+        }
+
+        // the area and count of everything right of each boundary, then sweep from the left
+        let mut right_costs = [0.0; SAH_BUCKETS];
+        let (mut right_bounds, mut right_count) = (None::<AABoundingBox>, 0);
+        for i in (1..SAH_BUCKETS).rev() {
+            if let Some(bucket_bounds) = buckets[i].0 {
+                right_bounds = Some(right_bounds.map_or(bucket_bounds, |total| total.merged(bucket_bounds)));
+            }
+            right_count += buckets[i].1;
+            right_costs[i] = right_bounds.map_or(0.0, |total| total.surface_area()) * right_count as f32;
+        }
+
+        let (mut left_bounds, mut left_count) = (None::<AABoundingBox>, 0);
+        for i in 1..SAH_BUCKETS {
+            if let Some(bucket_bounds) = buckets[i - 1].0 {
+                left_bounds = Some(left_bounds.map_or(bucket_bounds, |total| total.merged(bucket_bounds)));
+            }
+            left_count += buckets[i - 1].1;
+            if left_count == 0 || left_count == triangles.len() {continue;}
+
+            let cost = left_bounds.map_or(0.0, |total| total.surface_area()) * left_count as f32 + right_costs[i];
+            if best.is_none_or(|(_, _, best_cost)| cost < best_cost) {
+                best = Some((axis, min + i as f32 / scale, cost));
+            }
+        }
+    }
+    best
+}
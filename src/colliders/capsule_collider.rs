@@ -0,0 +1,104 @@
+use super::{Vector3, AABoundingBox, BoundingSphere, Collider, RayHitInfo, solve_quadratic};
+use super::cylinder_collider::{ray_infinite_cylinder, nearest_hit};
+
+
+/// a solid capsule, all the points within the radius of the line segment from start to end
+#[derive(Default, Debug, PartialEq, Clone, Copy)]
+pub struct CapsuleCollider {
+    pub start: Vector3,
+    pub end: Vector3,
+    pub radius: f32,
+}
+
+impl CapsuleCollider {
+    pub fn new(start: impl Into<Vector3>, end: impl Into<Vector3>, radius: f32) -> Self {
+        CapsuleCollider {
+            start: start.into(),
+            end: end.into(),
+            radius
+        }
+    }
+
+    /// the closest point on the line segment through the middle of the capsule
+    pub fn closest_point_on_segment(&self, point: impl Into<Vector3>) -> Vector3 {
+        let point = point.into();
+        let line = self.end - self.start;
+        let length = line.sqr_magnitude();
+        if length == 0.0 {return self.start;}
+        let t = ((point - self.start).dot(line) / length).clamp(0.0, 1.0);
+        self.start + line * t
+    }
+
+    /// the closest point in the capsule to the given point, which is the point itself if it is inside
+    pub fn closest_point(&self, point: impl Into<Vector3>) -> Vector3 {
+        let point = point.into();
+        let centre = self.closest_point_on_segment(point);
+        let offset = point - centre;
+        if offset.sqr_magnitude() <= self.radius * self.radius {return point;}
+        centre + offset.normalised() * self.radius
+    }
+
+    pub fn contains_point(&self, point: impl Into<Vector3>) -> bool {
+        let point = point.into();
+        (point - self.closest_point_on_segment(point)).sqr_magnitude() <= self.radius * self.radius
+    }
+
+    pub fn bounding_box(&self) -> AABoundingBox {
+        let radius = Vector3::ONE * self.radius;
+        AABoundingBox::from_points(vec![self.start - radius, self.start + radius, self.end - radius, self.end + radius])
+    }
+
+    pub fn bounding_sphere(&self) -> BoundingSphere {
+        BoundingSphere::new((self.start + self.end) * 0.5, (self.end - self.start).magnitude() * 0.5 + self.radius)
+    }
+
+    pub fn is_intersecting_sphere(&self, sphere: BoundingSphere) -> bool {
+        let radii = self.radius + sphere.radius;
+        (self.closest_point_on_segment(sphere.centre) - sphere.centre).sqr_magnitude() <= radii * radii
+    }
+}
+
+
+// the distances along a ray where it crosses a sphere
+fn ray_sphere(root_position: Vector3, direction: Vector3, centre: Vector3, radius: f32) -> Vec<f32> {
+    let offset = root_position - centre;
+    let (r1, r2) = solve_quadratic(1.0, 2.0 * direction.dot(offset), offset.dot(offset) - radius * radius);
+    [r1, r2].into_iter().flatten().collect()
+}
+
+
+impl Collider for CapsuleCollider {
+    /// the capsule is checked as a cylinder for the middle and a sphere for each end
+    fn check_ray(
+        &self,
+        root_position: impl Into<Vector3>,
+        direction: impl Into<Vector3>,
+        max_distance: Option<f32>,
+    ) -> Option<RayHitInfo> {
+        let (root_position, direction): (Vector3, Vector3) = (root_position.into(), direction.into());
+        let direction = direction.normalised();
+
+        if self.contains_point(root_position) {return Some(RayHitInfo::new(root_position, 0.0, Vector3::ZERO));}
+
+        let mut hits = Vec::new();
+        let line = self.end - self.start;
+        if line.sqr_magnitude() > 0.0 {
+            let (axis, length) = (line.normalised(), line.magnitude());
+            for dist in ray_infinite_cylinder(root_position, direction, self.start, axis, self.radius) {
+                let offset = root_position + direction * dist - self.start;
+                let along = offset.dot(axis);
+                if (0.0..=length).contains(&along) {
+                    hits.push((dist, (offset - axis * along).normalised()));
+                }
+            }
+        }
+
+        for centre in [self.start, self.end] {
+            for dist in ray_sphere(root_position, direction, centre, self.radius) {
+                hits.push((dist, (root_position + direction * dist - centre).normalised()));
+            }
+        }
+
+        nearest_hit(root_position, direction, hits, max_distance)
+    }
+}
@@ -0,0 +1,330 @@
+use super::{
+    Vector3, AABoundingBox, BoundingSphere, CapsuleCollider, Collider, ConeCollider, CylinderCollider, Intersects, MeshCollider,
+    OrientedBoundingBox, PlaneCollider, QuadCollider, Ray, RayHitInfo, TriangleCollider, dynamic_tree::DynamicTree
+};
+
+
+// how far the boxes kept in the tree are grown past the colliders, so small movements don't need the tree changing
+const DEFAULT_MARGIN: f32 = 0.1;
+
+
+/// a collider with a finite size that can be boxed
+pub trait Bounded {
+    fn bounding_box(&self) -> AABoundingBox;
+}
+
+/// a collider that can be stored in a `CollisionWorld`
+///
+/// `Collider` takes generic arguments so can't be used as a trait object, this is implemented for everything that is both a `Collider` and `Bounded`
+pub trait WorldCollider {
+    fn bounds(&self) -> AABoundingBox;
+    fn check_ray_dyn(&self, root_position: Vector3, direction: Vector3, max_distance: Option<f32>) -> Option<RayHitInfo>;
+}
+
+impl<T: Collider + Bounded> WorldCollider for T {
+    fn bounds(&self) -> AABoundingBox {
+        self.bounding_box()
+    }
+
+    fn check_ray_dyn(&self, root_position: Vector3, direction: Vector3, max_distance: Option<f32>) -> Option<RayHitInfo> {
+        self.check_ray(root_position, direction, max_distance)
+    }
+}
+
+
+/// refers to a collider in a `CollisionWorld`, which stops being valid once that collider is removed even if the space is reused
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ColliderHandle {
+    index: u32,
+    generation: u32,
+}
+
+struct Entry {
+    collider: Box<dyn WorldCollider>,
+    bounds: AABoundingBox,
+    leaf: usize,
+}
+
+#[derive(Default)]
+struct Slot {
+    generation: u32,
+    entry: Option<Entry>,
+}
+
+
+/// a set of colliders kept in a dynamic AABB tree for quickly finding which of them a ray or shape could touch
+pub struct CollisionWorld {
+    slots: Vec<Slot>,
+    free_slots: Vec<usize>,
+    tree: DynamicTree,
+    margin: f32,
+    len: usize,
+}
+
+impl Default for CollisionWorld {
+    fn default() -> Self {
+        CollisionWorld::new()
+    }
+}
+
+
+impl CollisionWorld {
+    pub fn new() -> Self {
+        CollisionWorld::with_margin(DEFAULT_MARGIN)
+    }
+
+    /// the margin is how much bigger the boxes kept in the tree are than the colliders,
+    /// bigger margins mean less work updating moving colliders but more false positives to check
+    pub fn with_margin(margin: f32) -> Self {
+        CollisionWorld {
+            slots: Vec::new(),
+            free_slots: Vec::new(),
+            tree: DynamicTree::default(),
+            margin,
+            len: 0
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// how many levels deep the tree goes
+    pub fn tree_height(&self) -> usize {
+        self.tree.height()
+    }
+
+    pub fn insert(&mut self, collider: impl WorldCollider + 'static) -> ColliderHandle {
+        let index = self.free_slots.pop().unwrap_or_else(|| {
+            self.slots.push(Slot::default());
+            self.slots.len() - 1
+        });
+
+        let bounds = collider.bounds();
+        let leaf = self.tree.insert(self.fatten(bounds), index);
+        let slot = &mut self.slots[index];
+        slot.entry = Some(Entry {collider: Box::new(collider), bounds, leaf});
+        self.len += 1;
+
+        ColliderHandle {index: index as u32, generation: slot.generation}
+    }
+
+    /// takes the collider out of the world, None if the handle is no longer valid
+    pub fn remove(&mut self, handle: ColliderHandle) -> Option<Box<dyn WorldCollider>> {
+        self.get_entry(handle)?;
+        let slot = &mut self.slots[handle.index as usize];
+        let entry = slot.entry.take()?;
+        slot.generation = slot.generation.wrapping_add(1);
+
+        self.tree.remove(entry.leaf);
+        self.free_slots.push(handle.index as usize);
+        self.len -= 1;
+        Some(entry.collider)
+    }
+
+    pub fn contains(&self, handle: ColliderHandle) -> bool {
+        self.get_entry(handle).is_some()
+    }
+
+    pub fn get(&self, handle: ColliderHandle) -> Option<&dyn WorldCollider> {
+        self.get_entry(handle).map(|entry| entry.collider.as_ref())
+    }
+
+    /// the handles of every collider in the world
+    pub fn handles(&self) -> impl Iterator<Item = ColliderHandle> + '_ {
+        self.slots.iter().enumerate()
+            .filter(|(_, slot)| slot.entry.is_some())
+            .map(|(index, slot)| ColliderHandle {index: index as u32, generation: slot.generation})
+    }
+
+    /// swaps the collider for a moved or changed one, the tree is only changed if it has moved outside of its margin.
+    /// Gives back false if the handle is no longer valid
+    pub fn update(&mut self, handle: ColliderHandle, collider: impl WorldCollider + 'static) -> bool {
+        if self.get_entry(handle).is_none() {return false;}
+
+        let bounds = collider.bounds();
+        let fat_bounds = self.fatten(bounds);
+        let entry = self.slots[handle.index as usize].entry.as_mut().unwrap();
+        entry.collider = Box::new(collider);
+        entry.bounds = bounds;
+
+        let kept_bounds = self.tree.get_bounds(entry.leaf);
+        if !kept_bounds.contains_points(vec![bounds.min_corner, bounds.max_corner]) {
+            self.tree.remove(entry.leaf);
+            entry.leaf = self.tree.insert(fat_bounds, handle.index as usize);
+        }
+        true
+    }
+
+    /// every collider with bounds overlapping the box
+    pub fn query_box(&self, bounds: AABoundingBox) -> Vec<ColliderHandle> {
+        let mut found = Vec::new();
+        self.tree.query_box(bounds, |index| {
+            if self.entry_at(index).bounds.is_intersecting_box(bounds) {found.push(self.handle_at(index));}
+        });
+        found
+    }
+
+    /// every collider with bounds overlapping the sphere
+    pub fn query_sphere(&self, sphere: BoundingSphere) -> Vec<ColliderHandle> {
+        let mut found = Vec::new();
+        self.tree.query_box(sphere.bounding_box(), |index| {
+            if sphere.intersects(&self.entry_at(index).bounds) {found.push(self.handle_at(index));}
+        });
+        found
+    }
+
+    /// every pair of colliders with overlapping bounds, each pair only given once with the lower handle first
+    pub fn overlapping_pairs(&self) -> Vec<(ColliderHandle, ColliderHandle)> {
+        let mut pairs = Vec::new();
+        for (index, slot) in self.slots.iter().enumerate() {
+            let Some(entry) = &slot.entry else {continue;};
+            self.tree.query_box(entry.bounds, |other| {
+                if other > index && self.entry_at(other).bounds.is_intersecting_box(entry.bounds) {
+                    pairs.push((self.handle_at(index), self.handle_at(other)));
+                }
+            });
+        }
+        pairs.sort();
+        pairs
+    }
+
+    /// every collider the ray hits, sorted from nearest to furthest
+    pub fn raycast_all(&self, root_position: impl Into<Vector3>, direction: impl Into<Vector3>, max_distance: Option<f32>) -> Vec<(ColliderHandle, RayHitInfo)> {
+        let (root_position, direction): (Vector3, Vector3) = (root_position.into(), direction.into());
+        let direction = direction.normalised();
+
+        let mut hits = Vec::new();
+        self.tree.query_ray(&Ray::with_range(root_position, direction, 0.0, max_distance.unwrap_or(f32::INFINITY)), |index| {
+            if let Some(hit) = self.entry_at(index).collider.check_ray_dyn(root_position, direction, max_distance) {
+                hits.push((self.handle_at(index), hit));
+            }
+        });
+        hits.sort_by(|(_, a), (_, b)| a.hit_distance.total_cmp(&b.hit_distance));
+        hits
+    }
+
+    /// the nearest collider the ray hits, colliders behind the nearest hit found so far aren't checked
+    pub fn raycast(&self, root_position: impl Into<Vector3>, direction: impl Into<Vector3>, max_distance: Option<f32>) -> Option<(ColliderHandle, RayHitInfo)> {
+        let (root_position, direction): (Vector3, Vector3) = (root_position.into(), direction.into());
+        let direction = direction.normalised();
+
+        let mut nearest: Option<(ColliderHandle, RayHitInfo)> = None;
+        self.tree.query_ray_nearest(&Ray::with_range(root_position, direction, 0.0, max_distance.unwrap_or(f32::INFINITY)), |index, ray| {
+            let hit = self.entry_at(index).collider.check_ray_dyn(root_position, direction, ray.max_distance())?;
+            if nearest.as_ref().is_some_and(|(_, best)| best.hit_distance <= hit.hit_distance) {return None;}
+            let dist = hit.hit_distance;
+            nearest = Some((self.handle_at(index), hit));
+            Some(dist)
+        });
+        nearest
+    }
+
+
+    fn fatten(&self, bounds: AABoundingBox) -> AABoundingBox {
+        let margin = Vector3::ONE * self.margin;
+        AABoundingBox::new(bounds.min_corner - margin, bounds.max_corner + margin)
+    }
+
+    fn get_entry(&self, handle: ColliderHandle) -> Option<&Entry> {
+        let slot = self.slots.get(handle.index as usize)?;
+        if slot.generation != handle.generation {return None;}
+        slot.entry.as_ref()
+    }
+
+    // the entry for an index found in the tree, which is always filled
+    fn entry_at(&self, index: usize) -> &Entry {
+        self.slots[index].entry.as_ref().unwrap()
+    }
+
+    fn handle_at(&self, index: usize) -> ColliderHandle {
+        ColliderHandle {index: index as u32, generation: self.slots[index].generation}
+    }
+}
+
+
+// bounds for the colliders
+impl Bounded for AABoundingBox {
+    fn bounding_box(&self) -> AABoundingBox {
+        *self
+    }
+}
+
+impl Bounded for BoundingSphere {
+    fn bounding_box(&self) -> AABoundingBox {
+        let radius = Vector3::ONE * self.radius;
+        AABoundingBox::new(self.centre - radius, self.centre + radius)
+    }
+}
+
+impl Bounded for OrientedBoundingBox {
+    fn bounding_box(&self) -> AABoundingBox {
+        OrientedBoundingBox::bounding_box(self)
+    }
+}
+
+impl Bounded for TriangleCollider {
+    fn bounding_box(&self) -> AABoundingBox {
+        let points = self.get_points();
+        AABoundingBox::from_points(vec![points.c0(), points.c1(), points.c2()])
+    }
+}
+
+impl Bounded for PlaneCollider {
+    fn bounding_box(&self) -> AABoundingBox {
+        self.get_bounds()
+    }
+}
+
+impl Bounded for QuadCollider {
+    fn bounding_box(&self) -> AABoundingBox {
+        QuadCollider::bounding_box(self)
+    }
+}
+
+impl Bounded for MeshCollider {
+    fn bounding_box(&self) -> AABoundingBox {
+        self.get_bounds()
+    }
+}
+
+impl Bounded for CapsuleCollider {
+    fn bounding_box(&self) -> AABoundingBox {
+        CapsuleCollider::bounding_box(self)
+    }
+}
+
+impl Bounded for CylinderCollider {
+    fn bounding_box(&self) -> AABoundingBox {
+        CylinderCollider::bounding_box(self)
+    }
+}
+
+impl Bounded for ConeCollider {
+    fn bounding_box(&self) -> AABoundingBox {
+        ConeCollider::bounding_box(self)
+    }
+}
+
+
+#[cfg(test)]
+mod collision_world_tests {
+    use super::{CollisionWorld, BoundingSphere};
+
+    #[test]
+    fn generation_wraps_test() {
+        let mut world = CollisionWorld::new();
+        let handle = world.insert(BoundingSphere::new([0, 0, 0], 1.0));
+        world.slots[handle.index as usize].generation = u32::MAX;
+        let handle = world.handles().next().unwrap();
+
+        assert!(world.remove(handle).is_some());
+        let reused = world.insert(BoundingSphere::new([0, 0, 0], 1.0));
+        assert_eq!((reused.index, reused.generation), (handle.index, 0));
+        assert!(!world.contains(handle));
+    }
+}
@@ -0,0 +1,140 @@
+use super::{Vector3, Vector2, AABoundingBox, BoundingSphere, Collider, RayHitInfo, solve_quadratic};
+use super::cylinder_collider::{ray_disc, nearest_hit};
+
+
+/// a solid cone, with a flat circular base and coming to a point at the apex
+#[derive(Default, Debug, PartialEq, Clone, Copy)]
+pub struct ConeCollider {
+    pub base: Vector3,
+    pub apex: Vector3,
+    pub radius: f32,
+}
+
+impl ConeCollider {
+    pub fn new(base: impl Into<Vector3>, apex: impl Into<Vector3>, radius: f32) -> Self {
+        ConeCollider {
+            base: base.into(),
+            apex: apex.into(),
+            radius
+        }
+    }
+
+    pub fn height(&self) -> f32 {
+        (self.apex - self.base).magnitude()
+    }
+
+    /// the direction from the base to the apex
+    pub fn axis(&self) -> Vector3 {
+        (self.apex - self.base).normalised()
+    }
+
+    pub fn bounding_box(&self) -> AABoundingBox {
+        let axis = self.axis();
+        let extent = Vector3::new(
+            (1.0 - axis.x * axis.x).max(0.0).sqrt(),
+            (1.0 - axis.y * axis.y).max(0.0).sqrt(),
+            (1.0 - axis.z * axis.z).max(0.0).sqrt(),
+        ) * self.radius;
+        AABoundingBox::from_points(vec![self.base - extent, self.base + extent, self.apex])
+    }
+
+    /// the smallest sphere around the cone, which is either the sphere around the base circle or the sphere through the base circle and the apex
+    pub fn bounding_sphere(&self) -> BoundingSphere {
+        let height = self.height();
+        if height <= self.radius {
+            return BoundingSphere::new(self.base, self.radius);
+        }
+        // the centre is on the axis, the same distance from the apex and the rim of the base
+        let along = (height * height - self.radius * self.radius) / (2.0 * height);
+        BoundingSphere::new(self.base + self.axis() * along, height - along)
+    }
+
+    // splits a point into how far it is along the axis from the base and how far it is from the axis
+    fn axis_coordinates(&self, point: Vector3) -> (Vector2, Vector3) {
+        let axis = self.axis();
+        let offset = point - self.base;
+        let along = offset.dot(axis);
+        let radial = offset - axis * along;
+        let radial_length = radial.magnitude();
+        let radial_direction = if radial_length > 0.0 {radial / radial_length} else {Vector3::ZERO};
+        (Vector2::new(along, radial_length), radial_direction)
+    }
+
+    pub fn contains_point(&self, point: impl Into<Vector3>) -> bool {
+        let (local, _) = self.axis_coordinates(point.into());
+        let height = self.height();
+        local.x >= 0.0 && local.x <= height && local.y <= self.radius * (1.0 - local.x / height)
+    }
+
+    /// the closest point in the cone to the given point, which is the point itself if it is inside
+    ///
+    /// the cone is a triangle when looked at side on, so this finds the closest point on the base or side of that triangle
+    pub fn closest_point(&self, point: impl Into<Vector3>) -> Vector3 {
+        let point = point.into();
+        if self.contains_point(point) {return point;}
+
+        let (local, radial_direction) = self.axis_coordinates(point);
+        let closest_on_segment = |start: Vector2, end: Vector2| {
+            let line = end - start;
+            let t = ((local - start).dot(line) / line.dot(line)).clamp(0.0, 1.0);
+            start + line * t
+        };
+        let base = closest_on_segment(Vector2::new(0.0, 0.0), Vector2::new(0.0, self.radius));
+        let side = closest_on_segment(Vector2::new(0.0, self.radius), Vector2::new(self.height(), 0.0));
+        let closest = if (base - local).sqr_magnitude() < (side - local).sqr_magnitude() {base} else {side};
+
+        self.base + self.axis() * closest.x + radial_direction * closest.y
+    }
+
+    pub fn is_intersecting_sphere(&self, sphere: BoundingSphere) -> bool {
+        (self.closest_point(sphere.centre) - sphere.centre).sqr_magnitude() <= sphere.radius * sphere.radius
+    }
+}
+
+
+impl Collider for ConeCollider {
+    fn check_ray(
+        &self,
+        root_position: impl Into<Vector3>,
+        direction: impl Into<Vector3>,
+        max_distance: Option<f32>,
+    ) -> Option<RayHitInfo> {
+        let (root_position, direction): (Vector3, Vector3) = (root_position.into(), direction.into());
+        let direction = direction.normalised();
+
+        if self.contains_point(root_position) {return Some(RayHitInfo::new(root_position, 0.0, Vector3::ZERO));}
+
+        // the side of the cone, with the axis pointing from the apex into the cone
+        let (axis, height) = (-self.axis(), self.height());
+        let cos_sqr = height * height / (height * height + self.radius * self.radius);
+        let offset = root_position - self.apex;
+        let (dir_along, offset_along) = (direction.dot(axis), offset.dot(axis));
+
+        let a = dir_along * dir_along - cos_sqr;
+        let b = 2.0 * (dir_along * offset_along - cos_sqr * direction.dot(offset));
+        let c = offset_along * offset_along - cos_sqr * offset.dot(offset);
+        let side_hits: Vec<f32> = if a.abs() <= f32::EPSILON {
+            // parallel to the side of the cone so there is at most one hit
+            if b == 0.0 {Vec::new()} else {vec![-c / b]}
+        } else {
+            let (r1, r2) = solve_quadratic(a, b, c);
+            [r1, r2].into_iter().flatten().collect()
+        };
+
+        let mut hits = Vec::new();
+        for dist in side_hits {
+            let from_apex = root_position + direction * dist - self.apex;
+            // the equation also includes the mirrored cone on the other side of the apex
+            let along = from_apex.dot(axis);
+            if (0.0..=height).contains(&along) {
+                hits.push((dist, (from_apex * cos_sqr - axis * along).normalised()));
+            }
+        }
+
+        if let Some(dist) = ray_disc(root_position, direction, self.base, axis, self.radius) {
+            hits.push((dist, axis));
+        }
+
+        nearest_hit(root_position, direction, hits, max_distance)
+    }
+}
@@ -0,0 +1,42 @@
+use super::{Vector3, AABoundingBox, BoundingSphere};
+
+
+/// the convex hull of a set of points, anything inside the hull is treated as solid
+///
+/// the hull faces are never built, the shape is only used through its support points so the points don't need to be
+/// reduced to the hull first
+#[derive(Default, Debug, PartialEq, Clone)]
+pub struct ConvexHull {
+    points: Vec<Vector3>,
+    bounds: AABoundingBox,
+}
+
+impl ConvexHull {
+    pub fn new(points: Vec<Vector3>) -> Self {
+        let bounds = AABoundingBox::from_points(points.clone());
+        ConvexHull {
+            points,
+            bounds
+        }
+    }
+
+    pub fn get_points(&self) -> &[Vector3] {
+        &self.points
+    }
+
+    pub fn get_bounds(&self) -> AABoundingBox {
+        self.bounds
+    }
+
+    pub fn bounding_sphere(&self) -> BoundingSphere {
+        BoundingSphere::from_points(self.points.clone())
+    }
+
+    /// moves every point of the hull
+    pub fn translate(&mut self, offset: impl Into<Vector3>) {
+        let offset = offset.into();
+        self.points.iter_mut().for_each(|point| *point += offset);
+        self.bounds.min_corner += offset;
+        self.bounds.max_corner += offset;
+    }
+}
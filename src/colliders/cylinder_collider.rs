@@ -0,0 +1,142 @@
+use super::{Vector3, AABoundingBox, BoundingSphere, Collider, RayHitInfo, solve_quadratic};
+
+
+/// a solid cylinder with flat caps, going from the centre of the start cap to the centre of the end cap
+#[derive(Default, Debug, PartialEq, Clone, Copy)]
+pub struct CylinderCollider {
+    pub start: Vector3,
+    pub end: Vector3,
+    pub radius: f32,
+}
+
+impl CylinderCollider {
+    pub fn new(start: impl Into<Vector3>, end: impl Into<Vector3>, radius: f32) -> Self {
+        CylinderCollider {
+            start: start.into(),
+            end: end.into(),
+            radius
+        }
+    }
+
+    pub fn height(&self) -> f32 {
+        (self.end - self.start).magnitude()
+    }
+
+    pub fn axis(&self) -> Vector3 {
+        (self.end - self.start).normalised()
+    }
+
+    pub fn bounding_box(&self) -> AABoundingBox {
+        let axis = self.axis();
+        // how far the cap circles reach along each world axis
+        let extent = Vector3::new(
+            (1.0 - axis.x * axis.x).max(0.0).sqrt(),
+            (1.0 - axis.y * axis.y).max(0.0).sqrt(),
+            (1.0 - axis.z * axis.z).max(0.0).sqrt(),
+        ) * self.radius;
+        AABoundingBox::from_points(vec![self.start - extent, self.start + extent, self.end - extent, self.end + extent])
+    }
+
+    pub fn bounding_sphere(&self) -> BoundingSphere {
+        let half_height = self.height() * 0.5;
+        BoundingSphere::new((self.start + self.end) * 0.5, (half_height * half_height + self.radius * self.radius).sqrt())
+    }
+
+    /// the closest point in the cylinder to the given point, which is the point itself if it is inside
+    pub fn closest_point(&self, point: impl Into<Vector3>) -> Vector3 {
+        let point = point.into();
+        let axis = self.axis();
+        let offset = point - self.start;
+        let along = offset.dot(axis).clamp(0.0, self.height());
+        let radial = offset - axis * offset.dot(axis);
+        let radial_length = radial.magnitude();
+
+        let radial = if radial_length > self.radius {radial * (self.radius / radial_length)} else {radial};
+        self.start + axis * along + radial
+    }
+
+    pub fn contains_point(&self, point: impl Into<Vector3>) -> bool {
+        let offset = point.into() - self.start;
+        let axis = self.axis();
+        let along = offset.dot(axis);
+        along >= 0.0 && along <= self.height() && (offset - axis * along).sqr_magnitude() <= self.radius * self.radius
+    }
+
+    pub fn is_intersecting_sphere(&self, sphere: BoundingSphere) -> bool {
+        (self.closest_point(sphere.centre) - sphere.centre).sqr_magnitude() <= sphere.radius * sphere.radius
+    }
+}
+
+
+/// the distances along a ray where it crosses an infinitely long cylinder, with the ray direction normalised
+pub(super) fn ray_infinite_cylinder(root_position: Vector3, direction: Vector3, start: Vector3, axis: Vector3, radius: f32) -> Vec<f32> {
+    let offset = root_position - start;
+    let flat_direction = direction - axis * direction.dot(axis);
+    let flat_offset = offset - axis * offset.dot(axis);
+
+    let a = flat_direction.dot(flat_direction);
+    // parallel to the axis, so it either never crosses the side or is always on it
+    if a <= f32::EPSILON {return Vec::new();}
+    let b = 2.0 * flat_offset.dot(flat_direction);
+    let c = flat_offset.dot(flat_offset) - radius * radius;
+
+    let (r1, r2) = solve_quadratic(a, b, c);
+    [r1, r2].into_iter().flatten().collect()
+}
+
+/// the distance along a ray where it hits a disc, with the ray direction normalised
+pub(super) fn ray_disc(root_position: Vector3, direction: Vector3, centre: Vector3, normal: Vector3, radius: f32) -> Option<f32> {
+    let facing = direction.dot(normal);
+    if facing == 0.0 {return None;}
+    let dist = (centre - root_position).dot(normal) / facing;
+    let hit = root_position + direction * dist;
+    if (hit - centre).sqr_magnitude() > radius * radius {return None;}
+    Some(dist)
+}
+
+/// picks the closest hit in front of the ray that is within the max distance
+pub(super) fn nearest_hit(root_position: Vector3, direction: Vector3, hits: Vec<(f32, Vector3)>, max_distance: Option<f32>) -> Option<RayHitInfo> {
+    let (dist, normal) = hits.into_iter()
+        .filter(|(dist, _)| *dist >= 0.0)
+        .min_by(|(a, _), (b, _)| a.total_cmp(b))?;
+
+    if max_distance.is_some_and(|max| dist > max) {return None;}
+    Some(RayHitInfo::new(root_position + direction * dist, dist, normal))
+}
+
+
+impl Collider for CylinderCollider {
+    fn check_ray(
+        &self,
+        root_position: impl Into<Vector3>,
+        direction: impl Into<Vector3>,
+        max_distance: Option<f32>,
+    ) -> Option<RayHitInfo> {
+        let (root_position, direction): (Vector3, Vector3) = (root_position.into(), direction.into());
+        let direction = direction.normalised();
+
+        if self.contains_point(root_position) {return Some(RayHitInfo::new(root_position, 0.0, Vector3::ZERO));}
+
+        let (axis, height) = (self.axis(), self.height());
+        let mut hits = Vec::new();
+
+        // the side, only where it is between the caps
+        for dist in ray_infinite_cylinder(root_position, direction, self.start, axis, self.radius) {
+            let offset = root_position + direction * dist - self.start;
+            let along = offset.dot(axis);
+            if (0.0..=height).contains(&along) {
+                hits.push((dist, (offset - axis * along).normalised()));
+            }
+        }
+
+        // the caps
+        if let Some(dist) = ray_disc(root_position, direction, self.start, -axis, self.radius) {
+            hits.push((dist, -axis));
+        }
+        if let Some(dist) = ray_disc(root_position, direction, self.end, axis, self.radius) {
+            hits.push((dist, axis));
+        }
+
+        nearest_hit(root_position, direction, hits, max_distance)
+    }
+}
@@ -0,0 +1,231 @@
+use super::{AABoundingBox, Ray};
+
+
+// a node is a leaf when its height is 0, then `item` is what it holds and its children are unused
+#[derive(Default, Debug, Clone, Copy)]
+struct TreeNode {
+    bounds: AABoundingBox,
+    parent: Option<usize>,
+    children: [usize; 2],
+    item: usize,
+    height: usize,
+}
+
+impl TreeNode {
+    fn is_leaf(&self) -> bool {
+        self.height == 0
+    }
+}
+
+
+/// a bounding volume hierarchy that can have boxes added, removed and moved without being rebuilt,
+/// kept balanced with tree rotations the same way as Box2D's dynamic tree
+#[derive(Default, Debug, Clone)]
+pub(super) struct DynamicTree {
+    nodes: Vec<TreeNode>,
+    free_nodes: Vec<usize>,
+    root: Option<usize>,
+}
+
+
+impl DynamicTree {
+    /// adds the item with the given bounds, giving back the leaf it is stored in
+    pub fn insert(&mut self, bounds: AABoundingBox, item: usize) -> usize {
+        let leaf = self.allocate(TreeNode {bounds, item, ..Default::default()});
+        let Some(root) = self.root else {
+            self.root = Some(leaf);
+            return leaf;
+        };
+
+        // walk down to the cheapest sibling, going by how much the new box would grow the surface area of each branch
+        let mut index = root;
+        while !self.nodes[index].is_leaf() {
+            let node = self.nodes[index];
+            let combined_area = node.bounds.merged(bounds).surface_area();
+            let cost = 2.0 * combined_area;
+            let inherited_cost = 2.0 * (combined_area - node.bounds.surface_area());
+
+            let child_cost = |child: usize| {
+                let child = &self.nodes[child];
+                let merged_area = child.bounds.merged(bounds).surface_area();
+                if child.is_leaf() {merged_area + inherited_cost} else {merged_area - child.bounds.surface_area() + inherited_cost}
+            };
+            let (cost_0, cost_1) = (child_cost(node.children[0]), child_cost(node.children[1]));
+
+            if cost < cost_0 && cost < cost_1 {break;}
+            index = if cost_0 < cost_1 {node.children[0]} else {node.children[1]};
+        }
+
+        // a new branch replaces the sibling, holding both it and the new leaf
+        let sibling = index;
+        let old_parent = self.nodes[sibling].parent;
+        let new_parent = self.allocate(TreeNode {
+            bounds: self.nodes[sibling].bounds.merged(bounds),
+            parent: old_parent,
+            children: [sibling, leaf],
+            item: 0,
+            height: self.nodes[sibling].height + 1,
+        });
+        self.nodes[sibling].parent = Some(new_parent);
+        self.nodes[leaf].parent = Some(new_parent);
+        self.replace_child(old_parent, sibling, new_parent);
+
+        self.fix_upwards(Some(new_parent));
+        leaf
+    }
+
+    pub fn remove(&mut self, leaf: usize) {
+        let parent = self.nodes[leaf].parent;
+        self.free_node(leaf);
+
+        let Some(parent) = parent else {
+            self.root = None;
+            return;
+        };
+
+        // the sibling takes the place of the parent
+        let children = self.nodes[parent].children;
+        let sibling = if children[0] == leaf {children[1]} else {children[0]};
+        let grandparent = self.nodes[parent].parent;
+        self.nodes[sibling].parent = grandparent;
+        self.replace_child(grandparent, parent, sibling);
+        self.free_node(parent);
+
+        self.fix_upwards(grandparent);
+    }
+
+    pub fn get_bounds(&self, leaf: usize) -> AABoundingBox {
+        self.nodes[leaf].bounds
+    }
+
+    /// how many levels deep the tree goes, which is 0 when it is empty
+    pub fn height(&self) -> usize {
+        self.root.map_or(0, |root| self.nodes[root].height + 1)
+    }
+
+    /// every item with bounds overlapping the given box
+    pub fn query_box(&self, bounds: AABoundingBox, mut found: impl FnMut(usize)) {
+        let mut stack: Vec<usize> = self.root.into_iter().collect();
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            if !node.bounds.is_intersecting_box(bounds) {continue;}
+            if node.is_leaf() {found(node.item);} else {stack.extend(node.children);}
+        }
+    }
+
+    /// every item with bounds the ray passes through
+    pub fn query_ray(&self, ray: &Ray, mut found: impl FnMut(usize)) {
+        let mut stack: Vec<usize> = self.root.into_iter().collect();
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            if ray.box_distance(&node.bounds).is_none() {continue;}
+            if node.is_leaf() {found(node.item);} else {stack.extend(node.children);}
+        }
+    }
+
+    /// visits the items the ray passes through nearest box first, `hit` gives back the distance of any hit on the item
+    /// and the ray is cut short there, so boxes behind the nearest hit so far are skipped
+    pub fn query_ray_nearest(&self, ray: &Ray, mut hit: impl FnMut(usize, &Ray) -> Option<f32>) {
+        let mut ray = *ray;
+        let mut stack: Vec<usize> = self.root.into_iter().collect();
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            if ray.box_distance(&node.bounds).is_none() {continue;}
+
+            if node.is_leaf() {
+                if let Some(dist) = hit(node.item, &ray) {ray.t_max = ray.t_max.min(dist);}
+                continue;
+            }
+
+            // visit the nearer child first so the further one can be skipped once something is hit
+            let [first, second] = node.children;
+            let first_dist = ray.box_distance(&self.nodes[first].bounds).unwrap_or(f32::INFINITY);
+            let second_dist = ray.box_distance(&self.nodes[second].bounds).unwrap_or(f32::INFINITY);
+            if first_dist < second_dist {
+                stack.extend([second, first]);
+            } else {
+                stack.extend([first, second]);
+            }
+        }
+    }
+
+
+    fn allocate(&mut self, node: TreeNode) -> usize {
+        match self.free_nodes.pop() {
+            Some(index) => {
+                self.nodes[index] = node;
+                index
+            },
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        }
+    }
+
+    fn free_node(&mut self, index: usize) {
+        self.free_nodes.push(index);
+    }
+
+    // points the parent at the new child instead of the old one, or makes the new child the root if there is no parent
+    fn replace_child(&mut self, parent: Option<usize>, old_child: usize, new_child: usize) {
+        match parent {
+            Some(parent) => {
+                let children = &mut self.nodes[parent].children;
+                if children[0] == old_child {children[0] = new_child} else {children[1] = new_child}
+            },
+            None => self.root = Some(new_child),
+        }
+    }
+
+    // rebalances and refits every branch from the given one up to the root
+    fn fix_upwards(&mut self, mut index: Option<usize>) {
+        while let Some(current) = index {
+            let current = self.balance(current);
+            let [a, b] = self.nodes[current].children;
+            self.nodes[current].height = 1 + self.nodes[a].height.max(self.nodes[b].height);
+            self.nodes[current].bounds = self.nodes[a].bounds.merged(self.nodes[b].bounds);
+            index = self.nodes[current].parent;
+        }
+    }
+
+    // if one child is more than one level taller than the other it is rotated up to take this node's place,
+    // giving back whichever node is now in this position
+    fn balance(&mut self, index: usize) -> usize {
+        let node = self.nodes[index];
+        if node.is_leaf() || node.height < 2 {return index;}
+
+        let [b, c] = node.children;
+        let (height_b, height_c) = (self.nodes[b].height, self.nodes[c].height);
+        if height_c > height_b + 1 {
+            self.rotate_up(index, 1)
+        } else if height_b > height_c + 1 {
+            self.rotate_up(index, 0)
+        } else {
+            index
+        }
+    }
+
+    // swaps the node with its child, the taller grandchild stays with the raised child and the shorter one moves down
+    fn rotate_up(&mut self, index: usize, side: usize) -> usize {
+        let raised = self.nodes[index].children[side];
+        let [f, g] = self.nodes[raised].children;
+        let (taller, shorter) = if self.nodes[f].height > self.nodes[g].height {(f, g)} else {(g, f)};
+
+        let parent = self.nodes[index].parent;
+        self.nodes[raised].parent = parent;
+        self.replace_child(parent, index, raised);
+        self.nodes[raised].children = [index, taller];
+        self.nodes[index].parent = Some(raised);
+
+        self.nodes[index].children[side] = shorter;
+        self.nodes[shorter].parent = Some(index);
+
+        let [a0, a1] = self.nodes[index].children;
+        self.nodes[index].bounds = self.nodes[a0].bounds.merged(self.nodes[a1].bounds);
+        self.nodes[index].height = 1 + self.nodes[a0].height.max(self.nodes[a1].height);
+        self.nodes[raised].bounds = self.nodes[index].bounds.merged(self.nodes[taller].bounds);
+        self.nodes[raised].height = 1 + self.nodes[index].height.max(self.nodes[taller].height);
+        raised
+    }
+}
@@ -0,0 +1,521 @@
+use super::{Vector3, Matrix3, AABoundingBox, BoundingSphere, CapsuleCollider, ConeCollider, ConvexHull, CylinderCollider, OrientedBoundingBox, PlaneCollider, TriangleCollider};
+
+
+const MAX_GJK_ITERATIONS: usize = 64;
+const MAX_EPA_ITERATIONS: usize = 128;
+const GJK_TOLERANCE: f32 = 1e-6;
+const EPA_TOLERANCE: f32 = 1e-4;
+// how close a vertex has to be to the other shape to count as a contact point
+const CONTACT_TOLERANCE: f32 = 1e-3;
+
+
+/// a convex shape described by its support function, which is all GJK and EPA need to work with a shape
+pub trait SupportMap {
+    /// the point of the shape that is furthest in the given direction
+    fn support(&self, direction: Vector3) -> Vector3;
+
+    /// all of the points of the shape that are furthest in the given direction, such as the 4 corners of a box face.
+    ///
+    /// curved shapes only have the single support point
+    fn support_feature(&self, direction: Vector3) -> Vec<Vector3> {
+        vec![self.support(direction)]
+    }
+}
+
+/// the closest points between two separated shapes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClosestPoints {
+    pub point_a: Vector3,
+    pub point_b: Vector3,
+    pub distance: f32,
+}
+
+/// how two overlapping shapes touch.
+///
+/// The normal points from the first shape to the second, so moving the second shape by `normal * depth` separates them
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContactManifold {
+    pub points: Vec<Vector3>,
+    pub normal: Vector3,
+    pub depth: f32,
+}
+
+
+impl SupportMap for Vector3 {
+    fn support(&self, _direction: Vector3) -> Vector3 {
+        *self
+    }
+}
+
+impl SupportMap for BoundingSphere {
+    fn support(&self, direction: Vector3) -> Vector3 {
+        if direction.sqr_magnitude() == 0.0 {
+            return self.centre + Vector3::X * self.radius;
+        }
+        self.centre + direction.normalised() * self.radius
+    }
+}
+
+impl SupportMap for AABoundingBox {
+    fn support(&self, direction: Vector3) -> Vector3 {
+        Vector3::new(
+            if direction.x >= 0.0 {self.max_corner.x} else {self.min_corner.x},
+            if direction.y >= 0.0 {self.max_corner.y} else {self.min_corner.y},
+            if direction.z >= 0.0 {self.max_corner.z} else {self.min_corner.z},
+        )
+    }
+
+    fn support_feature(&self, direction: Vector3) -> Vec<Vector3> {
+        furthest_points(&self.corners(), direction)
+    }
+}
+
+impl SupportMap for OrientedBoundingBox {
+    fn support(&self, direction: Vector3) -> Vector3 {
+        let local = self.basis.transposed() * direction;
+        self.to_world(AABoundingBox::new(-self.half_extents, self.half_extents).support(local))
+    }
+
+    fn support_feature(&self, direction: Vector3) -> Vec<Vector3> {
+        furthest_points(&self.corners(), direction)
+    }
+}
+
+impl SupportMap for TriangleCollider {
+    fn support(&self, direction: Vector3) -> Vector3 {
+        let points = self.get_points();
+        furthest_point(&[points.c0(), points.c1(), points.c2()], direction)
+    }
+
+    fn support_feature(&self, direction: Vector3) -> Vec<Vector3> {
+        let points = self.get_points();
+        furthest_points(&[points.c0(), points.c1(), points.c2()], direction)
+    }
+}
+
+impl SupportMap for PlaneCollider {
+    fn support(&self, direction: Vector3) -> Vector3 {
+        self.get_bounds().support(direction)
+    }
+
+    fn support_feature(&self, direction: Vector3) -> Vec<Vector3> {
+        let bounds = self.get_bounds();
+        let mut corners = furthest_points(&bounds.corners(), direction);
+        corners.dedup();
+        corners
+    }
+}
+
+impl SupportMap for ConvexHull {
+    fn support(&self, direction: Vector3) -> Vector3 {
+        furthest_point(self.get_points(), direction)
+    }
+
+    fn support_feature(&self, direction: Vector3) -> Vec<Vector3> {
+        furthest_points(self.get_points(), direction)
+    }
+}
+
+impl SupportMap for CapsuleCollider {
+    fn support(&self, direction: Vector3) -> Vector3 {
+        let end = if direction.dot(self.end - self.start) >= 0.0 {self.end} else {self.start};
+        BoundingSphere::new(end, self.radius).support(direction)
+    }
+}
+
+impl SupportMap for CylinderCollider {
+    fn support(&self, direction: Vector3) -> Vector3 {
+        let axis = self.axis();
+        let end = if direction.dot(axis) >= 0.0 {self.end} else {self.start};
+        end + rim_direction(direction, axis) * self.radius
+    }
+}
+
+impl SupportMap for ConeCollider {
+    fn support(&self, direction: Vector3) -> Vector3 {
+        let rim = self.base + rim_direction(direction, self.axis()) * self.radius;
+        if self.apex.dot(direction) > rim.dot(direction) {self.apex} else {rim}
+    }
+}
+
+// the direction out from the axis of a circle that is furthest along the given direction
+fn rim_direction(direction: Vector3, axis: Vector3) -> Vector3 {
+    let radial = direction - axis * direction.dot(axis);
+    if radial.sqr_magnitude() <= f32::EPSILON {return Vector3::ZERO;}
+    radial.normalised()
+}
+
+fn furthest_point(points: &[Vector3], direction: Vector3) -> Vector3 {
+    let mut best = points.first().copied().unwrap_or(Vector3::ZERO);
+    let mut best_dist = f32::MIN;
+    for point in points {
+        let dist = point.dot(direction);
+        if dist > best_dist {
+            best_dist = dist;
+            best = *point;
+        }
+    }
+    best
+}
+
+fn furthest_points(points: &[Vector3], direction: Vector3) -> Vec<Vector3> {
+    let direction = direction.normalised();
+    let max = points.iter().fold(f32::MIN, |max, point| max.max(point.dot(direction)));
+    let tolerance = EPA_TOLERANCE * (1.0 + max.abs());
+    points.iter().filter(|point| point.dot(direction) >= max - tolerance).copied().collect()
+}
+
+
+// a point on the minkowski difference A - B, along with the points on each shape that made it
+#[derive(Debug, Clone, Copy)]
+struct SupportPoint {
+    w: Vector3,
+    a: Vector3,
+    b: Vector3,
+}
+
+fn support_point(a: &impl SupportMap, b: &impl SupportMap, direction: Vector3) -> SupportPoint {
+    let (point_a, point_b) = (a.support(direction), b.support(-direction));
+    SupportPoint {w: point_a - point_b, a: point_a, b: point_b}
+}
+
+// the weighted sum of the shape points, giving the closest points on each shape
+fn witness_points(weighted: &[(SupportPoint, f32)]) -> (Vector3, Vector3) {
+    weighted.iter().fold((Vector3::ZERO, Vector3::ZERO), |(a, b), (point, weight)| (a + point.a * *weight, b + point.b * *weight))
+}
+
+
+// closest point of a segment to the origin, along with the points used and their weights
+fn closest_on_segment(p: SupportPoint, q: SupportPoint) -> Vec<(SupportPoint, f32)> {
+    let pq = q.w - p.w;
+    let length = pq.sqr_magnitude();
+    if length == 0.0 {return vec![(p, 1.0)];}
+    let t = -p.w.dot(pq) / length;
+    if t <= 0.0 {
+        vec![(p, 1.0)]
+    } else if t >= 1.0 {
+        vec![(q, 1.0)]
+    } else {
+        vec![(p, 1.0 - t), (q, t)]
+    }
+}
+
+// same as `TriangleCollider::closest_point` but keeps track of the weights of the vertices used
+fn closest_on_triangle(a: SupportPoint, b: SupportPoint, c: SupportPoint) -> Vec<(SupportPoint, f32)> {
+    let (ab, ac) = (b.w - a.w, c.w - a.w);
+
+    let (d1, d2) = (-ab.dot(a.w), -ac.dot(a.w));
+    if d1 <= 0.0 && d2 <= 0.0 {return vec![(a, 1.0)];}
+
+    let (d3, d4) = (-ab.dot(b.w), -ac.dot(b.w));
+    if d3 >= 0.0 && d4 <= d3 {return vec![(b, 1.0)];}
+
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+        let v = d1 / (d1 - d3);
+        return vec![(a, 1.0 - v), (b, v)];
+    }
+
+    let (d5, d6) = (-ab.dot(c.w), -ac.dot(c.w));
+    if d6 >= 0.0 && d5 <= d6 {return vec![(c, 1.0)];}
+
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+        let w = d2 / (d2 - d6);
+        return vec![(a, 1.0 - w), (c, w)];
+    }
+
+    let va = d3 * d6 - d5 * d4;
+    if va <= 0.0 && (d4 - d3) >= 0.0 && (d5 - d6) >= 0.0 {
+        let w = (d4 - d3) / ((d4 - d3) + (d5 - d6));
+        return vec![(b, 1.0 - w), (c, w)];
+    }
+
+    let sum = va + vb + vc;
+    if sum.abs() <= f32::EPSILON {
+        // the triangle is flat so fall back to its closest edge
+        let edges = [closest_on_segment(a, b), closest_on_segment(b, c), closest_on_segment(a, c)];
+        return edges.into_iter()
+            .min_by(|x, y| weighted_point(x).sqr_magnitude().total_cmp(&weighted_point(y).sqr_magnitude()))
+            .unwrap();
+    }
+    let (v, w) = (vb / sum, vc / sum);
+    vec![(a, 1.0 - v - w), (b, v), (c, w)]
+}
+
+fn closest_on_tetrahedron(points: [SupportPoint; 4]) -> Vec<(SupportPoint, f32)> {
+    let [a, b, c, d] = points;
+    let faces = [(a, b, c, d), (a, c, d, b), (a, d, b, c), (b, d, c, a)];
+    let volume = (b.w - a.w).cross(c.w - a.w).dot(d.w - a.w);
+
+    let mut best: Option<Vec<(SupportPoint, f32)>> = None;
+    for (p, q, r, opposite) in faces {
+        let normal = (q.w - p.w).cross(r.w - p.w);
+        let origin_side = -normal.dot(p.w);
+        let opposite_side = normal.dot(opposite.w - p.w);
+        // only faces with the origin on the other side to the rest of the tetrahedron can be closest
+        if volume.abs() > f32::EPSILON && origin_side * opposite_side >= 0.0 {continue;}
+
+        let candidate = closest_on_triangle(p, q, r);
+        let is_closer = match &best {
+            Some(current) => weighted_point(&candidate).sqr_magnitude() < weighted_point(current).sqr_magnitude(),
+            None => true,
+        };
+        if is_closer {best = Some(candidate);}
+    }
+
+    // the origin is inside so use the volumes of the sub tetrahedrons as the weights
+    best.unwrap_or_else(|| {
+        let sub_volume = |p: Vector3, q: Vector3, r: Vector3| q.cross(r).dot(p) / volume;
+        vec![
+            (a, sub_volume(b.w, c.w, d.w)),
+            (b, sub_volume(a.w, d.w, c.w)),
+            (c, sub_volume(a.w, b.w, d.w)),
+            (d, sub_volume(a.w, c.w, b.w)),
+        ]
+    })
+}
+
+fn weighted_point(weighted: &[(SupportPoint, f32)]) -> Vector3 {
+    weighted.iter().fold(Vector3::ZERO, |sum, (point, weight)| sum + point.w * *weight)
+}
+
+fn closest_on_simplex(simplex: &[SupportPoint]) -> Vec<(SupportPoint, f32)> {
+    match simplex {
+        [p] => vec![(*p, 1.0)],
+        [p, q] => closest_on_segment(*p, *q),
+        [p, q, r] => closest_on_triangle(*p, *q, *r),
+        [p, q, r, s] => closest_on_tetrahedron([*p, *q, *r, *s]),
+        _ => unreachable!("gjk simplex has between 1 and 4 points"),
+    }
+}
+
+
+enum GjkResult {
+    Overlapping(Vec<SupportPoint>),
+    Separated(ClosestPoints),
+}
+
+// gjk distance algorithm, repeatedly moves the simplex towards the origin until it either contains the origin or can't get any closer
+fn gjk(a: &impl SupportMap, b: &impl SupportMap) -> GjkResult {
+    let mut simplex = vec![support_point(a, b, Vector3::X)];
+    let mut weighted = vec![(simplex[0], 1.0)];
+
+    for _ in 0..MAX_GJK_ITERATIONS {
+        weighted = closest_on_simplex(&simplex);
+        let closest = weighted_point(&weighted);
+        simplex = weighted.iter().map(|(point, _)| *point).collect();
+
+        let sqr_distance = closest.sqr_magnitude();
+        if sqr_distance <= GJK_TOLERANCE * GJK_TOLERANCE || simplex.len() == 4 {
+            return GjkResult::Overlapping(simplex);
+        }
+
+        let new_point = support_point(a, b, -closest);
+        // no progress towards the origin so this is as close as it gets
+        let progress = sqr_distance - closest.dot(new_point.w);
+        let is_duplicate = simplex.iter().any(|point| (point.w - new_point.w).sqr_magnitude() <= GJK_TOLERANCE * GJK_TOLERANCE);
+        if progress <= GJK_TOLERANCE * sqr_distance.max(1.0) || is_duplicate {
+            break;
+        }
+        simplex.push(new_point);
+    }
+
+    let (point_a, point_b) = witness_points(&weighted);
+    GjkResult::Separated(ClosestPoints {
+        point_a,
+        point_b,
+        distance: weighted_point(&weighted).magnitude(),
+    })
+}
+
+
+/// checks if two convex shapes overlap using GJK, touching counts as overlapping
+pub fn gjk_intersects(a: &impl SupportMap, b: &impl SupportMap) -> bool {
+    matches!(gjk(a, b), GjkResult::Overlapping(_))
+}
+
+/// the closest points on two convex shapes and the distance between them, None if they overlap
+pub fn gjk_closest_points(a: &impl SupportMap, b: &impl SupportMap) -> Option<ClosestPoints> {
+    match gjk(a, b) {
+        GjkResult::Overlapping(_) => None,
+        GjkResult::Separated(closest) => Some(closest),
+    }
+}
+
+/// uses GJK to check for overlap then EPA to find how deep the shapes overlap, None if they don't overlap.
+///
+/// Where the shapes have flat faces touching, all the corners of one that lie within the other are used as contact points,
+/// otherwise the single point half way between the deepest points of each shape is used
+pub fn epa_contact_manifold(a: &impl SupportMap, b: &impl SupportMap) -> Option<ContactManifold> {
+    let simplex = match gjk(a, b) {
+        GjkResult::Overlapping(simplex) => simplex,
+        GjkResult::Separated(_) => return None,
+    };
+    let (normal, depth, (point_a, point_b)) = epa(a, b, simplex);
+
+    let mut points: Vec<Vector3> = Vec::new();
+    let feature_a = a.support_feature(normal);
+    let feature_b = b.support_feature(-normal);
+    if feature_a.len() > 1 {
+        points.extend(feature_a.into_iter().filter(|point| point_within(point, b)));
+    }
+    if feature_b.len() > 1 {
+        points.extend(feature_b.into_iter().filter(|point| point_within(point, a)));
+    }
+    points.dedup_by(|p, q| (*p - *q).sqr_magnitude() <= CONTACT_TOLERANCE * CONTACT_TOLERANCE);
+    if points.is_empty() {
+        points.push((point_a + point_b) * 0.5);
+    }
+
+    Some(ContactManifold {
+        points,
+        normal,
+        depth,
+    })
+}
+
+fn point_within(point: &Vector3, shape: &impl SupportMap) -> bool {
+    match gjk(point, shape) {
+        GjkResult::Overlapping(_) => true,
+        GjkResult::Separated(closest) => closest.distance <= CONTACT_TOLERANCE,
+    }
+}
+
+
+// grows the simplex gjk finished with into a tetrahedron, if the minkowski difference is flat the normal of the flat part is returned instead
+fn blow_up_simplex(a: &impl SupportMap, b: &impl SupportMap, mut simplex: Vec<SupportPoint>) -> Result<Vec<SupportPoint>, Vector3> {
+    let axes = [Vector3::X, Vector3::Y, Vector3::Z, -Vector3::X, -Vector3::Y, -Vector3::Z];
+    let is_new = |simplex: &[SupportPoint], point: &SupportPoint| simplex.iter().all(|p| (p.w - point.w).sqr_magnitude() > GJK_TOLERANCE);
+
+    if simplex.len() == 1 {
+        for axis in axes {
+            let point = support_point(a, b, axis);
+            if is_new(&simplex, &point) {
+                simplex.push(point);
+                break;
+            }
+        }
+        if simplex.len() == 1 {return Err(Vector3::Y);}
+    }
+
+    if simplex.len() == 2 {
+        let line = (simplex[1].w - simplex[0].w).normalised();
+        // go around the line looking for a point off it
+        let least_aligned = *axes[..3].iter().min_by(|x, y| line.dot(**x).abs().total_cmp(&line.dot(**y).abs())).unwrap();
+        let perpendicular = line.cross(least_aligned).normalised();
+        for step in 0..6 {
+            let direction = Matrix3::from_angle_and_axis(step as f32 * std::f32::consts::FRAC_PI_3, line) * perpendicular;
+            let point = support_point(a, b, direction);
+            let offset = point.w - simplex[0].w;
+            if (offset - line * offset.dot(line)).sqr_magnitude() > GJK_TOLERANCE {
+                simplex.push(point);
+                break;
+            }
+        }
+        if simplex.len() == 2 {return Err(perpendicular);}
+    }
+
+    if simplex.len() == 3 {
+        let normal = (simplex[1].w - simplex[0].w).cross(simplex[2].w - simplex[0].w).normalised();
+        for direction in [normal, -normal] {
+            let point = support_point(a, b, direction);
+            if (point.w - simplex[0].w).dot(normal).abs() > GJK_TOLERANCE {
+                simplex.push(point);
+                break;
+            }
+        }
+        if simplex.len() == 3 {return Err(normal);}
+    }
+    Ok(simplex)
+}
+
+struct Face {
+    indices: [usize; 3],
+    normal: Vector3,
+    distance: f32,
+}
+
+impl Face {
+    fn new(vertices: &[SupportPoint], indices: [usize; 3]) -> Self {
+        let [i, j, k] = indices;
+        let normal = (vertices[j].w - vertices[i].w).cross(vertices[k].w - vertices[i].w).normalised();
+        Face {
+            indices,
+            normal,
+            distance: normal.dot(vertices[i].w),
+        }
+    }
+}
+
+// expanding polytope algorithm, grows the gjk simplex out to the surface of the minkowski difference to find the face nearest the origin
+fn epa(a: &impl SupportMap, b: &impl SupportMap, simplex: Vec<SupportPoint>) -> (Vector3, f32, (Vector3, Vector3)) {
+    let mut vertices = match blow_up_simplex(a, b, simplex.clone()) {
+        Ok(vertices) => vertices,
+        Err(normal) => {
+            // the shapes are touching along a flat patch so there is no depth
+            return (normal, 0.0, witness_points(&closest_on_simplex(&simplex)));
+        }
+    };
+
+    // wind all the faces so their normals point away from the inside of the tetrahedron
+    let mut faces: Vec<Face> = Vec::new();
+    for [i, j, k, opposite] in [[0, 1, 2, 3], [0, 3, 1, 2], [0, 2, 3, 1], [1, 3, 2, 0]] {
+        let normal = (vertices[j].w - vertices[i].w).cross(vertices[k].w - vertices[i].w);
+        let indices = if normal.dot(vertices[opposite].w - vertices[i].w) > 0.0 {[i, k, j]} else {[i, j, k]};
+        faces.push(Face::new(&vertices, indices));
+    }
+
+    for _ in 0..MAX_EPA_ITERATIONS {
+        let face = &faces[closest_face(&faces)];
+        let new_point = support_point(a, b, face.normal);
+        if new_point.w.dot(face.normal) - face.distance <= EPA_TOLERANCE * (1.0 + face.distance.abs()) {
+            break;
+        }
+
+        // remove every face the new point can see, keeping track of the edges around the hole they leave
+        let new_index = vertices.len();
+        vertices.push(new_point);
+        let mut edges: Vec<(usize, usize)> = Vec::new();
+        faces.retain(|face| {
+            if face.normal.dot(new_point.w - vertices[face.indices[0]].w) <= 0.0 {return true;}
+            let [i, j, k] = face.indices;
+            for edge in [(i, j), (j, k), (k, i)] {
+                if let Some(position) = edges.iter().position(|other| *other == (edge.1, edge.0)) {
+                    edges.remove(position);
+                } else {
+                    edges.push(edge);
+                }
+            }
+            false
+        });
+        faces.extend(edges.into_iter().map(|(i, j)| Face::new(&vertices, [i, j, new_index])));
+    }
+
+    let face = &faces[closest_face(&faces)];
+    let [i, j, k] = face.indices;
+    let weights = barycentric(face.normal * face.distance, vertices[i].w, vertices[j].w, vertices[k].w);
+    let weighted = [(vertices[i], weights.x), (vertices[j], weights.y), (vertices[k], weights.z)];
+    (face.normal, face.distance.max(0.0), witness_points(&weighted))
+}
+
+fn closest_face(faces: &[Face]) -> usize {
+    faces.iter()
+        .enumerate()
+        .filter(|(_, face)| face.normal.sqr_magnitude() > 0.0)
+        .min_by(|(_, x), (_, y)| x.distance.total_cmp(&y.distance))
+        .map_or(0, |(index, _)| index)
+}
+
+fn barycentric(point: Vector3, a: Vector3, b: Vector3, c: Vector3) -> Vector3 {
+    let (v0, v1, v2) = (b - a, c - a, point - a);
+    let (d00, d01, d11) = (v0.dot(v0), v0.dot(v1), v1.dot(v1));
+    let (d20, d21) = (v2.dot(v0), v2.dot(v1));
+    let denom = d00 * d11 - d01 * d01;
+    if denom.abs() <= f32::EPSILON {
+        return Vector3::new(1.0, 0.0, 0.0);
+    }
+    let v = (d11 * d20 - d01 * d21) / denom;
+    let w = (d00 * d21 - d01 * d20) / denom;
+    Vector3::new(1.0 - v - w, v, w)
+}
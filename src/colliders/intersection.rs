@@ -0,0 +1,372 @@
+use super::{Vector3, AABoundingBox, BoundingSphere, CapsuleCollider, ConeCollider, CylinderCollider, MeshCollider, OrientedBoundingBox, PlaneCollider, TriangleCollider, Bounded, gjk_intersects};
+
+
+/// overlap tests between two colliders, touching counts as intersecting
+///
+/// every pair of colliders is implemented both ways round so shapes can be tested against each other generically,
+/// e.g. `fn overlapping<A: Intersects<B>, B>(a: &A, b: &B) -> bool`
+///
+/// Meshes and planes are treated as surfaces, so a shape that is completely inside a closed mesh does not intersect it
+pub trait Intersects<T> {
+    fn intersects(&self, other: &T) -> bool;
+}
+
+
+// separating axis test, the two convex point sets overlap if their projections overlap on every axis
+fn overlap_on_axes(a: &[Vector3], b: &[Vector3], axes: &[Vector3]) -> bool {
+    for axis in axes {
+        // edges that are parallel give no axis
+        if axis.sqr_magnitude() <= f32::EPSILON * f32::EPSILON {continue;}
+        let (a_min, a_max) = project(a, *axis);
+        let (b_min, b_max) = project(b, *axis);
+        if a_max < b_min || b_max < a_min {return false;}
+    }
+    true
+}
+
+fn project(points: &[Vector3], axis: Vector3) -> (f32, f32) {
+    points.iter().fold((f32::MAX, f32::MIN), |(min, max), point| {
+        let dist = point.dot(axis);
+        (min.min(dist), max.max(dist))
+    })
+}
+
+fn triangle_points(tri: &TriangleCollider) -> [Vector3; 3] {
+    let points = tri.get_points();
+    [points.c0(), points.c1(), points.c2()]
+}
+
+fn triangle_edges(points: &[Vector3; 3]) -> [Vector3; 3] {
+    [points[1] - points[0], points[2] - points[1], points[0] - points[2]]
+}
+
+
+fn sphere_sphere(a: &BoundingSphere, b: &BoundingSphere) -> bool {
+    let radii = a.radius + b.radius;
+    (a.centre - b.centre).sqr_magnitude() <= radii * radii
+}
+
+fn sphere_box(sphere: &BoundingSphere, bounds: &AABoundingBox) -> bool {
+    (bounds.closest_point(sphere.centre) - sphere.centre).sqr_magnitude() <= sphere.radius * sphere.radius
+}
+
+fn sphere_obb(sphere: &BoundingSphere, bounds: &OrientedBoundingBox) -> bool {
+    (bounds.closest_point(sphere.centre) - sphere.centre).sqr_magnitude() <= sphere.radius * sphere.radius
+}
+
+fn sphere_triangle(sphere: &BoundingSphere, tri: &TriangleCollider) -> bool {
+    (tri.closest_point(sphere.centre) - sphere.centre).sqr_magnitude() <= sphere.radius * sphere.radius
+}
+
+// the box axes, the triangle normal and the cross products of the box axes with the triangle edges
+fn box_triangle(bounds: &AABoundingBox, tri: &TriangleCollider) -> bool {
+    let points = triangle_points(tri);
+    let edges = triangle_edges(&points);
+    let mut axes = vec![Vector3::X, Vector3::Y, Vector3::Z, tri.get_normal()];
+    for box_axis in [Vector3::X, Vector3::Y, Vector3::Z] {
+        axes.extend(edges.iter().map(|edge| box_axis.cross(*edge)));
+    }
+    overlap_on_axes(&bounds.corners(), &points, &axes)
+}
+
+// both normals, the cross products of each pair of edges, and the in plane edge normals which handle coplanar triangles
+fn triangle_triangle(a: &TriangleCollider, b: &TriangleCollider) -> bool {
+    let (a_points, b_points) = (triangle_points(a), triangle_points(b));
+    let (a_edges, b_edges) = (triangle_edges(&a_points), triangle_edges(&b_points));
+    let (a_normal, b_normal) = (a.get_normal(), b.get_normal());
+
+    let mut axes = vec![a_normal, b_normal];
+    for a_edge in a_edges.iter() {
+        axes.extend(b_edges.iter().map(|b_edge| a_edge.cross(*b_edge)));
+        axes.push(a_normal.cross(*a_edge));
+    }
+    axes.extend(b_edges.iter().map(|b_edge| b_normal.cross(*b_edge)));
+    overlap_on_axes(&a_points, &b_points, &axes)
+}
+
+// anything against a mesh is first checked against the mesh bounds,
+// then against only the triangles the bounding volume hierarchy finds near the other shape's bounds
+fn mesh_with<T: Bounded>(mesh: &MeshCollider, other: &T) -> bool
+where
+    AABoundingBox: Intersects<T>,
+    TriangleCollider: Intersects<T>,
+{
+    if !mesh.get_bounds().intersects(other) {return false;}
+    mesh.get_triangles_in(other.bounding_box()).into_iter().any(|tri| tri.intersects(other))
+}
+
+fn mesh_mesh(a: &MeshCollider, b: &MeshCollider) -> bool {
+    let (a_bounds, b_bounds) = (a.get_bounds(), b.get_bounds());
+    if !a_bounds.is_intersecting_box(b_bounds) {return false;}
+
+    // only the triangles inside the overlap of the two bounds can touch,
+    // and each of those only needs checking against the triangles of the other mesh near it
+    a.get_triangles_in(a_bounds.overlap(b_bounds)).into_iter()
+        .any(|a_tri| b.get_triangles_in(a_tri.bounding_box()).into_iter().any(|b_tri| triangle_triangle(a_tri, b_tri)))
+}
+
+
+// spheres
+impl Intersects<BoundingSphere> for BoundingSphere {
+    fn intersects(&self, other: &BoundingSphere) -> bool {
+        sphere_sphere(self, other)
+    }
+}
+
+impl Intersects<AABoundingBox> for BoundingSphere {
+    fn intersects(&self, other: &AABoundingBox) -> bool {
+        sphere_box(self, other)
+    }
+}
+
+impl Intersects<TriangleCollider> for BoundingSphere {
+    fn intersects(&self, other: &TriangleCollider) -> bool {
+        sphere_triangle(self, other)
+    }
+}
+
+impl Intersects<PlaneCollider> for BoundingSphere {
+    fn intersects(&self, other: &PlaneCollider) -> bool {
+        sphere_box(self, &other.get_bounds())
+    }
+}
+
+impl Intersects<MeshCollider> for BoundingSphere {
+    fn intersects(&self, other: &MeshCollider) -> bool {
+        mesh_with(other, self)
+    }
+}
+
+
+// boxes
+impl Intersects<BoundingSphere> for AABoundingBox {
+    fn intersects(&self, other: &BoundingSphere) -> bool {
+        sphere_box(other, self)
+    }
+}
+
+impl Intersects<AABoundingBox> for AABoundingBox {
+    fn intersects(&self, other: &AABoundingBox) -> bool {
+        self.is_intersecting_box(*other)
+    }
+}
+
+impl Intersects<TriangleCollider> for AABoundingBox {
+    fn intersects(&self, other: &TriangleCollider) -> bool {
+        box_triangle(self, other)
+    }
+}
+
+impl Intersects<PlaneCollider> for AABoundingBox {
+    fn intersects(&self, other: &PlaneCollider) -> bool {
+        self.is_intersecting_box(other.get_bounds())
+    }
+}
+
+impl Intersects<MeshCollider> for AABoundingBox {
+    fn intersects(&self, other: &MeshCollider) -> bool {
+        mesh_with(other, self)
+    }
+}
+
+
+// triangles
+impl Intersects<BoundingSphere> for TriangleCollider {
+    fn intersects(&self, other: &BoundingSphere) -> bool {
+        sphere_triangle(other, self)
+    }
+}
+
+impl Intersects<AABoundingBox> for TriangleCollider {
+    fn intersects(&self, other: &AABoundingBox) -> bool {
+        box_triangle(other, self)
+    }
+}
+
+impl Intersects<TriangleCollider> for TriangleCollider {
+    fn intersects(&self, other: &TriangleCollider) -> bool {
+        triangle_triangle(self, other)
+    }
+}
+
+impl Intersects<PlaneCollider> for TriangleCollider {
+    fn intersects(&self, other: &PlaneCollider) -> bool {
+        box_triangle(&other.get_bounds(), self)
+    }
+}
+
+impl Intersects<MeshCollider> for TriangleCollider {
+    fn intersects(&self, other: &MeshCollider) -> bool {
+        mesh_with(other, self)
+    }
+}
+
+
+// planes
+impl Intersects<BoundingSphere> for PlaneCollider {
+    fn intersects(&self, other: &BoundingSphere) -> bool {
+        sphere_box(other, &self.get_bounds())
+    }
+}
+
+impl Intersects<AABoundingBox> for PlaneCollider {
+    fn intersects(&self, other: &AABoundingBox) -> bool {
+        other.is_intersecting_box(self.get_bounds())
+    }
+}
+
+impl Intersects<TriangleCollider> for PlaneCollider {
+    fn intersects(&self, other: &TriangleCollider) -> bool {
+        box_triangle(&self.get_bounds(), other)
+    }
+}
+
+impl Intersects<PlaneCollider> for PlaneCollider {
+    fn intersects(&self, other: &PlaneCollider) -> bool {
+        self.get_bounds().is_intersecting_box(other.get_bounds())
+    }
+}
+
+impl Intersects<MeshCollider> for PlaneCollider {
+    fn intersects(&self, other: &MeshCollider) -> bool {
+        mesh_with(other, &self.get_bounds())
+    }
+}
+
+
+// meshes
+impl Intersects<BoundingSphere> for MeshCollider {
+    fn intersects(&self, other: &BoundingSphere) -> bool {
+        mesh_with(self, other)
+    }
+}
+
+impl Intersects<AABoundingBox> for MeshCollider {
+    fn intersects(&self, other: &AABoundingBox) -> bool {
+        mesh_with(self, other)
+    }
+}
+
+impl Intersects<TriangleCollider> for MeshCollider {
+    fn intersects(&self, other: &TriangleCollider) -> bool {
+        mesh_with(self, other)
+    }
+}
+
+impl Intersects<PlaneCollider> for MeshCollider {
+    fn intersects(&self, other: &PlaneCollider) -> bool {
+        mesh_with(self, &other.get_bounds())
+    }
+}
+
+impl Intersects<MeshCollider> for MeshCollider {
+    fn intersects(&self, other: &MeshCollider) -> bool {
+        mesh_mesh(self, other)
+    }
+}
+
+impl Intersects<OrientedBoundingBox> for MeshCollider {
+    fn intersects(&self, other: &OrientedBoundingBox) -> bool {
+        mesh_with(self, other)
+    }
+}
+
+
+// oriented boxes
+impl Intersects<OrientedBoundingBox> for OrientedBoundingBox {
+    fn intersects(&self, other: &OrientedBoundingBox) -> bool {
+        self.is_intersecting_obb(other)
+    }
+}
+
+impl Intersects<BoundingSphere> for OrientedBoundingBox {
+    fn intersects(&self, other: &BoundingSphere) -> bool {
+        sphere_obb(other, self)
+    }
+}
+
+impl Intersects<OrientedBoundingBox> for BoundingSphere {
+    fn intersects(&self, other: &OrientedBoundingBox) -> bool {
+        sphere_obb(self, other)
+    }
+}
+
+impl Intersects<AABoundingBox> for OrientedBoundingBox {
+    fn intersects(&self, other: &AABoundingBox) -> bool {
+        self.is_intersecting_obb(&(*other).into())
+    }
+}
+
+impl Intersects<OrientedBoundingBox> for AABoundingBox {
+    fn intersects(&self, other: &OrientedBoundingBox) -> bool {
+        other.is_intersecting_obb(&(*self).into())
+    }
+}
+
+impl Intersects<TriangleCollider> for OrientedBoundingBox {
+    fn intersects(&self, other: &TriangleCollider) -> bool {
+        gjk_intersects(self, other)
+    }
+}
+
+impl Intersects<OrientedBoundingBox> for TriangleCollider {
+    fn intersects(&self, other: &OrientedBoundingBox) -> bool {
+        gjk_intersects(self, other)
+    }
+}
+
+impl Intersects<PlaneCollider> for OrientedBoundingBox {
+    fn intersects(&self, other: &PlaneCollider) -> bool {
+        self.is_intersecting_obb(&other.get_bounds().into())
+    }
+}
+
+impl Intersects<OrientedBoundingBox> for PlaneCollider {
+    fn intersects(&self, other: &OrientedBoundingBox) -> bool {
+        other.is_intersecting_obb(&self.get_bounds().into())
+    }
+}
+
+impl Intersects<MeshCollider> for OrientedBoundingBox {
+    fn intersects(&self, other: &MeshCollider) -> bool {
+        mesh_with(other, self)
+    }
+}
+
+
+// capsules, cylinders and cones
+impl Intersects<BoundingSphere> for CapsuleCollider {
+    fn intersects(&self, other: &BoundingSphere) -> bool {
+        self.is_intersecting_sphere(*other)
+    }
+}
+
+impl Intersects<CapsuleCollider> for BoundingSphere {
+    fn intersects(&self, other: &CapsuleCollider) -> bool {
+        other.is_intersecting_sphere(*self)
+    }
+}
+
+impl Intersects<BoundingSphere> for CylinderCollider {
+    fn intersects(&self, other: &BoundingSphere) -> bool {
+        self.is_intersecting_sphere(*other)
+    }
+}
+
+impl Intersects<CylinderCollider> for BoundingSphere {
+    fn intersects(&self, other: &CylinderCollider) -> bool {
+        other.is_intersecting_sphere(*self)
+    }
+}
+
+impl Intersects<BoundingSphere> for ConeCollider {
+    fn intersects(&self, other: &BoundingSphere) -> bool {
+        self.is_intersecting_sphere(*other)
+    }
+}
+
+impl Intersects<ConeCollider> for BoundingSphere {
+    fn intersects(&self, other: &ConeCollider) -> bool {
+        other.is_intersecting_sphere(*self)
+    }
+}
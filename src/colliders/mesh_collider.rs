@@ -1,11 +1,12 @@
 #![allow(dead_code, unused_variables, unused_imports)]
-use super::{Vector3, Collider, RayHitInfo, triangle_collider::TriangleCollider, AABoundingBox, quicksort};
+use super::{Vector3, Collider, RayHitInfo, triangle_collider::TriangleCollider, AABoundingBox, Ray, bvh::Bvh};
 
 
 #[derive(Default, Debug, PartialEq, Clone)]
 pub struct MeshCollider {
     tris: Vec<TriangleCollider>,
-    bounds: AABoundingBox
+    bounds: AABoundingBox,
+    bvh: Bvh,
 }
 
 
@@ -18,15 +19,48 @@ impl MeshCollider {
         }
 
         let bounds = AABoundingBox::from_points(vertices);
+        let bvh = Bvh::new(&tris);
         MeshCollider {
             tris,
-            bounds
+            bounds,
+            bvh
         }
     }
+
+    pub fn get_triangles(&self) -> &[TriangleCollider] {
+        &self.tris
+    }
+
+    pub fn get_bounds(&self) -> AABoundingBox {
+        self.bounds
+    }
+
+    /// the triangles with bounds overlapping the given box, found using the bounding volume hierarchy
+    pub fn get_triangles_in(&self, bounds: AABoundingBox) -> Vec<&TriangleCollider> {
+        let mut found = Vec::new();
+        self.bvh.query_box(bounds, |tri| found.push(&self.tris[tri]));
+        found
+    }
+
+    /// whether rays hitting the back of the triangles are ignored, the back being the side facing away from the normal
+    pub fn set_back_face_culling(&mut self, cull: bool) {
+        self.tris.iter_mut().for_each(|tri| tri.set_back_face_culling(cull));
+    }
+
+    /// the closest point on any of the triangles, None if there are no triangles
+    pub fn closest_point_on_surface(&self, point: impl Into<Vector3>) -> Option<Vector3> {
+        self.bvh.closest_point(&self.tris, point.into())
+    }
+
+    /// how many levels deep the bounding volume hierarchy over the triangles goes
+    pub fn get_bvh_depth(&self) -> usize {
+        self.bvh.depth()
+    }
 }
 
 impl Collider for MeshCollider {
-    /// for checking intersections of a mesh and ray, I chose to sort the mesh triangles by distance from the ray root and loop through them
+    /// the triangles are kept in a bounding volume hierarchy built when the mesh is made,
+    /// so only the triangles in boxes the ray passes through are checked and the nearest hit is returned
     /// 
     /// A mesh does not check if a point is contained as there is not guarantee there is an inside to a mesh
     fn check_ray(
@@ -38,26 +72,17 @@ impl Collider for MeshCollider {
         let (root_position, direction): (Vector3, Vector3) = (root_position.into(), direction.into());
         let direction = direction.normalised();
 
-        if self.bounds.check_ray(root_position, direction, max_distance).is_none() {
-            // println!("failed mesh collide on bounds\n- Origin: {:?} \n- Direction: {:?} \n- Bounds: {:?}", root_position, direction, self.bounds);
-            return None;
-        }
+        self.bvh.check_ray(&self.tris, &Ray::with_range(root_position, direction, 0.0, max_distance.unwrap_or(f32::INFINITY)))
+    }
 
-        // sort the tris by distance
-        let dist_indices = {
-            let mut indices = Vec::new();
-            for i in 0..self.tris.len() {
-                indices.push((self.tris[i].centre_dist_to(root_position), i));
-            }
-            indices
-        };
-
-        let sorted_indices = quicksort(dist_indices);
-        for index in sorted_indices {
-            let check = self.tris[index.1].check_ray(root_position, direction, max_distance);
-            if check.is_some() {return check;}
-        }
-        None
+    fn cast_ray(&self, ray: &Ray) -> Option<RayHitInfo> {
+        self.bvh.check_ray(&self.tris, ray)
     }
-}
 
+    /// every triangle the ray passes through, as a mesh can be hit any number of times
+    fn cast_all(&self, ray: &Ray) -> Vec<RayHitInfo> {
+        let mut hits = self.bvh.check_ray_all(&self.tris, ray);
+        hits.sort_by(|a, b| a.hit_distance.total_cmp(&b.hit_distance));
+        hits
+    }
+}
@@ -1,16 +1,46 @@
-use super::{vectors::*, matrices::Matrix3, quicksort, solve_quadratic};
+use super::{vectors::*, matrices::Matrix3, solve_quadratic};
 
 mod mesh_collider;
+mod bvh;
+mod ray;
+mod shape_cast;
+mod point_query;
 mod plane_collider;
+mod plane;
+mod quad_collider;
 mod triangle_collider;
 mod aabb;
 mod bounding_sphere;
+mod obb;
+mod capsule_collider;
+mod cylinder_collider;
+mod cone_collider;
+mod intersection;
+mod convex_hull;
+mod gjk;
+mod dynamic_tree;
+mod collision_world;
+mod anchored_collider;
 
 pub use mesh_collider::MeshCollider;
+pub use ray::Ray;
+pub use shape_cast::ShapeCast;
+pub use point_query::{PointQuery, closest_points_segment_segment, closest_points_segment_triangle};
 pub use bounding_sphere::BoundingSphere;
 pub use plane_collider::PlaneCollider;
+pub use plane::Plane;
+pub use quad_collider::QuadCollider;
 pub use triangle_collider::TriangleCollider;
 pub use aabb::AABoundingBox;
+pub use obb::OrientedBoundingBox;
+pub use capsule_collider::CapsuleCollider;
+pub use cylinder_collider::CylinderCollider;
+pub use cone_collider::ConeCollider;
+pub use intersection::Intersects;
+pub use convex_hull::ConvexHull;
+pub use collision_world::{Bounded, WorldCollider, ColliderHandle, CollisionWorld};
+pub use anchored_collider::AnchoredCollider;
+pub use gjk::{SupportMap, ClosestPoints, ContactManifold, gjk_intersects, gjk_closest_points, epa_contact_manifold};
 
 
 pub trait Collider {
@@ -20,13 +50,33 @@ pub trait Collider {
         direction: impl Into<Vector3>,
         max_distance: Option<f32>,
     ) -> Option<RayHitInfo>;
+
+    /// checks the ray from its `t_min` to its `t_max`, with the hit distance measured from the ray origin
+    fn cast_ray(&self, ray: &Ray) -> Option<RayHitInfo> {
+        let start = ray.t_min.max(0.0);
+        let mut hit = self.check_ray(ray.at(start), ray.direction, ray.max_distance().map(|max| max - start))?;
+        hit.hit_distance += start;
+        Some(hit)
+    }
+
+    /// every hit along the ray sorted from nearest to furthest
+    ///
+    /// solid shapes only give the first hit as the ray is inside them after that
+    fn cast_all(&self, ray: &Ray) -> Vec<RayHitInfo> {
+        self.cast_ray(ray).into_iter().collect()
+    }
 }
 
+/// where a ray or cast shape hit a collider
+///
+/// for shape casts the hit position is the contact point, the distance is how far the shape moved before it touched,
+/// which is its time of impact, and the shape position is where the centre of the shape was at that point
 #[derive(Debug)]
 pub struct RayHitInfo {
     pub hit_position: Vector3,
     pub hit_distance: f32,
     pub hit_normal: Vector3,
+    pub shape_position: Vector3,
 }
 
 impl RayHitInfo {
@@ -34,7 +84,17 @@ impl RayHitInfo {
         RayHitInfo{
             hit_position: position,
             hit_distance: dist,
-            hit_normal: surface_normal
+            hit_normal: surface_normal,
+            shape_position: position
+        }
+    }
+
+    pub fn shape_hit(contact_point: Vector3, dist: f32, surface_normal: Vector3, shape_position: Vector3) -> Self {
+        RayHitInfo {
+            hit_position: contact_point,
+            hit_distance: dist,
+            hit_normal: surface_normal,
+            shape_position
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,167 @@
+use super::{Vector3, Matrix3, AABoundingBox, Collider, RayHitInfo};
+
+
+/// a box that can be rotated, made of a centre, the half size along each axis and the box axes as the columns of the basis
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct OrientedBoundingBox {
+    pub centre: Vector3,
+    pub half_extents: Vector3,
+    pub basis: Matrix3,
+}
+
+impl Default for OrientedBoundingBox {
+    fn default() -> Self {
+        OrientedBoundingBox::new(Vector3::ZERO, Vector3::ZERO, Matrix3::IDENTITY)
+    }
+}
+
+
+impl OrientedBoundingBox {
+    /// the basis should be a rotation matrix, such as `Matrix3::from_euler_angles`
+    pub fn new(centre: impl Into<Vector3>, half_extents: impl Into<Vector3>, basis: Matrix3) -> Self {
+        OrientedBoundingBox {
+            centre: centre.into(),
+            half_extents: half_extents.into(),
+            basis
+        }
+    }
+
+    /// a best fit box for the points, with the box axes along the principal axes of the points
+    pub fn from_points(points: &[Vector3]) -> Self {
+        if points.is_empty() {
+            return OrientedBoundingBox::default();
+        }
+
+        let (_, covariance) = Matrix3::covariance(points);
+        let (_, mut basis) = covariance.symmetric_eigen();
+        // keep the basis a rotation rather than a reflection
+        if basis.determinant() < 0.0 {
+            basis = Matrix3::from_columns(basis.c0(), basis.c1(), -basis.c2());
+        }
+
+        // the points are boxed in the local space of the axes
+        let inverse = basis.transposed();
+        let local_bounds = AABoundingBox::from_points(points.iter().map(|point| inverse * *point).collect());
+        let local_centre = (local_bounds.min_corner + local_bounds.max_corner) * 0.5;
+
+        OrientedBoundingBox {
+            centre: basis * local_centre,
+            half_extents: (local_bounds.max_corner - local_bounds.min_corner) * 0.5,
+            basis
+        }
+    }
+
+    pub fn axes(&self) -> [Vector3; 3] {
+        [self.basis.c0(), self.basis.c1(), self.basis.c2()]
+    }
+
+    pub fn to_local(&self, point: impl Into<Vector3>) -> Vector3 {
+        self.basis.transposed() * (point.into() - self.centre)
+    }
+
+    pub fn to_world(&self, point: impl Into<Vector3>) -> Vector3 {
+        self.basis * point.into() + self.centre
+    }
+
+    pub fn corners(&self) -> [Vector3; 8] {
+        AABoundingBox::new(-self.half_extents, self.half_extents).corners().map(|corner| self.to_world(corner))
+    }
+
+    pub fn contains_point(&self, point: impl Into<Vector3>) -> bool {
+        let local = self.to_local(point);
+        local.x.abs() <= self.half_extents.x && local.y.abs() <= self.half_extents.y && local.z.abs() <= self.half_extents.z
+    }
+
+    /// the closest point in the box to the given point, which is the point itself if it is inside
+    pub fn closest_point(&self, point: impl Into<Vector3>) -> Vector3 {
+        let local = AABoundingBox::new(-self.half_extents, self.half_extents).closest_point(self.to_local(point));
+        self.to_world(local)
+    }
+
+    /// the smallest axis aligned box that contains this box
+    pub fn bounding_box(&self) -> AABoundingBox {
+        let [x, y, z] = self.axes();
+        let extent = Vector3::new(
+            x.x.abs() * self.half_extents.x + y.x.abs() * self.half_extents.y + z.x.abs() * self.half_extents.z,
+            x.y.abs() * self.half_extents.x + y.y.abs() * self.half_extents.y + z.y.abs() * self.half_extents.z,
+            x.z.abs() * self.half_extents.x + y.z.abs() * self.half_extents.y + z.z.abs() * self.half_extents.z,
+        );
+        AABoundingBox::new(self.centre - extent, self.centre + extent)
+    }
+
+    /// separating axis test using the 3 axes of each box and the 9 cross products between them,
+    /// taken from Real-Time Collision Detection by Christer Ericson
+    pub fn is_intersecting_obb(&self, other: &OrientedBoundingBox) -> bool {
+        let (a_axes, b_axes) = (self.axes(), other.axes());
+        let a_extents: [f32; 3] = self.half_extents.into();
+        let b_extents: [f32; 3] = other.half_extents.into();
+
+        // the other box's rotation and position in this box's space,
+        // with an epsilon to stop the cross products of near parallel axes giving false separations
+        let rotation = a_axes.map(|a_axis| b_axes.map(|b_axis| a_axis.dot(b_axis)));
+        let abs_rotation = rotation.map(|row| row.map(|val| val.abs() + f32::EPSILON));
+        let offset = other.centre - self.centre;
+        let t = a_axes.map(|axis| offset.dot(axis));
+
+        // this box's axes
+        for i in 0..3 {
+            let rb = b_extents[0] * abs_rotation[i][0] + b_extents[1] * abs_rotation[i][1] + b_extents[2] * abs_rotation[i][2];
+            if t[i].abs() > a_extents[i] + rb {return false;}
+        }
+
+        // the other box's axes
+        for j in 0..3 {
+            let ra = a_extents[0] * abs_rotation[0][j] + a_extents[1] * abs_rotation[1][j] + a_extents[2] * abs_rotation[2][j];
+            let dist = t[0] * rotation[0][j] + t[1] * rotation[1][j] + t[2] * rotation[2][j];
+            if dist.abs() > ra + b_extents[j] {return false;}
+        }
+
+        // the cross products of each pair of axes
+        for i in 0..3 {
+            let (i1, i2) = ((i + 1) % 3, (i + 2) % 3);
+            for j in 0..3 {
+                let (j1, j2) = ((j + 1) % 3, (j + 2) % 3);
+                let ra = a_extents[i1] * abs_rotation[i2][j] + a_extents[i2] * abs_rotation[i1][j];
+                let rb = b_extents[j1] * abs_rotation[i][j2] + b_extents[j2] * abs_rotation[i][j1];
+                let dist = t[i2] * rotation[i1][j] - t[i1] * rotation[i2][j];
+                if dist.abs() > ra + rb {return false;}
+            }
+        }
+        true
+    }
+}
+
+
+impl From<AABoundingBox> for OrientedBoundingBox {
+    fn from(value: AABoundingBox) -> Self {
+        OrientedBoundingBox {
+            centre: (value.min_corner + value.max_corner) * 0.5,
+            half_extents: (value.max_corner - value.min_corner) * 0.5,
+            basis: Matrix3::IDENTITY
+        }
+    }
+}
+
+impl From<OrientedBoundingBox> for AABoundingBox {
+    fn from(value: OrientedBoundingBox) -> Self {
+        value.bounding_box()
+    }
+}
+
+
+impl Collider for OrientedBoundingBox {
+    /// the ray is moved into the local space of the box and checked against it as an axis aligned box
+    fn check_ray(
+        &self,
+        root_position: impl Into<Vector3>,
+        direction: impl Into<Vector3>,
+        max_distance: Option<f32>,
+    ) -> Option<RayHitInfo> {
+        let local_root = self.to_local(root_position);
+        let local_direction = self.basis.transposed() * direction.into();
+
+        let local_box = AABoundingBox::new(-self.half_extents, self.half_extents);
+        let hit = local_box.check_ray(local_root, local_direction, max_distance)?;
+        Some(RayHitInfo::new(self.to_world(hit.hit_position), hit.hit_distance, self.basis * hit.hit_normal))
+    }
+}
@@ -0,0 +1,110 @@
+use super::{Vector3, Collider, RayHitInfo};
+
+
+/// an infinite plane, made of all the points where `normal.dot(point) == distance`
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Plane {
+    pub normal: Vector3,
+    pub distance: f32,
+}
+
+impl Default for Plane {
+    fn default() -> Self {
+        Plane::new(Vector3::Y, 0.0)
+    }
+}
+
+
+impl Plane {
+    /// the normal is normalised, with the distance scaled to match
+    pub fn new(normal: impl Into<Vector3>, distance: f32) -> Self {
+        let normal = normal.into();
+        let length = normal.magnitude();
+        Plane {
+            normal: normal / length,
+            distance: distance / length
+        }
+    }
+
+    pub fn from_point_normal(point: impl Into<Vector3>, normal: impl Into<Vector3>) -> Self {
+        let normal = normal.into().normalised();
+        Plane {
+            normal,
+            distance: normal.dot(point.into())
+        }
+    }
+
+    /// the plane through the three points, with the normal facing the side the points go anticlockwise around.
+    /// None if the points are in a line
+    pub fn from_points(a: impl Into<Vector3>, b: impl Into<Vector3>, c: impl Into<Vector3>) -> Option<Self> {
+        let (a, b, c) = (a.into(), b.into(), c.into());
+        let normal = (b - a).cross(c - a);
+        if normal.sqr_magnitude() <= f32::EPSILON * f32::EPSILON {return None;}
+        Some(Plane::from_point_normal(a, normal))
+    }
+
+    /// how far the point is above the plane, negative if it is behind it
+    pub fn signed_distance(&self, point: impl Into<Vector3>) -> f32 {
+        self.normal.dot(point.into()) - self.distance
+    }
+
+    /// the closest point on the plane
+    pub fn project_point(&self, point: impl Into<Vector3>) -> Vector3 {
+        let point = point.into();
+        point - self.normal * self.signed_distance(point)
+    }
+
+    /// the point mirrored to the other side of the plane
+    pub fn reflect_point(&self, point: impl Into<Vector3>) -> Vector3 {
+        let point = point.into();
+        point - self.normal * (2.0 * self.signed_distance(point))
+    }
+
+    /// the direction bounced off the plane
+    pub fn reflect_direction(&self, direction: impl Into<Vector3>) -> Vector3 {
+        let direction = direction.into();
+        direction - self.normal * (2.0 * direction.dot(self.normal))
+    }
+
+    /// the same plane facing the other way
+    pub fn flipped(&self) -> Self {
+        Plane {
+            normal: -self.normal,
+            distance: -self.distance
+        }
+    }
+
+    /// the point where the three planes meet, None if any two of them are parallel
+    pub fn intersect_planes(a: &Plane, b: &Plane, c: &Plane) -> Option<Vector3> {
+        let (bc, ca, ab) = (b.normal.cross(c.normal), c.normal.cross(a.normal), a.normal.cross(b.normal));
+        let denominator = a.normal.dot(bc);
+        if denominator.abs() <= f32::EPSILON {return None;}
+        Some((bc * a.distance + ca * b.distance + ab * c.distance) / denominator)
+    }
+}
+
+
+impl Collider for Plane {
+    /// the hit normal faces the side of the plane the ray came from
+    fn check_ray(
+        &self,
+        root_position: impl Into<Vector3>,
+        direction: impl Into<Vector3>,
+        max_distance: Option<f32>,
+    ) -> Option<RayHitInfo> {
+        let (root_position, direction): (Vector3, Vector3) = (root_position.into(), direction.into());
+        let direction = direction.normalised();
+
+        let height = self.signed_distance(root_position);
+        if height == 0.0 {return Some(RayHitInfo::new(root_position, 0.0, Vector3::ZERO));}
+
+        let facing = direction.dot(self.normal);
+        if facing == 0.0 {return None;}
+
+        let distance = -height / facing;
+        if distance < 0.0 || max_distance.is_some_and(|max| distance > max) {return None;}
+
+        let normal = if height > 0.0 {self.normal} else {-self.normal};
+        Some(RayHitInfo::new(root_position + direction * distance, distance, normal))
+    }
+}
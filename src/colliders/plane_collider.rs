@@ -1,4 +1,4 @@
-use super::{Vector3, Vector2, Collider, RayHitInfo};
+use super::{Vector3, Vector2, Collider, RayHitInfo, AABoundingBox};
 
 
 /// Axis Alligned Plane Collider
@@ -22,6 +22,19 @@ impl PlaneCollider {
             centre: position + scale
         }
     }
+
+    pub fn get_position(&self) -> Vector3 {
+        self.position
+    }
+
+    pub fn get_size(&self) -> Vector2 {
+        Vector2::new(self.x_length, self.z_length)
+    }
+
+    /// the plane as a box with no height
+    pub fn get_bounds(&self) -> AABoundingBox {
+        AABoundingBox::new(self.position, self.position + Vector3::new(self.x_length, 0.0, self.z_length))
+    }
 }
 
 impl Collider for PlaneCollider {
@@ -46,7 +59,8 @@ impl Collider for PlaneCollider {
 
         let point = root_position + direction * distance;
 
-        if (point.x - self.position.x > self.x_length) || (point.z - self.position.z > self.z_length) {return None;}
+        let (x, z) = (point.x - self.position.x, point.z - self.position.z);
+        if !(0.0..=self.x_length).contains(&x) || !(0.0..=self.z_length).contains(&z) {return None;}
 
         Some(RayHitInfo::new(point, distance, Vector3::Y))
     }
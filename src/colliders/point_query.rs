@@ -0,0 +1,210 @@
+use super::{
+    Vector3, AABoundingBox, BoundingSphere, CapsuleCollider, ConeCollider, CylinderCollider, MeshCollider, OrientedBoundingBox,
+    Plane, PlaneCollider, QuadCollider, TriangleCollider
+};
+
+
+// how far from a surface a point can be and still be counted as on it
+const SURFACE_TOLERANCE: f32 = 1e-5;
+
+
+/// nearest point and distance queries from a point to a collider
+///
+/// solid shapes give the point itself as the closest point when it is inside them, with a distance of 0.
+/// Surfaces such as triangles, planes and meshes only contain points lying on them
+pub trait PointQuery {
+    fn closest_point(&self, point: impl Into<Vector3>) -> Vector3;
+
+    fn contains_point(&self, point: impl Into<Vector3>) -> bool;
+
+    fn distance_to_point(&self, point: impl Into<Vector3>) -> f32 {
+        let point = point.into();
+        (self.closest_point(point) - point).magnitude()
+    }
+}
+
+
+impl PointQuery for TriangleCollider {
+    fn closest_point(&self, point: impl Into<Vector3>) -> Vector3 {
+        TriangleCollider::closest_point(self, point)
+    }
+
+    fn contains_point(&self, point: impl Into<Vector3>) -> bool {
+        self.distance_to_point(point) <= SURFACE_TOLERANCE
+    }
+}
+
+impl PointQuery for AABoundingBox {
+    fn closest_point(&self, point: impl Into<Vector3>) -> Vector3 {
+        AABoundingBox::closest_point(self, point)
+    }
+
+    fn contains_point(&self, point: impl Into<Vector3>) -> bool {
+        AABoundingBox::contains_point(self, point.into())
+    }
+}
+
+impl PointQuery for BoundingSphere {
+    fn closest_point(&self, point: impl Into<Vector3>) -> Vector3 {
+        BoundingSphere::closest_point(self, point)
+    }
+
+    fn contains_point(&self, point: impl Into<Vector3>) -> bool {
+        (point.into() - self.centre).sqr_magnitude() <= self.radius * self.radius
+    }
+}
+
+impl PointQuery for OrientedBoundingBox {
+    fn closest_point(&self, point: impl Into<Vector3>) -> Vector3 {
+        OrientedBoundingBox::closest_point(self, point)
+    }
+
+    fn contains_point(&self, point: impl Into<Vector3>) -> bool {
+        OrientedBoundingBox::contains_point(self, point)
+    }
+}
+
+impl PointQuery for Plane {
+    fn closest_point(&self, point: impl Into<Vector3>) -> Vector3 {
+        self.project_point(point)
+    }
+
+    fn contains_point(&self, point: impl Into<Vector3>) -> bool {
+        self.signed_distance(point).abs() <= SURFACE_TOLERANCE
+    }
+
+    fn distance_to_point(&self, point: impl Into<Vector3>) -> f32 {
+        self.signed_distance(point).abs()
+    }
+}
+
+impl PointQuery for PlaneCollider {
+    fn closest_point(&self, point: impl Into<Vector3>) -> Vector3 {
+        self.get_bounds().closest_point(point)
+    }
+
+    fn contains_point(&self, point: impl Into<Vector3>) -> bool {
+        self.distance_to_point(point) <= SURFACE_TOLERANCE
+    }
+}
+
+impl PointQuery for QuadCollider {
+    fn closest_point(&self, point: impl Into<Vector3>) -> Vector3 {
+        QuadCollider::closest_point(self, point)
+    }
+
+    fn contains_point(&self, point: impl Into<Vector3>) -> bool {
+        self.distance_to_point(point) <= SURFACE_TOLERANCE
+    }
+}
+
+impl PointQuery for CapsuleCollider {
+    fn closest_point(&self, point: impl Into<Vector3>) -> Vector3 {
+        CapsuleCollider::closest_point(self, point)
+    }
+
+    fn contains_point(&self, point: impl Into<Vector3>) -> bool {
+        CapsuleCollider::contains_point(self, point)
+    }
+}
+
+impl PointQuery for CylinderCollider {
+    fn closest_point(&self, point: impl Into<Vector3>) -> Vector3 {
+        CylinderCollider::closest_point(self, point)
+    }
+
+    fn contains_point(&self, point: impl Into<Vector3>) -> bool {
+        CylinderCollider::contains_point(self, point)
+    }
+}
+
+impl PointQuery for ConeCollider {
+    fn closest_point(&self, point: impl Into<Vector3>) -> Vector3 {
+        ConeCollider::closest_point(self, point)
+    }
+
+    fn contains_point(&self, point: impl Into<Vector3>) -> bool {
+        ConeCollider::contains_point(self, point)
+    }
+}
+
+impl PointQuery for MeshCollider {
+    /// the closest point on the surface of the mesh, found using the bounding volume hierarchy.
+    /// An empty mesh gives back the point itself
+    fn closest_point(&self, point: impl Into<Vector3>) -> Vector3 {
+        let point = point.into();
+        self.closest_point_on_surface(point).unwrap_or(point)
+    }
+
+    fn contains_point(&self, point: impl Into<Vector3>) -> bool {
+        self.distance_to_point(point) <= SURFACE_TOLERANCE
+    }
+}
+
+
+/// the closest points between the line segments `p1` to `q1` and `p2` to `q2`, in the same order,
+/// taken from Real-Time Collision Detection by Christer Ericson
+pub fn closest_points_segment_segment(
+    p1: impl Into<Vector3>, q1: impl Into<Vector3>,
+    p2: impl Into<Vector3>, q2: impl Into<Vector3>
+) -> (Vector3, Vector3) {
+    let (p1, q1, p2, q2) = (p1.into(), q1.into(), p2.into(), q2.into());
+    let (d1, d2) = (q1 - p1, q2 - p2);
+    let r = p1 - p2;
+    let (a, e, f) = (d1.dot(d1), d2.dot(d2), d2.dot(r));
+
+    // either or both segments are points
+    if a <= f32::EPSILON && e <= f32::EPSILON {return (p1, p2);}
+    if a <= f32::EPSILON {
+        return (p1, p2 + d2 * (f / e).clamp(0.0, 1.0));
+    }
+    let c = d1.dot(r);
+    if e <= f32::EPSILON {
+        return (p1 + d1 * (-c / a).clamp(0.0, 1.0), p2);
+    }
+
+    let b = d1.dot(d2);
+    let denominator = a * e - b * b;
+    // parallel segments have no single closest pair so any point on the first will do
+    let mut s = if denominator > f32::EPSILON {((b * f - c * e) / denominator).clamp(0.0, 1.0)} else {0.0};
+    let mut t = (b * s + f) / e;
+
+    // clamp t and redo s for the new t if it was outside the segment
+    if t < 0.0 {
+        t = 0.0;
+        s = (-c / a).clamp(0.0, 1.0);
+    } else if t > 1.0 {
+        t = 1.0;
+        s = ((b - c) / a).clamp(0.0, 1.0);
+    }
+    (p1 + d1 * s, p2 + d2 * t)
+}
+
+/// the closest points between the line segment `p` to `q` and the triangle, the point on the segment first
+pub fn closest_points_segment_triangle(p: impl Into<Vector3>, q: impl Into<Vector3>, triangle: &TriangleCollider) -> (Vector3, Vector3) {
+    let (p, q) = (p.into(), q.into());
+    let points = triangle.get_points();
+    let [a, b, c] = [points.c0(), points.c1(), points.c2()];
+
+    // the segment going through the triangle, worked out here rather than with a ray so back face culling doesn't matter
+    let normal = (b - a).cross(c - a);
+    let (p_side, q_side) = ((p - a).dot(normal), (q - a).dot(normal));
+    if p_side * q_side <= 0.0 && p_side != q_side {
+        let crossing = p + (q - p) * (p_side / (p_side - q_side));
+        let inside = [(a, b), (b, c), (c, a)].into_iter()
+            .all(|(start, end)| (end - start).cross(crossing - start).dot(normal) >= 0.0);
+        if inside {return (crossing, crossing);}
+    }
+
+    // otherwise the closest points are between an end of the segment and the face, or the segment and an edge
+    let candidates = [
+        (p, triangle.closest_point(p)),
+        (q, triangle.closest_point(q)),
+        closest_points_segment_segment(p, q, a, b),
+        closest_points_segment_segment(p, q, b, c),
+        closest_points_segment_segment(p, q, c, a),
+    ];
+    candidates.into_iter()
+        .min_by(|(a1, a2), (b1, b2)| (*a1 - *a2).sqr_magnitude().total_cmp(&(*b1 - *b2).sqr_magnitude()))
+        .unwrap()
+}
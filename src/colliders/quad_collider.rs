@@ -0,0 +1,92 @@
+use super::{Vector3, Vector2, Matrix3, AABoundingBox, Collider, Plane, RayHitInfo};
+
+
+/// a flat rectangle that can be rotated, lying on the local x and z axes of the rotation with local y as its normal
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct QuadCollider {
+    pub centre: Vector3,
+    pub half_size: Vector2,
+    pub rotation: Matrix3,
+}
+
+impl Default for QuadCollider {
+    fn default() -> Self {
+        QuadCollider::new(Vector3::ZERO, Vector2::ZERO, Matrix3::IDENTITY)
+    }
+}
+
+
+impl QuadCollider {
+    /// the size is the full width along local x and depth along local z
+    pub fn new(centre: impl Into<Vector3>, size: impl Into<Vector2>, rotation: Matrix3) -> Self {
+        QuadCollider {
+            centre: centre.into(),
+            half_size: size.into() * 0.5,
+            rotation
+        }
+    }
+
+    pub fn get_size(&self) -> Vector2 {
+        self.half_size * 2.0
+    }
+
+    pub fn get_normal(&self) -> Vector3 {
+        self.rotation.c1()
+    }
+
+    /// the infinite plane the quad lies on
+    pub fn get_plane(&self) -> Plane {
+        Plane::from_point_normal(self.centre, self.get_normal())
+    }
+
+    pub fn to_local(&self, point: impl Into<Vector3>) -> Vector3 {
+        self.rotation.transposed() * (point.into() - self.centre)
+    }
+
+    pub fn to_world(&self, point: impl Into<Vector3>) -> Vector3 {
+        self.rotation * point.into() + self.centre
+    }
+
+    /// the corners going anticlockwise when looking down on the normal
+    pub fn corners(&self) -> [Vector3; 4] {
+        let (x, z) = (self.half_size.x, self.half_size.y);
+        [[-x, 0.0, -z], [-x, 0.0, z], [x, 0.0, z], [x, 0.0, -z]].map(|corner| self.to_world(corner))
+    }
+
+    pub fn contains_point(&self, point: impl Into<Vector3>) -> bool {
+        let local = self.to_local(point);
+        local.y.abs() <= f32::EPSILON && local.x.abs() <= self.half_size.x && local.z.abs() <= self.half_size.y
+    }
+
+    /// the closest point on the quad
+    pub fn closest_point(&self, point: impl Into<Vector3>) -> Vector3 {
+        let local = self.to_local(point);
+        self.to_world(Vector3::new(
+            local.x.clamp(-self.half_size.x, self.half_size.x),
+            0.0,
+            local.z.clamp(-self.half_size.y, self.half_size.y),
+        ))
+    }
+
+    pub fn bounding_box(&self) -> AABoundingBox {
+        AABoundingBox::from_points(self.corners().to_vec())
+    }
+}
+
+
+impl Collider for QuadCollider {
+    /// the ray is checked against the plane of the quad and then whether the hit is within its edges
+    fn check_ray(
+        &self,
+        root_position: impl Into<Vector3>,
+        direction: impl Into<Vector3>,
+        max_distance: Option<f32>,
+    ) -> Option<RayHitInfo> {
+        let (root_position, direction): (Vector3, Vector3) = (root_position.into(), direction.into());
+        let hit = self.get_plane().check_ray(root_position, direction, max_distance)?;
+
+        let local = self.to_local(hit.hit_position);
+        if local.x.abs() > self.half_size.x || local.z.abs() > self.half_size.y {return None;}
+        Some(hit)
+    }
+}
@@ -0,0 +1,66 @@
+use super::{Vector3, AABoundingBox};
+
+
+/// a ray going from the origin along the direction, only counting hits between `t_min` and `t_max` along it
+///
+/// the direction is always normalised and the inverse direction is kept alongside it for checking against boxes
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Ray {
+    pub origin: Vector3,
+    pub direction: Vector3,
+    pub inverse_direction: Vector3,
+    pub t_min: f32,
+    pub t_max: f32,
+}
+
+impl Default for Ray {
+    fn default() -> Self {
+        Ray::new(Vector3::ZERO, Vector3::Z)
+    }
+}
+
+
+impl Ray {
+    /// a ray with no limit on how far it goes
+    pub fn new(origin: impl Into<Vector3>, direction: impl Into<Vector3>) -> Self {
+        Ray::with_range(origin, direction, 0.0, f32::INFINITY)
+    }
+
+    pub fn with_range(origin: impl Into<Vector3>, direction: impl Into<Vector3>, t_min: f32, t_max: f32) -> Self {
+        let direction = direction.into().normalised();
+        Ray {
+            origin: origin.into(),
+            direction,
+            inverse_direction: Vector3::ONE / direction,
+            t_min,
+            t_max
+        }
+    }
+
+    /// the point the given distance along the ray
+    pub fn at(&self, distance: f32) -> Vector3 {
+        self.origin + self.direction * distance
+    }
+
+    /// the furthest the ray goes as the max distance taken by `Collider::check_ray`, None if it goes on forever
+    pub fn max_distance(&self) -> Option<f32> {
+        if self.t_max.is_finite() {Some(self.t_max)} else {None}
+    }
+
+    pub fn contains_distance(&self, distance: f32) -> bool {
+        (self.t_min..=self.t_max).contains(&distance)
+    }
+
+    /// the distance along the ray where it enters the box, or `t_min` if it is already inside at that point.
+    /// None if the ray misses the box within its range
+    pub fn box_distance(&self, bounds: &AABoundingBox) -> Option<f32> {
+        let t1 = (bounds.min_corner - self.origin) * self.inverse_direction;
+        let t2 = (bounds.max_corner - self.origin) * self.inverse_direction;
+
+        let entry = t1.x.min(t2.x).max(t1.y.min(t2.y)).max(t1.z.min(t2.z)).max(self.t_min);
+        let exit = t1.x.max(t2.x).min(t1.y.max(t2.y)).min(t1.z.max(t2.z)).min(self.t_max);
+
+        if exit < entry {return None;}
+        Some(entry)
+    }
+}
@@ -0,0 +1,235 @@
+use super::{Vector3, AABoundingBox, BoundingSphere, CapsuleCollider, Collider, MeshCollider, PlaneCollider, RayHitInfo, TriangleCollider};
+
+
+/// moving a shape along a direction and finding where it first touches the collider, so fast objects can't pass through thin geometry
+///
+/// the hit distance is the time of impact, measured in how far the shape moves along the normalised direction,
+/// the hit position is the contact point and the shape position is where the centre of the shape is at the time of impact.
+/// Like rays, a shape that starts touching the collider hits at a distance of 0 with a zero normal
+pub trait ShapeCast {
+    fn sphere_cast(&self, sphere: BoundingSphere, direction: impl Into<Vector3>, max_distance: Option<f32>) -> Option<RayHitInfo>;
+
+    fn box_cast(&self, bounds: AABoundingBox, direction: impl Into<Vector3>, max_distance: Option<f32>) -> Option<RayHitInfo>;
+}
+
+
+impl ShapeCast for BoundingSphere {
+    fn sphere_cast(&self, sphere: BoundingSphere, direction: impl Into<Vector3>, max_distance: Option<f32>) -> Option<RayHitInfo> {
+        let direction = direction.into().normalised();
+        let radius = self.radius + sphere.radius;
+
+        let offset = sphere.centre - self.centre;
+        if offset.sqr_magnitude() <= radius * radius {
+            return Some(RayHitInfo::shape_hit(self.closest_point(sphere.centre), 0.0, Vector3::ZERO, sphere.centre));
+        }
+
+        // a ray against the sphere grown by the cast sphere's radius
+        let b = direction.dot(offset);
+        let c = offset.sqr_magnitude() - radius * radius;
+        let discriminant = b * b - c;
+        if b > 0.0 || discriminant < 0.0 {return None;}
+
+        let dist = -b - discriminant.sqrt();
+        if max_distance.is_some_and(|max| dist > max) {return None;}
+
+        let position = sphere.centre + direction * dist;
+        let normal = (position - self.centre).normalised();
+        Some(RayHitInfo::shape_hit(self.centre + normal * self.radius, dist, normal, position))
+    }
+
+    fn box_cast(&self, bounds: AABoundingBox, direction: impl Into<Vector3>, max_distance: Option<f32>) -> Option<RayHitInfo> {
+        let direction = direction.into().normalised();
+        // the same as the sphere moving the other way into the box
+        let hit = sphere_cast_box(&bounds, *self, -direction, max_distance)?;
+
+        let moved = direction * hit.hit_distance;
+        Some(RayHitInfo::shape_hit(hit.hit_position + moved, hit.hit_distance, -hit.hit_normal, bounds.centre() + moved))
+    }
+}
+
+impl ShapeCast for AABoundingBox {
+    fn sphere_cast(&self, sphere: BoundingSphere, direction: impl Into<Vector3>, max_distance: Option<f32>) -> Option<RayHitInfo> {
+        sphere_cast_box(self, sphere, direction.into().normalised(), max_distance)
+    }
+
+    fn box_cast(&self, bounds: AABoundingBox, direction: impl Into<Vector3>, max_distance: Option<f32>) -> Option<RayHitInfo> {
+        let corners = self.corners();
+        box_cast_points(&corners, &[Vector3::X, Vector3::Y, Vector3::Z], bounds, direction.into().normalised(), max_distance)
+    }
+}
+
+impl ShapeCast for PlaneCollider {
+    fn sphere_cast(&self, sphere: BoundingSphere, direction: impl Into<Vector3>, max_distance: Option<f32>) -> Option<RayHitInfo> {
+        self.get_bounds().sphere_cast(sphere, direction, max_distance)
+    }
+
+    fn box_cast(&self, bounds: AABoundingBox, direction: impl Into<Vector3>, max_distance: Option<f32>) -> Option<RayHitInfo> {
+        self.get_bounds().box_cast(bounds, direction, max_distance)
+    }
+}
+
+impl ShapeCast for TriangleCollider {
+    /// the sphere is checked as a ray against the triangle moved out by the radius and capsules around each edge
+    fn sphere_cast(&self, sphere: BoundingSphere, direction: impl Into<Vector3>, max_distance: Option<f32>) -> Option<RayHitInfo> {
+        let direction = direction.into().normalised();
+        let closest = self.closest_point(sphere.centre);
+        if (closest - sphere.centre).sqr_magnitude() <= sphere.radius * sphere.radius {
+            return Some(RayHitInfo::shape_hit(closest, 0.0, Vector3::ZERO, sphere.centre));
+        }
+
+        let points = self.get_points();
+        let [a, b, c] = [points.c0(), points.c1(), points.c2()];
+
+        // the face on the side of the triangle the sphere is on
+        let normal = self.get_normal();
+        let height = (sphere.centre - a).dot(normal);
+        let normal = if height > 0.0 || (height == 0.0 && direction.dot(normal) < 0.0) {normal} else {-normal};
+        let offset = normal * sphere.radius;
+        let face = TriangleCollider::new(a + offset, b + offset, c + offset)
+            .check_ray(sphere.centre, direction, max_distance)
+            .map(|hit| RayHitInfo::new(hit.hit_position, hit.hit_distance, normal));
+
+        let edges = [(a, b), (b, c), (c, a)].map(|(start, end)| CapsuleCollider::new(start, end, sphere.radius).check_ray(sphere.centre, direction, max_distance));
+        let hit = nearest(face.into_iter().chain(edges.into_iter().flatten()))?;
+
+        Some(RayHitInfo::shape_hit(self.closest_point(hit.hit_position), hit.hit_distance, hit.hit_normal, hit.hit_position))
+    }
+
+    /// a separating axis test over time with the box axes, the triangle normal and the cross products of the edges and box axes
+    fn box_cast(&self, bounds: AABoundingBox, direction: impl Into<Vector3>, max_distance: Option<f32>) -> Option<RayHitInfo> {
+        let points = self.get_points();
+        let [a, b, c] = [points.c0(), points.c1(), points.c2()];
+
+        let mut axes = vec![Vector3::X, Vector3::Y, Vector3::Z, self.get_normal()];
+        for edge in [b - a, c - b, a - c] {
+            for axis in [Vector3::X, Vector3::Y, Vector3::Z] {
+                axes.push(edge.cross(axis));
+            }
+        }
+        box_cast_points(&[a, b, c], &axes, bounds, direction.into().normalised(), max_distance)
+    }
+}
+
+impl ShapeCast for MeshCollider {
+    fn sphere_cast(&self, sphere: BoundingSphere, direction: impl Into<Vector3>, max_distance: Option<f32>) -> Option<RayHitInfo> {
+        let direction = direction.into().normalised();
+        let radius = Vector3::ONE * sphere.radius;
+        let swept = self.swept_bounds(AABoundingBox::new(sphere.centre - radius, sphere.centre + radius), direction, max_distance);
+        nearest(self.get_triangles_in(swept).into_iter().filter_map(|tri| tri.sphere_cast(sphere, direction, max_distance)))
+    }
+
+    fn box_cast(&self, bounds: AABoundingBox, direction: impl Into<Vector3>, max_distance: Option<f32>) -> Option<RayHitInfo> {
+        let direction = direction.into().normalised();
+        let swept = self.swept_bounds(bounds, direction, max_distance);
+        nearest(self.get_triangles_in(swept).into_iter().filter_map(|tri| tri.box_cast(bounds, direction, max_distance)))
+    }
+}
+
+impl MeshCollider {
+    // the space the box covers as it moves, only as far as it could still reach the mesh
+    fn swept_bounds(&self, bounds: AABoundingBox, direction: Vector3, max_distance: Option<f32>) -> AABoundingBox {
+        let mesh_bounds = self.get_bounds();
+        let reach = (bounds.centre() - mesh_bounds.centre()).magnitude()
+            + (mesh_bounds.max_corner - mesh_bounds.min_corner).magnitude()
+            + (bounds.max_corner - bounds.min_corner).magnitude();
+        let moved = direction * max_distance.map_or(reach, |max| max.min(reach));
+        bounds.merged(AABoundingBox::new(bounds.min_corner + moved, bounds.max_corner + moved))
+    }
+}
+
+
+fn nearest(hits: impl Iterator<Item = RayHitInfo>) -> Option<RayHitInfo> {
+    hits.min_by(|a, b| a.hit_distance.total_cmp(&b.hit_distance))
+}
+
+// a ray against the box grown by the radius, with the edges and corners rounded off by capsules
+fn sphere_cast_box(bounds: &AABoundingBox, sphere: BoundingSphere, direction: Vector3, max_distance: Option<f32>) -> Option<RayHitInfo> {
+    let closest = bounds.closest_point(sphere.centre);
+    if (closest - sphere.centre).sqr_magnitude() <= sphere.radius * sphere.radius {
+        return Some(RayHitInfo::shape_hit(closest, 0.0, Vector3::ZERO, sphere.centre));
+    }
+
+    let radius = Vector3::ONE * sphere.radius;
+    let grown = AABoundingBox::new(bounds.min_corner - radius, bounds.max_corner + radius);
+
+    // hits on the flat faces only need to be outside the box on one axis
+    if !grown.contains_point(sphere.centre) {
+        let hit = grown.check_ray(sphere.centre, direction, max_distance)?;
+        let position = hit.hit_position;
+        let outside = [
+            position.x < bounds.min_corner.x || position.x > bounds.max_corner.x,
+            position.y < bounds.min_corner.y || position.y > bounds.max_corner.y,
+            position.z < bounds.min_corner.z || position.z > bounds.max_corner.z,
+        ].into_iter().filter(|outside| *outside).count();
+
+        if outside <= 1 {
+            return Some(RayHitInfo::shape_hit(bounds.closest_point(position), hit.hit_distance, hit.hit_normal, position));
+        }
+    }
+
+    let corners = bounds.corners();
+    let mut edges = Vec::new();
+    for i in 0..8 {
+        for bit in [1, 2, 4] {
+            if i & bit == 0 {edges.push((corners[i], corners[i | bit]));}
+        }
+    }
+
+    let hit = nearest(edges.into_iter().filter_map(|(start, end)| CapsuleCollider::new(start, end, sphere.radius).check_ray(sphere.centre, direction, max_distance)))?;
+    Some(RayHitInfo::shape_hit(bounds.closest_point(hit.hit_position), hit.hit_distance, hit.hit_normal, hit.hit_position))
+}
+
+// separating axis test for a moving box against a still convex shape made of the given points,
+// finding when the box first overlaps the shape on every axis at once
+fn box_cast_points(points: &[Vector3], axes: &[Vector3], bounds: AABoundingBox, direction: Vector3, max_distance: Option<f32>) -> Option<RayHitInfo> {
+    let (centre, half_size) = (bounds.centre(), (bounds.max_corner - bounds.min_corner) * 0.5);
+    let (mut enter, mut exit) = (f32::NEG_INFINITY, f32::INFINITY);
+    let mut normal = Vector3::ZERO;
+
+    for axis in axes {
+        if axis.sqr_magnitude() <= f32::EPSILON {continue;}
+        let axis = axis.normalised();
+
+        let box_radius = half_size.x * axis.x.abs() + half_size.y * axis.y.abs() + half_size.z * axis.z.abs();
+        let box_centre = centre.dot(axis);
+        let (min, max) = points.iter().fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), point| {
+            let projected = point.dot(axis);
+            (min.min(projected), max.max(projected))
+        });
+
+        let speed = direction.dot(axis);
+        if speed.abs() <= f32::EPSILON {
+            // not moving on this axis, so if it is separated now it always will be
+            if box_centre + box_radius < min || box_centre - box_radius > max {return None;}
+            continue;
+        }
+
+        let (t0, t1) = ((min - box_radius - box_centre) / speed, (max + box_radius - box_centre) / speed);
+        let (t0, t1) = if t0 > t1 {(t1, t0)} else {(t0, t1)};
+        if t0 > enter {
+            enter = t0;
+            normal = if speed > 0.0 {-axis} else {axis};
+        }
+        exit = exit.min(t1);
+        if enter > exit || exit < 0.0 {return None;}
+    }
+
+    if enter <= 0.0 {
+        let contact = bounds.closest_point(closest_of(points, centre));
+        return Some(RayHitInfo::shape_hit(contact, 0.0, Vector3::ZERO, centre));
+    }
+    if max_distance.is_some_and(|max| enter > max) {return None;}
+
+    let moved = direction * enter;
+    let moved_bounds = AABoundingBox::new(bounds.min_corner + moved, bounds.max_corner + moved);
+    let contact = moved_bounds.closest_point(closest_of(points, centre + moved));
+    Some(RayHitInfo::shape_hit(contact, enter, normal, centre + moved))
+}
+
+// the closest point on the convex shape made of up to 3 points, or the box of any more points
+fn closest_of(points: &[Vector3], target: Vector3) -> Vector3 {
+    match points {
+        [a, b, c] => TriangleCollider::new(*a, *b, *c).closest_point(target),
+        _ => AABoundingBox::from_points(points.to_vec()).closest_point(target),
+    }
+}
@@ -10,6 +10,8 @@ pub struct TriangleCollider {
     edge_two: Vector3,
 
     centre: Vector3,
+
+    cull_back_faces: bool,
 }
 
 impl TriangleCollider {
@@ -24,7 +26,8 @@ impl TriangleCollider {
             points: Matrix3::from_columns(a, b, c),
             edge_one,
             edge_two,
-            centre
+            centre,
+            cull_back_faces: false
         }
     }
 
@@ -40,6 +43,63 @@ impl TriangleCollider {
         self.centre
     }
 
+    pub fn get_normal(&self) -> Vector3 {
+        self.normal
+    }
+
+    /// whether rays hitting the back of the triangle are ignored, the back being the side facing away from the normal
+    pub fn set_back_face_culling(&mut self, cull: bool) {
+        self.cull_back_faces = cull;
+    }
+
+    pub fn get_back_face_culling(&self) -> bool {
+        self.cull_back_faces
+    }
+
+    /// the closest point on the triangle to the given point, taken from Real-Time Collision Detection by Christer Ericson
+    pub fn closest_point(&self, point: impl Into<Vector3>) -> Vector3 {
+        let p = point.into();
+        let (a, b, c) = (self.points.c0(), self.points.c1(), self.points.c2());
+        let (ab, ac) = (self.edge_one, self.edge_two);
+
+        // vertex region a
+        let ap = p - a;
+        let (d1, d2) = (ab.dot(ap), ac.dot(ap));
+        if d1 <= 0.0 && d2 <= 0.0 {return a;}
+
+        // vertex region b
+        let bp = p - b;
+        let (d3, d4) = (ab.dot(bp), ac.dot(bp));
+        if d3 >= 0.0 && d4 <= d3 {return b;}
+
+        // edge region ab
+        let vc = d1 * d4 - d3 * d2;
+        if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+            return a + ab * (d1 / (d1 - d3));
+        }
+
+        // vertex region c
+        let cp = p - c;
+        let (d5, d6) = (ab.dot(cp), ac.dot(cp));
+        if d6 >= 0.0 && d5 <= d6 {return c;}
+
+        // edge region ac
+        let vb = d5 * d2 - d1 * d6;
+        if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+            return a + ac * (d2 / (d2 - d6));
+        }
+
+        // edge region bc
+        let va = d3 * d6 - d5 * d4;
+        if va <= 0.0 && (d4 - d3) >= 0.0 && (d5 - d6) >= 0.0 {
+            return b + (c - b) * ((d4 - d3) / ((d4 - d3) + (d5 - d6)));
+        }
+
+        // inside the face
+        let denom = 1.0 / (va + vb + vc);
+        a + ab * (vb * denom) + ac * (vc * denom)
+    }
+
 }
 
 fn compute_incentre(a: Vector3, b: Vector3, c: Vector3) -> Vector3 {
@@ -76,6 +136,9 @@ impl Collider for TriangleCollider {
             return None;
         } // ray is parallel to triangle
 
+        // a is negative when the ray comes from behind the triangle
+        if self.cull_back_faces && a < 0.0 {return None;}
+
         let f = 1.0 / a;
         let s = root_position - self.points.c0();
         let u = f * s.dot(h);
@@ -4,6 +4,12 @@ pub use vectors::*;
 // matrices
 mod matrices;
 pub use matrices::*;
+// quaternions
+mod quaternion;
+pub use quaternion::Quaternion;
+// transforms
+mod transform;
+pub use transform::{Transform, TransformHierarchy, TransformId, TransformHierarchyError};
 // colliders
 mod colliders;
 pub use colliders::*;
@@ -15,7 +21,7 @@ mod interpolation;
 pub use interpolation::*;
 // camera maths
 mod camera;
-pub use camera::{Camera, CameraDirections};
+pub use camera::{Camera, DCamera, CameraDirections};
 
 use std::f32::consts::{FRAC_2_SQRT_PI, SQRT_2, PI};
 pub use rand_chacha::{ChaChaRng, rand_core::SeedableRng};
@@ -0,0 +1,135 @@
+use super::{MaxtrixNM, MatrixArithmeticError, Matrix3, DMatrix3};
+use super::super::vectors::{Vector3, DVector3};
+
+
+const MAX_SWEEPS: usize = 50;
+
+/// the eigenvalues of a symmetric matrix in ascending order,
+/// along with the matching orthonormal eigenvectors stored as the columns of a matrix
+#[derive(Debug, Clone)]
+pub struct SymmetricEigen {
+    pub eigenvalues: Vec<f32>,
+    pub eigenvectors: MaxtrixNM,
+}
+
+
+macro_rules! jacobi_eigen {
+    ($name:ident, $scalar:ident) => {
+        /// cyclic jacobi eigenvalue algorithm, repeatedly rotates away the off diagonal values until the matrix is diagonal
+        ///
+        /// only the upper triangle is read, the lower triangle is assumed to match
+        fn $name(mut a: Vec<Vec<$scalar>>) -> (Vec<$scalar>, Vec<Vec<$scalar>>) {
+            let n = a.len();
+            let mut v: Vec<Vec<$scalar>> = (0..n).map(|row| (0..n).map(|col| if row == col {1.0} else {0.0}).collect()).collect();
+            for row in 1..n {
+                let (upper, lower) = a.split_at_mut(row);
+                for (col, upper_row) in upper.iter().enumerate() {
+                    lower[0][col] = upper_row[row];
+                }
+            }
+
+            let scale: $scalar = a.iter().flatten().map(|val| val * val).sum();
+            for _ in 0..MAX_SWEEPS {
+                let off_diagonal: $scalar = a.iter()
+                    .enumerate()
+                    .map(|(p, row)| row[(p + 1)..].iter().map(|val| val * val).sum::<$scalar>())
+                    .sum();
+                if off_diagonal <= scale * $scalar::EPSILON * $scalar::EPSILON {break;}
+
+                for p in 0..n {
+                    for q in (p + 1)..n {
+                        if a[p][q] == 0.0 {continue;}
+
+                        // pick the rotation that zeroes a[p][q]
+                        let theta = (a[q][q] - a[p][p]) / (2.0 * a[p][q]);
+                        let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                        let c = 1.0 / (t * t + 1.0).sqrt();
+                        let s = t * c;
+
+                        for row in a.iter_mut() {
+                            let (kp, kq) = (row[p], row[q]);
+                            row[p] = c * kp - s * kq;
+                            row[q] = s * kp + c * kq;
+                        }
+                        let (upper, lower) = a.split_at_mut(q);
+                        for (pk, qk) in upper[p].iter_mut().zip(lower[0].iter_mut()) {
+                            let (old_pk, old_qk) = (*pk, *qk);
+                            *pk = c * old_pk - s * old_qk;
+                            *qk = s * old_pk + c * old_qk;
+                        }
+                        for row in v.iter_mut() {
+                            let (kp, kq) = (row[p], row[q]);
+                            row[p] = c * kp - s * kq;
+                            row[q] = s * kp + c * kq;
+                        }
+                    }
+                }
+            }
+
+            // sort ascending, moving the eigenvector columns along with their values
+            let mut order: Vec<usize> = (0..n).collect();
+            order.sort_by(|i, j| a[*i][*i].total_cmp(&a[*j][*j]));
+            let values = order.iter().map(|i| a[*i][*i]).collect();
+            let vectors = v.iter().map(|row| order.iter().map(|i| row[*i]).collect()).collect();
+            (values, vectors)
+        }
+    };
+}
+
+jacobi_eigen!(jacobi_eigen, f32);
+jacobi_eigen!(jacobi_eigen_f64, f64);
+
+
+impl MaxtrixNM {
+    /// finds the eigenvalues and eigenvectors of a symmetric matrix, only the upper triangle of the matrix is used
+    pub fn symmetric_eigen(&self) -> Result<SymmetricEigen, MatrixArithmeticError> {
+        if !self.is_square() {
+            return Err(MatrixArithmeticError::NotSquare);
+        }
+        let (values, vectors) = jacobi_eigen(self.matrix.clone());
+        Ok(SymmetricEigen {
+            eigenvalues: values,
+            eigenvectors: MaxtrixNM {matrix: vectors, width: self.width, height: self.height},
+        })
+    }
+}
+
+macro_rules! symmetric_eigen3 {
+    ($name:ident, $scalar:ident, $vector3:ident, $jacobi:ident) => {
+        impl $name {
+            /// finds the eigenvalues and eigenvectors of a symmetric matrix, such as an inertia tensor or covariance matrix
+            ///
+            /// the eigenvalues are given in ascending order, with the matching eigenvectors as the columns of the matrix
+            pub fn symmetric_eigen(&self) -> ($vector3, $name) {
+                let (values, vectors) = $jacobi(vec![
+                    vec![self.x.x, self.x.y, self.x.z],
+                    vec![self.y.x, self.y.y, self.y.z],
+                    vec![self.z.x, self.z.y, self.z.z],
+                ]);
+                (
+                    $vector3::new(values[0], values[1], values[2]),
+                    $name::new(
+                        vectors[0][0], vectors[0][1], vectors[0][2],
+                        vectors[1][0], vectors[1][1], vectors[1][2],
+                        vectors[2][0], vectors[2][1], vectors[2][2],
+                    )
+                )
+            }
+
+            /// calculates the mean and covariance matrix of a set of points, the eigenvectors of the covariance are the principal axes of the points
+            pub fn covariance(points: &[$vector3]) -> ($vector3, $name) {
+                if points.is_empty() {
+                    return ($vector3::ZERO, $name::default());
+                }
+                let count = points.len() as $scalar;
+                let mean = points.iter().fold($vector3::ZERO, |sum, point| sum + *point) / count;
+                let covariance = points.iter()
+                    .fold($name::default(), |sum, point| sum + $name::from((*point - mean).outer_product()));
+                (mean, covariance / count)
+            }
+        }
+    };
+}
+
+symmetric_eigen3!(Matrix3, f32, Vector3, jacobi_eigen);
+symmetric_eigen3!(DMatrix3, f64, DVector3, jacobi_eigen_f64);
@@ -0,0 +1,152 @@
+use super::{MaxtrixNM, MatrixArithmeticError};
+
+
+/// the result of `MaxtrixNM::qr`, where A = QR with Q orthogonal and R upper triangular
+///
+/// for an m by n matrix Q is m by m and R is m by n
+#[derive(Debug, Clone)]
+pub struct QRDecomposition {
+    pub q: MaxtrixNM,
+    pub r: MaxtrixNM,
+}
+
+/// the result of a least squares solve
+#[derive(Debug, Clone)]
+pub struct LeastSquaresSolution {
+    pub solution: Vec<f32>,
+    /// the length of Ax - b for the solution, zero if the system could be solved exactly
+    pub residual_norm: f32,
+    /// an estimate of the condition number of A from the diagonal of R,
+    /// the bigger it is the more the solution is affected by small errors in the input
+    pub condition_estimate: f32,
+}
+
+
+impl MaxtrixNM {
+    /// decomposes the matrix into an orthogonal and an upper triangular matrix using householder reflections
+    pub fn qr(&self) -> QRDecomposition {
+        let (m, n) = (self.height, self.width);
+        let mut r = self.matrix.clone();
+        let mut q = MaxtrixNM::new_identity(m).matrix;
+
+        for k in 0..n.min(m.saturating_sub(1)) {
+            // householder vector that reflects the column below the diagonal onto the first axis
+            let mut v: Vec<f32> = r[k..].iter().map(|row| row[k]).collect();
+            let norm = v.iter().map(|val| val * val).sum::<f32>().sqrt();
+            if norm == 0.0 {continue;}
+            let alpha = if v[0] > 0.0 {-norm} else {norm};
+            v[0] -= alpha;
+            let v_norm = v.iter().map(|val| val * val).sum::<f32>().sqrt();
+            if v_norm == 0.0 {continue;}
+            v.iter_mut().for_each(|val| *val /= v_norm);
+
+            // R = HR
+            for col in k..n {
+                let dot: f32 = r[k..].iter().zip(&v).map(|(row, val)| row[col] * val).sum();
+                for (row, val) in r[k..].iter_mut().zip(&v) {
+                    row[col] -= 2.0 * dot * val;
+                }
+            }
+            for row in r[(k + 1)..].iter_mut() {
+                row[k] = 0.0;
+            }
+
+            // Q = QH
+            for row in q.iter_mut() {
+                let dot: f32 = row[k..].iter().zip(&v).map(|(q_val, val)| q_val * val).sum();
+                for (q_val, val) in row[k..].iter_mut().zip(&v) {
+                    *q_val -= 2.0 * dot * val;
+                }
+            }
+        }
+
+        QRDecomposition {
+            q: MaxtrixNM {matrix: q, width: m, height: m},
+            r: MaxtrixNM {matrix: r, width: n, height: m},
+        }
+    }
+
+    /// multiplies the matrix by a vector, giving Ax
+    pub fn mul_vector(&self, x: &[f32]) -> Result<Vec<f32>, MatrixArithmeticError> {
+        if x.len() != self.width {
+            return Err(MatrixArithmeticError::DimensionMismatch);
+        }
+        Ok(self.matrix.iter().map(|row| row.iter().zip(x).map(|(a, b)| a * b).sum()).collect())
+    }
+}
+
+
+// ratio of the biggest to smallest diagonal value of R, errors if R has no usable pivots
+fn diagonal_condition(r: &MaxtrixNM, size: usize) -> Result<f32, MatrixArithmeticError> {
+    let diagonal: Vec<f32> = (0..size).map(|i| r.matrix[i][i].abs()).collect();
+    let max = diagonal.iter().fold(0.0_f32, |max, val| max.max(*val));
+    let min = diagonal.iter().fold(f32::MAX, |min, val| min.min(*val));
+    if max == 0.0 || min <= max * (r.width.max(r.height) as f32) * f32::EPSILON {
+        return Err(MatrixArithmeticError::Singular);
+    }
+    Ok(max / min)
+}
+
+/// finds the x that minimises the length of Ax - b
+///
+/// - Overdetermined systems (more rows than columns) give the best fit solution
+/// - Underdetermined systems (more columns than rows) give the solution with the smallest length
+///
+/// errors if the matrix doesn't have full rank, as there is then no single best solution
+pub fn least_squares(a: &MaxtrixNM, b: &[f32]) -> Result<LeastSquaresSolution, MatrixArithmeticError> {
+    if b.len() != a.height {
+        return Err(MatrixArithmeticError::DimensionMismatch);
+    }
+    let (m, n) = (a.height, a.width);
+
+    let (solution, condition_estimate) = if m >= n {
+        // A = QR so the best fit is Rx = Q^T b, only using the square top of R
+        let QRDecomposition {q, r} = a.qr();
+        let condition = diagonal_condition(&r, n)?;
+        let qtb: Vec<f32> = (0..n).map(|col| (0..m).map(|row| q.matrix[row][col] * b[row]).sum()).collect();
+
+        let mut x = vec![0.0; n];
+        for row in (0..n).rev() {
+            let sum: f32 = ((row + 1)..n).map(|col| r.matrix[row][col] * x[col]).sum();
+            x[row] = (qtb[row] - sum) / r.matrix[row][row];
+        }
+        (x, condition)
+    } else {
+        // A^T = QR so A = R^T Q^T, solve R^T y = b then the smallest x is Qy
+        let QRDecomposition {q, r} = a.transpose().qr();
+        let condition = diagonal_condition(&r, m)?;
+
+        let mut y = vec![0.0; m];
+        for row in 0..m {
+            let sum: f32 = (0..row).map(|col| r.matrix[col][row] * y[col]).sum();
+            y[row] = (b[row] - sum) / r.matrix[row][row];
+        }
+        let x = (0..n).map(|row| (0..m).map(|col| q.matrix[row][col] * y[col]).sum()).collect();
+        (x, condition)
+    };
+
+    let residual_norm = a.mul_vector(&solution)?
+        .iter()
+        .zip(b)
+        .map(|(ax, b)| (ax - b) * (ax - b))
+        .sum::<f32>()
+        .sqrt();
+
+    Ok(LeastSquaresSolution {
+        solution,
+        residual_norm,
+        condition_estimate,
+    })
+}
+
+/// fits a polynomial of the given degree to the points, the solution holds the coefficients from the constant term up
+pub fn polynomial_fit(xs: &[f32], ys: &[f32], degree: usize) -> Result<LeastSquaresSolution, MatrixArithmeticError> {
+    if xs.len() != ys.len() {
+        return Err(MatrixArithmeticError::DimensionMismatch);
+    }
+    let mut items = Vec::new();
+    for x in xs {
+        items.extend((0..=degree).map(|power| x.powi(power as i32)));
+    }
+    least_squares(&MaxtrixNM::new_from_items(degree + 1, xs.len(), items), ys)
+}
@@ -1,250 +1,502 @@
 #![allow(dead_code)]
-use super::super::vectors::Vector4;
+use super::super::vectors::{Vector3, Vector4, DVector3, DVector4};
 use super::*;
 use std::{ops::*, fmt::Display};
 
-#[derive(Default, Clone, Copy, Debug, PartialEq, PartialOrd)]
-pub struct Matrix4 {
-    pub x: Vector4, 
-    pub y: Vector4,
-    pub z: Vector4,
-    pub w: Vector4,
+
+/// which way the z axis points in view space
+/// - Right handed (opengl style) cameras look down -z
+/// - Left handed (directx style) cameras look down +z
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Handedness {
+    Right,
+    Left,
 }
 
-impl Matrix4 {
-    pub const IDENTITY: Matrix4 = Matrix4 {
-        x: Vector4::X,
-        y: Vector4::Y,
-        z: Vector4::Z,
-        w: Vector4::W
-    };
+/// the range that clip space depth is mapped into by projection matrices
+/// - NegativeOneToOne is the opengl convention
+/// - ZeroToOne is the vulkan, directx, metal and wgpu convention
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DepthRange {
+    NegativeOneToOne,
+    ZeroToOne,
+}
 
-    pub const ONE: Matrix4 = Matrix4 {
-        x: Vector4::ONE,
-        y: Vector4::ONE,
-        z: Vector4::ONE,
-        w: Vector4::ONE
-    };
 
-    pub const EPSILON: Matrix4 = Matrix4 {
-        x: Vector4::EPSILON,
-        y: Vector4::EPSILON,
-        z: Vector4::EPSILON,
-        w: Vector4::EPSILON
-    };
+macro_rules! matrix4 {
+    ($(#[$attr:meta])* $name:ident, $scalar:ident, $vector3:ident, $vector4:ident, $matrix3:ident, $decomposition:ident) => {
+        /// the translation, rotation and scale that make up an affine transform, as given by `Matrix4::decompose`
+        ///
+        /// if the transform contains a reflection it is put into the x scale, so `scale.x` is negative and `is_reflection` is true
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        pub struct $decomposition {
+            pub translation: $vector3,
+            pub rotation: $matrix3,
+            pub euler_angles: $vector3,
+            pub scale: $vector3,
+            pub is_reflection: bool,
+        }
 
-    pub const fn new(
-        r0c0: f32, r0c1: f32, r0c2: f32, r0c3: f32,
-        r1c0: f32, r1c1: f32, r1c2: f32, r1c3: f32,
-        r2c0: f32, r2c1: f32, r2c2: f32, r2c3: f32,
-        r3c0: f32, r3c1: f32, r3c2: f32, r3c3: f32,
-    ) -> Matrix4 {
-        Matrix4 {
-            x: Vector4::new(r0c0, r0c1, r0c2, r0c3),
-            y: Vector4::new(r1c0, r1c1, r1c2, r1c3),
-            z: Vector4::new(r2c0, r2c1, r2c2, r2c3),
-            w: Vector4::new(r3c0, r3c1, r3c2, r3c3)
+        $(#[$attr])*
+        #[derive(Default, Clone, Copy, Debug, PartialEq, PartialOrd)]
+        pub struct $name {
+            pub x: $vector4, 
+            pub y: $vector4,
+            pub z: $vector4,
+            pub w: $vector4,
         }
-    }
 
-    pub fn c0(&self) -> Vector4 {
-        [self.x.x, self.y.x, self.z.x, self.w.x].into()
-    }
+        impl $name {
+            pub const IDENTITY: $name = $name {
+                x: $vector4::X,
+                y: $vector4::Y,
+                z: $vector4::Z,
+                w: $vector4::W
+            };
 
-    pub fn c1(&self) -> Vector4 {
-        [self.x.y, self.y.y, self.z.y, self.w.y].into()
-    }
+            pub const ONE: $name = $name {
+                x: $vector4::ONE,
+                y: $vector4::ONE,
+                z: $vector4::ONE,
+                w: $vector4::ONE
+            };
 
-    pub fn c2(&self) -> Vector4 {
-        [self.x.z, self.y.z, self.z.z, self.w.z].into()
-    }
+            pub const EPSILON: $name = $name {
+                x: $vector4::EPSILON,
+                y: $vector4::EPSILON,
+                z: $vector4::EPSILON,
+                w: $vector4::EPSILON
+            };
 
-    pub fn c3(&self) -> Vector4 {
-        [self.x.w, self.y.w, self.z.w, self.w.w].into()
-    }
+            pub const fn new(
+                r0c0: $scalar, r0c1: $scalar, r0c2: $scalar, r0c3: $scalar,
+                r1c0: $scalar, r1c1: $scalar, r1c2: $scalar, r1c3: $scalar,
+                r2c0: $scalar, r2c1: $scalar, r2c2: $scalar, r2c3: $scalar,
+                r3c0: $scalar, r3c1: $scalar, r3c2: $scalar, r3c3: $scalar,
+            ) -> $name {
+                $name {
+                    x: $vector4::new(r0c0, r0c1, r0c2, r0c3),
+                    y: $vector4::new(r1c0, r1c1, r1c2, r1c3),
+                    z: $vector4::new(r2c0, r2c1, r2c2, r2c3),
+                    w: $vector4::new(r3c0, r3c1, r3c2, r3c3)
+                }
+            }
 
-    pub fn from_rows(x: impl Into<Vector4>, y: impl Into<Vector4>, z: impl Into<Vector4>, w: impl Into<Vector4>) -> Matrix4 {
-        Matrix4 {
-            x: x.into(),
-            y: y.into(),
-            z: z.into(),
-            w: w.into()
-        }
-    }
+            pub fn c0(&self) -> $vector4 {
+                [self.x.x, self.y.x, self.z.x, self.w.x].into()
+            }
 
-    /// creates a perspective matrix for the specified settings, based on the opengl implementation
-    pub fn persective_matrix(fovy: f32, aspect: f32, znear: f32, zfar: f32) -> Matrix4 {
-        let f = 1.0 / (fovy / 2.0).tan();
-        Matrix4::new(
-            f / aspect, 0.0, 0.0, 0.0,
-            0.0, f, 0.0, 0.0,
-            0.0, 0.0, (znear + zfar ) / (znear - zfar), -1.0,
-            0.0, 0.0, (2.0 * zfar * znear) / (znear - zfar), 0.0
-        )
-    }
+            pub fn c1(&self) -> $vector4 {
+                [self.x.y, self.y.y, self.z.y, self.w.y].into()
+            }
 
-    pub fn transpose(&mut self) {
-        *self = self.transposed();
-    }
+            pub fn c2(&self) -> $vector4 {
+                [self.x.z, self.y.z, self.z.z, self.w.z].into()
+            }
 
-    pub fn transposed(&self) -> Matrix4 {
-        Matrix4::new(
-            self.x.x, self.y.x, self.z.x, self.w.x,
-            self.x.y, self.y.y, self.z.y, self.w.y,
-            self.x.z, self.y.z, self.z.z, self.w.z,
-            self.x.w, self.y.w, self.z.w, self.w.w
-        )
-    }
+            pub fn c3(&self) -> $vector4 {
+                [self.x.w, self.y.w, self.z.w, self.w.w].into()
+            }
 
-    pub fn truncate(&self) -> Matrix3 {
-        Matrix3::new(
-            self.x.x, self.x.y, self.x.z,
-            self.y.x, self.y.y, self.y.z,
-            self.z.x, self.z.y, self.z.z
-        )
-    }
+            pub fn from_rows(x: impl Into<$vector4>, y: impl Into<$vector4>, z: impl Into<$vector4>, w: impl Into<$vector4>) -> $name {
+                $name {
+                    x: x.into(),
+                    y: y.into(),
+                    z: z.into(),
+                    w: w.into()
+                }
+            }
 
-    pub fn determinant(&self) -> f32 {
-        // self.x.x * (
-        //     self.y.y * (self.z.z * self.w.w - self.w.z * self.z.w)
-        //     - self.y.z * (self.z.y * self.w.w - self.w.y * self.z.w)
-        //     + self.y.w * (self.z.y * self.w.z - self.w.y * self.z.z)
-        // )
-        // - self.x.y * (
-        //     self.y.x * (self.z.z * self.w.w - self.w.z * self.z.w)
-        //     - self.y.z * (self.z.x * self.w.w - self.w.x * self.z.w)
-        //     + self.y.w * (self.z.x * self.w.z - self.w.z * self.z.z)
-        // )
-        // + self.x.z * (
-        //     self.y.z * (self.z.y * self.w.w - self.w.y * self.z.w)
-        //     - self.y.y * (self.z.x * self.w.w - self.w.z * self.z.w)
-        //     + self.y.w * (self.z.x * self.w.y - self.w.x * self.z.y)
-        // )
-        // - self.x.w * (
-        //     self.y.x * (self.z.y * self.w.z - self.w.y * self.z.z)
-        //     - self.y.y * (self.z.x * self.w.z - self.w.x * self.z.z)
-        //     + self.y.z * (self.z.x * self.w.y - self.w.x * self.z.y)
-        // )
-        self.x.x * Matrix3::from_rows(self.y.truncate_n(0), self.z.truncate_n(0), self.w.truncate_n(0)).determinant()
-        - self.x.y * Matrix3::from_rows(self.y.truncate_n(1), self.z.truncate_n(1), self.w.truncate_n(1)).determinant()
-        + self.x.z * Matrix3::from_rows(self.y.truncate_n(2), self.z.truncate_n(2), self.w.truncate_n(2)).determinant()
-        - self.x.w * Matrix3::from_rows(self.y.truncate_n(3), self.z.truncate_n(3), self.w.truncate_n(3)).determinant()
-    }
+            /// creates a perspective matrix for the specified settings, based on the opengl implementation
+            pub fn persective_matrix(fovy: $scalar, aspect: $scalar, znear: $scalar, zfar: $scalar) -> $name {
+                let f = 1.0 / (fovy / 2.0).tan();
+                $name::new(
+                    f / aspect, 0.0, 0.0, 0.0,
+                    0.0, f, 0.0, 0.0,
+                    0.0, 0.0, (znear + zfar ) / (znear - zfar), -1.0,
+                    0.0, 0.0, (2.0 * zfar * znear) / (znear - zfar), 0.0
+                )
+            }
 
-    // pain
-    pub fn inverted(&self) -> Matrix4 {
-        let det = self.determinant();
-        if det == 0.0 {
-            self.clone()
-        } else {
-            let inv_det = 1.0 / det;
-            let t = self.transposed();
-            let cf = |i, j| {
-                let mat = match i {
-                    0 => {
-                        Matrix3::from_columns(t.y.truncate_n(j), t.z.truncate_n(j), t.w.truncate_n(j))
-                    }
-                    1 => {
-                        Matrix3::from_columns(t.x.truncate_n(j), t.z.truncate_n(j), t.w.truncate_n(j))
-                    }
-                    2 => {
-                        Matrix3::from_columns(t.x.truncate_n(j), t.y.truncate_n(j), t.w.truncate_n(j))
-                    }
-                    3 => {
-                        Matrix3::from_columns(t.x.truncate_n(j), t.y.truncate_n(j), t.z.truncate_n(j))
-                    }
-                    _ => panic!("out of range"),
+            /// creates a perspective matrix with no far plane, depth approaches the far end of the depth range as distance goes to infinity
+            pub fn infinite_perspective(fovy: $scalar, aspect: $scalar, znear: $scalar, handedness: Handedness, depth_range: DepthRange) -> $name {
+                let f = 1.0 / (fovy / 2.0).tan();
+                let z_sign = match handedness {
+                    Handedness::Right => -1.0,
+                    Handedness::Left => 1.0,
                 };
-                let sign = if (i + j) & 1 == 1 {
-                    -1.0
-                } else {
-                    1.0
+                let z_offset = match depth_range {
+                    DepthRange::NegativeOneToOne => -2.0 * znear,
+                    DepthRange::ZeroToOne => -znear,
                 };
-                mat.determinant() * sign * inv_det
-            };
+                $name::new(
+                    f / aspect, 0.0, 0.0, 0.0,
+                    0.0, f, 0.0, 0.0,
+                    0.0, 0.0, z_sign, z_sign,
+                    0.0, 0.0, z_offset, 0.0
+                )
+            }
+
+            /// creates a perspective matrix that maps znear to a depth of 1 and zfar to a depth of 0,
+            /// this spreads float precision far more evenly over the view distance than the usual mapping
+            ///
+            /// reversed z only makes sense with a 0 to 1 depth range, so this should be used with a greater than depth test and a depth clear value of 0
+            pub fn reversed_z_perspective(fovy: $scalar, aspect: $scalar, znear: $scalar, zfar: $scalar, handedness: Handedness) -> $name {
+                let f = 1.0 / (fovy / 2.0).tan();
+                let z_sign = match handedness {
+                    Handedness::Right => -1.0,
+                    Handedness::Left => 1.0,
+                };
+                $name::new(
+                    f / aspect, 0.0, 0.0, 0.0,
+                    0.0, f, 0.0, 0.0,
+                    0.0, 0.0, -z_sign * znear / (zfar - znear), z_sign,
+                    0.0, 0.0, zfar * znear / (zfar - znear), 0.0
+                )
+            }
+
+            /// creates an orthographic projection matrix mapping the given box into clip space
+            #[allow(clippy::too_many_arguments)]
+            pub fn orthographic(left: $scalar, right: $scalar, bottom: $scalar, top: $scalar, znear: $scalar, zfar: $scalar, handedness: Handedness, depth_range: DepthRange) -> $name {
+                let z_sign = match handedness {
+                    Handedness::Right => -1.0,
+                    Handedness::Left => 1.0,
+                };
+                let (z_scale, z_offset) = match depth_range {
+                    DepthRange::NegativeOneToOne => (2.0 / (zfar - znear), -(zfar + znear) / (zfar - znear)),
+                    DepthRange::ZeroToOne => (1.0 / (zfar - znear), -znear / (zfar - znear)),
+                };
+                $name::new(
+                    2.0 / (right - left), 0.0, 0.0, 0.0,
+                    0.0, 2.0 / (top - bottom), 0.0, 0.0,
+                    0.0, 0.0, z_sign * z_scale, 0.0,
+                    -(right + left) / (right - left), -(top + bottom) / (top - bottom), z_offset, 1.0
+                )
+            }
+
+            /// creates a right handed view matrix for a camera at eye looking towards target, the camera looks down -z in view space
+            pub fn look_at_rh(eye: impl Into<$vector3>, target: impl Into<$vector3>, up: impl Into<$vector3>) -> $name {
+                let (eye, target, up): ($vector3, $vector3, $vector3) = (eye.into(), target.into(), up.into());
+                let f = (target - eye).normalised();
+                let s = f.cross(up).normalised();
+                let u = s.cross(f);
+
+                $name::new(
+                    s.x, u.x, -f.x, 0.0,
+                    s.y, u.y, -f.y, 0.0,
+                    s.z, u.z, -f.z, 0.0,
+                    -eye.dot(s), -eye.dot(u), eye.dot(f), 1.0
+                )
+            }
+
+            /// creates a left handed view matrix for a camera at eye looking towards target, the camera looks down +z in view space
+            pub fn look_at_lh(eye: impl Into<$vector3>, target: impl Into<$vector3>, up: impl Into<$vector3>) -> $name {
+                let (eye, target, up): ($vector3, $vector3, $vector3) = (eye.into(), target.into(), up.into());
+                let f = (target - eye).normalised();
+                let s = up.cross(f).normalised();
+                let u = f.cross(s);
+
+                $name::new(
+                    s.x, u.x, f.x, 0.0,
+                    s.y, u.y, f.y, 0.0,
+                    s.z, u.z, f.z, 0.0,
+                    -eye.dot(s), -eye.dot(u), -eye.dot(f), 1.0
+                )
+            }
+
+            //////////////////////////////////////////////////////////////////
+            ///////////////////////////////// affine transforms
+            //////////////////////////////////////////////////////////////////
+            // These use the same layout as persective_matrix so everything can be multiplied together and sent to the gpu as is:
+            // points are treated as row vectors on the left (p * M), so translations live in the w row
+            // and transforms compose left to right, e.g. scale * rotation * translation scales first and translates last
+
+            pub fn from_translation(translation: impl Into<$vector3>) -> $name {
+                let t: $vector3 = translation.into();
+                $name::new(
+                    1.0, 0.0, 0.0, 0.0,
+                    0.0, 1.0, 0.0, 0.0,
+                    0.0, 0.0, 1.0, 0.0,
+                    t.x, t.y, t.z, 1.0
+                )
+            }
+
+            pub fn from_scale(scale: $scalar) -> $name {
+                $name::from_nonuniform_scale([scale; 3])
+            }
+
+            pub fn from_nonuniform_scale(scale: impl Into<$vector3>) -> $name {
+                let s: $vector3 = scale.into();
+                $name::new(
+                    s.x, 0.0, 0.0, 0.0,
+                    0.0, s.y, 0.0, 0.0,
+                    0.0, 0.0, s.z, 0.0,
+                    0.0, 0.0, 0.0, 1.0
+                )
+            }
+
+            /// creates a transform from a rotation matrix such as `Matrix3::from_angle_and_axis`, so that
+            /// `Matrix4::from_rotation(rot).transform_vector(v) == rot * v`
+            pub fn from_rotation(rotation: $matrix3) -> $name {
+                rotation.transposed().extend()
+            }
+
+            /// transforms a point by this matrix, including translation and the divide by w for projections
+            pub fn transform_point(&self, point: impl Into<$vector3>) -> $vector3 {
+                let p: $vector3 = point.into();
+                let result = self.x * p.x + self.y * p.y + self.z * p.z + self.w;
+                if result.w == 0.0 || result.w == 1.0 {
+                    result.truncate()
+                } else {
+                    result.truncate() / result.w
+                }
+            }
+
+            /// transforms a direction by this matrix, ignoring translation
+            pub fn transform_vector(&self, vector: impl Into<$vector3>) -> $vector3 {
+                let v: $vector3 = vector.into();
+                self.x.truncate() * v.x + self.y.truncate() * v.y + self.z.truncate() * v.z
+            }
 
+            /// splits an affine transform back into the translation, rotation and scale it was built from,
+            /// the reverse of `Matrix4::recompose`
+            ///
+            /// returns None if the matrix is a projection or has a zero scale. Any shear is not removed so matrices with shear will give a rotation that isn't orthonormal
+            pub fn decompose(&self) -> Option<$decomposition> {
+                const TOLERANCE: $scalar = 1e-6;
+                if self.x.w.abs() > TOLERANCE || self.y.w.abs() > TOLERANCE || self.z.w.abs() > TOLERANCE || (self.w.w - 1.0).abs() > TOLERANCE {
+                    return None;
+                }
+
+                let (x_axis, y_axis, z_axis) = (self.x.truncate(), self.y.truncate(), self.z.truncate());
+                let mut scale = $vector3::new(x_axis.magnitude(), y_axis.magnitude(), z_axis.magnitude());
+                if scale.x <= $scalar::EPSILON || scale.y <= $scalar::EPSILON || scale.z <= $scalar::EPSILON {
+                    return None;
+                }
+
+                // a negative determinant means the axes are mirrored, put the flip into x
+                let is_reflection = self.truncate().determinant() < 0.0;
+                if is_reflection {
+                    scale.x = -scale.x;
+                }
+
+                let rotation = $matrix3::from_columns(x_axis / scale.x, y_axis / scale.y, z_axis / scale.z);
+
+                Some($decomposition {
+                    translation: self.w.truncate(),
+                    rotation,
+                    euler_angles: $matrix3::euler_angles_from(rotation),
+                    scale,
+                    is_reflection,
+                })
+            }
+
+            /// builds the transform that scales, then rotates, then translates by the given parts, the reverse of `Matrix4::decompose`
+            pub fn recompose(parts: &$decomposition) -> $name {
+                $name::from_nonuniform_scale(parts.scale) * $name::from_rotation(parts.rotation) * $name::from_translation(parts.translation)
+            }
+
+            pub fn transpose(&mut self) {
+                *self = self.transposed();
+            }
+
+            pub fn transposed(&self) -> $name {
+                $name::new(
+                    self.x.x, self.y.x, self.z.x, self.w.x,
+                    self.x.y, self.y.y, self.z.y, self.w.y,
+                    self.x.z, self.y.z, self.z.z, self.w.z,
+                    self.x.w, self.y.w, self.z.w, self.w.w
+                )
+            }
+
+            pub fn truncate(&self) -> $matrix3 {
+                $matrix3::new(
+                    self.x.x, self.x.y, self.x.z,
+                    self.y.x, self.y.y, self.y.z,
+                    self.z.x, self.z.y, self.z.z
+                )
+            }
+
+            pub fn determinant(&self) -> $scalar {
+                // self.x.x * (
+                //     self.y.y * (self.z.z * self.w.w - self.w.z * self.z.w)
+                //     - self.y.z * (self.z.y * self.w.w - self.w.y * self.z.w)
+                //     + self.y.w * (self.z.y * self.w.z - self.w.y * self.z.z)
+                // )
+                // - self.x.y * (
+                //     self.y.x * (self.z.z * self.w.w - self.w.z * self.z.w)
+                //     - self.y.z * (self.z.x * self.w.w - self.w.x * self.z.w)
+                //     + self.y.w * (self.z.x * self.w.z - self.w.z * self.z.z)
+                // )
+                // + self.x.z * (
+                //     self.y.z * (self.z.y * self.w.w - self.w.y * self.z.w)
+                //     - self.y.y * (self.z.x * self.w.w - self.w.z * self.z.w)
+                //     + self.y.w * (self.z.x * self.w.y - self.w.x * self.z.y)
+                // )
+                // - self.x.w * (
+                //     self.y.x * (self.z.y * self.w.z - self.w.y * self.z.z)
+                //     - self.y.y * (self.z.x * self.w.z - self.w.x * self.z.z)
+                //     + self.y.z * (self.z.x * self.w.y - self.w.x * self.z.y)
+                // )
+                self.x.x * $matrix3::from_rows(self.y.truncate_n(0), self.z.truncate_n(0), self.w.truncate_n(0)).determinant()
+                - self.x.y * $matrix3::from_rows(self.y.truncate_n(1), self.z.truncate_n(1), self.w.truncate_n(1)).determinant()
+                + self.x.z * $matrix3::from_rows(self.y.truncate_n(2), self.z.truncate_n(2), self.w.truncate_n(2)).determinant()
+                - self.x.w * $matrix3::from_rows(self.y.truncate_n(3), self.z.truncate_n(3), self.w.truncate_n(3)).determinant()
+            }
+
+            // pain
+            pub fn inverted(&self) -> $name {
+                let det = self.determinant();
+                if det == 0.0 {
+                    self.clone()
+                } else {
+                    let inv_det = 1.0 / det;
+                    let t = self.transposed();
+                    let cf = |i, j| {
+                        let mat = match i {
+                            0 => {
+                                $matrix3::from_columns(t.y.truncate_n(j), t.z.truncate_n(j), t.w.truncate_n(j))
+                            }
+                            1 => {
+                                $matrix3::from_columns(t.x.truncate_n(j), t.z.truncate_n(j), t.w.truncate_n(j))
+                            }
+                            2 => {
+                                $matrix3::from_columns(t.x.truncate_n(j), t.y.truncate_n(j), t.w.truncate_n(j))
+                            }
+                            3 => {
+                                $matrix3::from_columns(t.x.truncate_n(j), t.y.truncate_n(j), t.z.truncate_n(j))
+                            }
+                            _ => panic!("out of range"),
+                        };
+                        let sign = if (i + j) & 1 == 1 {
+                            -1.0
+                        } else {
+                            1.0
+                        };
+                        mat.determinant() * sign * inv_det
+                    };
+
+
+                    $name::new(
+                        cf(0, 0), cf(0, 1), cf(0, 2), cf(0, 3),
+                        cf(1, 0), cf(1, 1), cf(1, 2), cf(1, 3),
+                        cf(2, 0), cf(2, 1), cf(2, 2), cf(2, 3),
+                        cf(3, 0), cf(3, 1), cf(3, 2), cf(3, 3),
+                    )
+                }
+            }
+
+            /// returns the inverse if the absolute value of the determinant is above the tolerance, otherwise None
+            pub fn try_inverted(&self, tolerance: $scalar) -> Option<$name> {
+                if self.determinant().abs() <= tolerance {return None;}
+                Some(self.inverted())
+            }
 
-            Matrix4::new(
-                cf(0, 0), cf(0, 1), cf(0, 2), cf(0, 3),
-                cf(1, 0), cf(1, 1), cf(1, 2), cf(1, 3),
-                cf(2, 0), cf(2, 1), cf(2, 2), cf(2, 3),
-                cf(3, 0), cf(3, 1), cf(3, 2), cf(3, 3),
-            )
         }
-    }
 
-}
+        impl Into<[[$scalar; 4]; 4]> for $name {
+            fn into(self) -> [[$scalar; 4]; 4] {
+                [self.x.into(), self.y.into(), self.z.into(), self.w.into()]
+            }
+        }
 
-impl Into<[[f32; 4]; 4]> for Matrix4 {
-    fn into(self) -> [[f32; 4]; 4] {
-        [self.x.into(), self.y.into(), self.z.into(), self.w.into()]
-    }
-}
 
+        impl Mul for $name {
+            type Output = $name;
+            fn mul(self, rhs: Self) -> Self::Output {
+                $name::new(
+                    self.x.dot(rhs.c0()), self.x.dot(rhs.c1()), self.x.dot(rhs.c2()), self.x.dot(rhs.c3()),
+                    self.y.dot(rhs.c0()), self.y.dot(rhs.c1()), self.y.dot(rhs.c2()), self.y.dot(rhs.c3()),
+                    self.z.dot(rhs.c0()), self.z.dot(rhs.c1()), self.z.dot(rhs.c2()), self.z.dot(rhs.c3()),
+                    self.w.dot(rhs.c0()), self.w.dot(rhs.c1()), self.w.dot(rhs.c2()), self.w.dot(rhs.c3())
+                )
+            }
+        }
 
-impl Mul for Matrix4 {
-    type Output = Matrix4;
-    fn mul(self, rhs: Self) -> Self::Output {
-        Matrix4::new(
-            self.x.dot(rhs.c0()), self.x.dot(rhs.c1()), self.x.dot(rhs.c2()), self.x.dot(rhs.c3()),
-            self.y.dot(rhs.c0()), self.y.dot(rhs.c1()), self.y.dot(rhs.c2()), self.y.dot(rhs.c3()),
-            self.z.dot(rhs.c0()), self.z.dot(rhs.c1()), self.z.dot(rhs.c2()), self.z.dot(rhs.c3()),
-            self.w.dot(rhs.c0()), self.w.dot(rhs.c1()), self.w.dot(rhs.c2()), self.w.dot(rhs.c3())
-        )
-    }
-}
+        impl Mul<$scalar> for $name {
+            type Output = $name;
+            fn mul(self, rhs: $scalar) -> Self::Output {
+                $name::from_rows(
+                    self.x * rhs,
+                    self.y * rhs,
+                    self.z * rhs,
+                    self.w * rhs
+                )
+            }
+        }
 
-impl Mul<f32> for Matrix4 {
-    type Output = Matrix4;
-    fn mul(self, rhs: f32) -> Self::Output {
-        Matrix4::from_rows(
-            self.x * rhs,
-            self.y * rhs,
-            self.z * rhs,
-            self.w * rhs
-        )
-    }
-}
+        impl Mul<$vector4> for $name {
+            type Output = $vector4;
+            fn mul(self, rhs: $vector4) -> Self::Output {
+                $vector4::new(
+                    self.x.dot(rhs),
+                    self.y.dot(rhs),
+                    self.z.dot(rhs),
+                    self.w.dot(rhs)
+                )
+            }
+        }
 
-impl Mul<Vector4> for Matrix4 {
-    type Output = Vector4;
-    fn mul(self, rhs: Vector4) -> Self::Output {
-        Vector4::new(
-            self.x.dot(rhs),
-            self.y.dot(rhs),
-            self.z.dot(rhs),
-            self.w.dot(rhs)
-        )
-    }
-}
+        impl Add for $name {
+            type Output = $name;
+            fn add(self, rhs: Self) -> Self::Output {
+                $name::from_rows(
+                    self.x + rhs.x,
+                    self.y + rhs.y,
+                    self.z + rhs.z,
+                    self.w + rhs.w
+                )
+            }
+        }
 
-impl Add for Matrix4 {
-    type Output = Matrix4;
-    fn add(self, rhs: Self) -> Self::Output {
-        Matrix4::from_rows(
-            self.x + rhs.x,
-            self.y + rhs.y,
-            self.z + rhs.z,
-            self.w + rhs.w
-        )
-    }
+        impl Sub for $name {
+            type Output = $name;
+            fn sub(self, rhs: Self) -> Self::Output {
+                $name::from_rows(
+                    self.x - rhs.x,
+                    self.y - rhs.y,
+                    self.z - rhs.z,
+                    self.w - rhs.w
+                )
+            }
+        }
+
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "[\n   {},\n   {},\n   {},\n   {}\n]", self.x, self.y, self.z, self.w)
+            }
+        }
+    };
 }
 
-impl Sub for Matrix4 {
-    type Output = Matrix4;
-    fn sub(self, rhs: Self) -> Self::Output {
-        Matrix4::from_rows(
-            self.x - rhs.x,
-            self.y - rhs.y,
-            self.z - rhs.z,
-            self.w - rhs.w
-        )
+matrix4!(Matrix4, f32, Vector3, Vector4, Matrix3, AffineDecomposition);
+matrix4!(
+    /// a 4x4 matrix of f64s with all the same methods as `Matrix4`, for transforms that place things far from the origin
+    DMatrix4, f64, DVector3, DVector4, DMatrix3, DAffineDecomposition
+);
+
+
+// precision conversions, only widening is done implicitly
+impl From<Matrix4> for DMatrix4 {
+    fn from(value: Matrix4) -> Self {
+        DMatrix4 {
+            x: value.x.into(),
+            y: value.y.into(),
+            z: value.z.into(),
+            w: value.w.into()
+        }
     }
 }
 
-
-impl Display for Matrix4 {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "[\n   {},\n   {},\n   {},\n   {}\n]", self.x, self.y, self.z, self.w)
+impl DMatrix4 {
+    /// rounds each element to the nearest f32, so large translations lose precision
+    pub fn to_single(&self) -> Matrix4 {
+        Matrix4 {
+            x: self.x.to_single(),
+            y: self.y.to_single(),
+            z: self.z.to_single(),
+            w: self.w.to_single()
+        }
     }
-}
\ No newline at end of file
+}
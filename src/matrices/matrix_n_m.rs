@@ -1,4 +1,5 @@
-use std::ops::{Add, Deref, Mul, Sub};
+use std::ops::{Add, Mul, Sub};
+use super::{Matrix3, Matrix4};
 
 
 /// I had to do this for my computational maths class
@@ -58,26 +59,255 @@ impl MaxtrixNM {
         Ok(self.matrix[y][x])
     }
 
+    pub fn new_identity(size: usize) -> MaxtrixNM {
+        let mut new = Self::new_empty(size, size);
+        for i in 0..size {
+            new.matrix[i][i] = 1.0;
+        }
+        new
+    }
+
+    /// creates a single column matrix from the given values
+    pub fn new_column(items: Vec<f32>) -> MaxtrixNM {
+        MaxtrixNM::new_from_items(1, items.len(), items)
+    }
+
+    pub fn is_square(&self) -> bool {
+        self.width == self.height
+    }
+
+    pub fn transpose(&self) -> MaxtrixNM {
+        let mut result = MaxtrixNM::new_empty(self.height, self.width);
+        for y in 0..self.height {
+            for x in 0..self.width {
+                result.matrix[x][y] = self.matrix[y][x];
+            }
+        }
+        result
+    }
+
+    pub fn trace(&self) -> Result<f32, MatrixArithmeticError> {
+        if !self.is_square() {
+            return Err(MatrixArithmeticError::NotSquare);
+        }
+        Ok((0..self.height).map(|i| self.matrix[i][i]).sum())
+    }
+
+    // values smaller than this are treated as zero when pivoting, scaled by the size of the matrix's values
+    fn pivot_tolerance(&self) -> f32 {
+        let max = self.matrix.iter().flatten().fold(0.0_f32, |max, val| max.max(val.abs()));
+        max * (self.width.max(self.height) as f32) * f32::EPSILON
+    }
+
+    /// splits the matrix into PA = LU using gaussian elimination with partial pivoting
+    ///
+    /// singular matrices still decompose, they just have a zero on the diagonal of U
+    pub fn lu(&self) -> Result<LUDecomposition, MatrixArithmeticError> {
+        if !self.is_square() {
+            return Err(MatrixArithmeticError::NotSquare);
+        }
+        let n = self.height;
+        let tolerance = self.pivot_tolerance();
+        let mut u = self.matrix.clone();
+        let mut l = MaxtrixNM::new_identity(n).matrix;
+        let mut permutation: Vec<usize> = (0..n).collect();
+        let mut swaps = 0;
+
+        for k in 0..n {
+            // swap the biggest remaining value in the column up to the pivot
+            let pivot_row = (k..n).fold(k, |best, row| if u[row][k].abs() > u[best][k].abs() {row} else {best});
+            if pivot_row != k {
+                u.swap(k, pivot_row);
+                permutation.swap(k, pivot_row);
+                let (upper, lower) = l.split_at_mut(pivot_row);
+                upper[k][..k].swap_with_slice(&mut lower[0][..k]);
+                swaps += 1;
+            }
+
+            if u[k][k].abs() <= tolerance {continue;}
+
+            for row in (k + 1)..n {
+                let factor = u[row][k] / u[k][k];
+                l[row][k] = factor;
+                let (upper, lower) = u.split_at_mut(row);
+                for (val, pivot_val) in lower[0][k..].iter_mut().zip(&upper[k][k..]) {
+                    *val -= factor * pivot_val;
+                }
+            }
+        }
+
+        Ok(LUDecomposition {
+            l: MaxtrixNM {matrix: l, width: n, height: n},
+            u: MaxtrixNM {matrix: u, width: n, height: n},
+            permutation,
+            swaps,
+            tolerance,
+        })
+    }
+
+    pub fn determinant(&self) -> Result<f32, MatrixArithmeticError> {
+        Ok(self.lu()?.determinant())
+    }
+
+    pub fn inverse(&self) -> Result<MaxtrixNM, MatrixArithmeticError> {
+        let lu = self.lu()?;
+        let n = self.height;
+        let mut result = MaxtrixNM::new_empty(n, n);
+        for col in 0..n {
+            let mut unit = vec![0.0; n];
+            unit[col] = 1.0;
+            let solved = lu.solve(&unit)?;
+            for (row, val) in solved.into_iter().enumerate() {
+                result.matrix[row][col] = val;
+            }
+        }
+        Ok(result)
+    }
+
+    /// the number of linearly independent rows (or columns) in the matrix
+    pub fn rank(&self) -> usize {
+        let tolerance = self.pivot_tolerance();
+        let mut rows = self.matrix.clone();
+        let mut rank = 0;
 
-    // pub fn determinant(&self) -> f32 {
+        for col in 0..self.width {
+            if rank == self.height {break;}
+            let pivot_row = (rank..self.height).fold(rank, |best, row| if rows[row][col].abs() > rows[best][col].abs() {row} else {best});
+            if rows[pivot_row][col].abs() <= tolerance {continue;}
+            rows.swap(rank, pivot_row);
 
-    // }
+            for row in (rank + 1)..self.height {
+                let factor = rows[row][col] / rows[rank][col];
+                let (upper, lower) = rows.split_at_mut(row);
+                for (val, pivot_val) in lower[0][col..].iter_mut().zip(&upper[rank][col..]) {
+                    *val -= factor * pivot_val;
+                }
+            }
+            rank += 1;
+        }
+        rank
+    }
 
-    // pub fn transpose(&self) -> MaxtrixNM {
+    /// solves Ax = b for x, where A is this matrix
+    pub fn solve(&self, b: &[f32]) -> Result<Vec<f32>, MatrixArithmeticError> {
+        if b.len() != self.height {
+            return Err(MatrixArithmeticError::DimensionMismatch);
+        }
+        self.lu()?.solve(b)
+    }
+}
 
-    // }
+impl From<Matrix3> for MaxtrixNM {
+    fn from(value: Matrix3) -> Self {
+        let rows: [[f32; 3]; 3] = [value.x.into(), value.y.into(), value.z.into()];
+        MaxtrixNM::new_from_items(3, 3, rows.concat())
+    }
+}
 
+impl From<Matrix4> for MaxtrixNM {
+    fn from(value: Matrix4) -> Self {
+        let rows: [[f32; 4]; 4] = value.into();
+        MaxtrixNM::new_from_items(4, 4, rows.concat())
+    }
 }
 
-#[derive(Debug)]
-pub struct MatrixArithmeticError;
+impl TryFrom<MaxtrixNM> for Matrix3 {
+    type Error = MatrixArithmeticError;
+    fn try_from(value: MaxtrixNM) -> Result<Self, Self::Error> {
+        if value.width != 3 || value.height != 3 {
+            return Err(MatrixArithmeticError::DimensionMismatch);
+        }
+        let m = value.matrix;
+        Ok(Matrix3::new(
+            m[0][0], m[0][1], m[0][2],
+            m[1][0], m[1][1], m[1][2],
+            m[2][0], m[2][1], m[2][2],
+        ))
+    }
+}
+
+impl TryFrom<MaxtrixNM> for Matrix4 {
+    type Error = MatrixArithmeticError;
+    fn try_from(value: MaxtrixNM) -> Result<Self, Self::Error> {
+        if value.width != 4 || value.height != 4 {
+            return Err(MatrixArithmeticError::DimensionMismatch);
+        }
+        let m = value.matrix;
+        Ok(Matrix4::new(
+            m[0][0], m[0][1], m[0][2], m[0][3],
+            m[1][0], m[1][1], m[1][2], m[1][3],
+            m[2][0], m[2][1], m[2][2], m[2][3],
+            m[3][0], m[3][1], m[3][2], m[3][3],
+        ))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatrixArithmeticError {
+    /// the matrices or vectors don't have compatible sizes for the operation
+    DimensionMismatch,
+    /// the operation only works on square matrices
+    NotSquare,
+    /// the matrix is singular, or too close to it for the result to mean anything
+    Singular,
+}
+
+
+/// the result of `MaxtrixNM::lu`, where PA = LU
+///
+/// row i of PA is row `permutation[i]` of A
+#[derive(Debug, Clone)]
+pub struct LUDecomposition {
+    pub l: MaxtrixNM,
+    pub u: MaxtrixNM,
+    pub permutation: Vec<usize>,
+    pub swaps: usize,
+    tolerance: f32,
+}
+
+impl LUDecomposition {
+    pub fn determinant(&self) -> f32 {
+        let sign = if self.swaps & 1 == 1 {-1.0} else {1.0};
+        (0..self.u.height).fold(sign, |det, i| det * self.u.matrix[i][i])
+    }
+
+    pub fn is_singular(&self) -> bool {
+        (0..self.u.height).any(|i| self.u.matrix[i][i].abs() <= self.tolerance)
+    }
+
+    /// solves Ax = b using the decomposition, so many right hand sides can be solved for the cost of one elimination
+    pub fn solve(&self, b: &[f32]) -> Result<Vec<f32>, MatrixArithmeticError> {
+        let n = self.u.height;
+        if b.len() != n {
+            return Err(MatrixArithmeticError::DimensionMismatch);
+        }
+        if self.is_singular() {
+            return Err(MatrixArithmeticError::Singular);
+        }
+
+        // forward substitution for Ly = Pb
+        let mut y = vec![0.0; n];
+        for row in 0..n {
+            let sum: f32 = (0..row).map(|col| self.l.matrix[row][col] * y[col]).sum();
+            y[row] = b[self.permutation[row]] - sum;
+        }
+
+        // back substitution for Ux = y
+        let mut x = vec![0.0; n];
+        for row in (0..n).rev() {
+            let sum: f32 = ((row + 1)..n).map(|col| self.u.matrix[row][col] * x[col]).sum();
+            x[row] = (y[row] - sum) / self.u.matrix[row][row];
+        }
+        Ok(x)
+    }
+}
 
 
 impl Sub for MaxtrixNM {
     type Output = Result<MaxtrixNM, MatrixArithmeticError>;
     fn sub(self, rhs: Self) -> Self::Output {
         if self.width != rhs.width || self.height != rhs.height {
-            return Err(MatrixArithmeticError);
+            return Err(MatrixArithmeticError::DimensionMismatch);
         }
         let mut result = MaxtrixNM::new_empty(self.width, self.height);
         for y in 0..self.height {
@@ -93,7 +323,7 @@ impl Add for MaxtrixNM {
     type Output = Result<MaxtrixNM, MatrixArithmeticError>;
     fn add(self, rhs: Self) -> Self::Output {
         if self.width != rhs.width || self.height != rhs.height {
-            return Err(MatrixArithmeticError);
+            return Err(MatrixArithmeticError::DimensionMismatch);
         }
         let mut result = MaxtrixNM::new_empty(self.width, self.height);
         for y in 0..self.height {
@@ -109,7 +339,7 @@ impl Mul for MaxtrixNM {
     type Output = Result<MaxtrixNM, MatrixArithmeticError>;
     fn mul(self, rhs: Self) -> Self::Output {
         if self.width != rhs.height {
-            return Err(MatrixArithmeticError{});
+            return Err(MatrixArithmeticError::DimensionMismatch);
         }
         let mut items = Vec::new();
         for y in 0..self.height {
@@ -0,0 +1,301 @@
+use std::fmt::Display;
+use std::ops::*;
+
+use super::{Matrix2, Matrix3, Matrix4, MaxtrixNM, MatrixArithmeticError};
+use super::super::vectors::{Vector2, Vector3, Vector4, VectorN};
+
+
+/// a matrix with R rows and C columns, stored on the stack
+///
+/// like `MaxtrixNM` but the size is part of the type, so multiplying matrices that don't fit together won't compile
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Matrix<const R: usize, const C: usize> {
+    pub rows: [[f32; C]; R],
+}
+
+impl<const R: usize, const C: usize> Default for Matrix<R, C> {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+
+impl<const R: usize, const C: usize> Matrix<R, C> {
+    pub const ZERO: Matrix<R, C> = Matrix{rows: [[0.0; C]; R]};
+
+    pub const fn new(rows: [[f32; C]; R]) -> Self {
+        Matrix {
+            rows
+        }
+    }
+
+    pub fn from_rows(rows: [VectorN<C>; R]) -> Self {
+        Matrix {
+            rows: rows.map(|row| row.components)
+        }
+    }
+
+    pub fn from_columns(columns: [VectorN<R>; C]) -> Self {
+        Matrix::<C, R>::from_rows(columns).transposed()
+    }
+
+    pub const fn width(&self) -> usize {
+        C
+    }
+
+    pub const fn height(&self) -> usize {
+        R
+    }
+
+    pub fn row(&self, index: usize) -> VectorN<C> {
+        VectorN::new(self.rows[index])
+    }
+
+    pub fn column(&self, index: usize) -> VectorN<R> {
+        VectorN::new(self.rows.map(|row| row[index]))
+    }
+
+    pub fn transposed(&self) -> Matrix<C, R> {
+        let mut result = Matrix::<C, R>::ZERO;
+        for (y, row) in self.rows.iter().enumerate() {
+            for (x, val) in row.iter().enumerate() {
+                result.rows[x][y] = *val;
+            }
+        }
+        result
+    }
+
+    pub fn map(&self, f: impl Fn(f32) -> f32) -> Self {
+        Matrix {
+            rows: self.rows.map(|row| row.map(&f))
+        }
+    }
+}
+
+impl<const N: usize> Matrix<N, N> {
+    pub fn identity() -> Self {
+        let mut result = Self::ZERO;
+        for (i, row) in result.rows.iter_mut().enumerate() {
+            row[i] = 1.0;
+        }
+        result
+    }
+
+    pub fn from_diagonal(diagonal: impl Into<VectorN<N>>) -> Self {
+        let diagonal = diagonal.into();
+        let mut result = Self::ZERO;
+        for (i, row) in result.rows.iter_mut().enumerate() {
+            row[i] = diagonal[i];
+        }
+        result
+    }
+
+    pub fn diagonal(&self) -> VectorN<N> {
+        let mut result = VectorN::ZERO;
+        for (i, row) in self.rows.iter().enumerate() {
+            result[i] = row[i];
+        }
+        result
+    }
+
+    pub fn trace(&self) -> f32 {
+        self.diagonal().sum()
+    }
+
+    /// goes through `MaxtrixNM` so allocates
+    pub fn determinant(&self) -> f32 {
+        // the lu decomposition only fails for non square matrices
+        MaxtrixNM::from(*self).determinant().unwrap_or(0.0)
+    }
+
+    /// goes through `MaxtrixNM` so allocates, errors if the matrix is singular
+    pub fn inverse(&self) -> Result<Self, MatrixArithmeticError> {
+        Self::try_from(MaxtrixNM::from(*self).inverse()?)
+    }
+}
+
+
+impl<const R: usize, const C: usize> From<[[f32; C]; R]> for Matrix<R, C> {
+    fn from(value: [[f32; C]; R]) -> Self {
+        Matrix::new(value)
+    }
+}
+
+impl<const R: usize, const C: usize> From<Matrix<R, C>> for [[f32; C]; R] {
+    fn from(value: Matrix<R, C>) -> Self {
+        value.rows
+    }
+}
+
+impl From<Matrix2> for Matrix<2, 2> {
+    fn from(value: Matrix2) -> Self {
+        Matrix::new([
+            [value.x.x, value.x.y],
+            [value.y.x, value.y.y],
+        ])
+    }
+}
+
+impl From<Matrix<2, 2>> for Matrix2 {
+    fn from(value: Matrix<2, 2>) -> Self {
+        let [x, y] = value.rows;
+        Matrix2 {
+            x: Vector2::new(x[0], x[1]),
+            y: Vector2::new(y[0], y[1]),
+        }
+    }
+}
+
+impl From<Matrix3> for Matrix<3, 3> {
+    fn from(value: Matrix3) -> Self {
+        Matrix::new([value.x.into(), value.y.into(), value.z.into()])
+    }
+}
+
+impl From<Matrix<3, 3>> for Matrix3 {
+    fn from(value: Matrix<3, 3>) -> Self {
+        let [x, y, z] = value.rows.map(Vector3::from);
+        Matrix3 {
+            x,
+            y,
+            z,
+        }
+    }
+}
+
+impl From<Matrix4> for Matrix<4, 4> {
+    fn from(value: Matrix4) -> Self {
+        Matrix::new(value.into())
+    }
+}
+
+impl From<Matrix<4, 4>> for Matrix4 {
+    fn from(value: Matrix<4, 4>) -> Self {
+        let [x, y, z, w] = value.rows.map(Vector4::from);
+        Matrix4 {
+            x,
+            y,
+            z,
+            w,
+        }
+    }
+}
+
+impl<const R: usize, const C: usize> From<Matrix<R, C>> for MaxtrixNM {
+    fn from(value: Matrix<R, C>) -> Self {
+        MaxtrixNM::new_from_items(C, R, value.rows.concat())
+    }
+}
+
+impl<const R: usize, const C: usize> TryFrom<MaxtrixNM> for Matrix<R, C> {
+    type Error = MatrixArithmeticError;
+    fn try_from(value: MaxtrixNM) -> Result<Self, Self::Error> {
+        if value.width != C || value.height != R {
+            return Err(MatrixArithmeticError::DimensionMismatch);
+        }
+        let mut result = Self::ZERO;
+        for (row, values) in result.rows.iter_mut().zip(value.matrix.iter()) {
+            row.copy_from_slice(values);
+        }
+        Ok(result)
+    }
+}
+
+
+impl<const R: usize, const C: usize> Index<(usize, usize)> for Matrix<R, C> {
+    type Output = f32;
+    /// indexed by (row, column)
+    fn index(&self, index: (usize, usize)) -> &Self::Output {
+        &self.rows[index.0][index.1]
+    }
+}
+
+impl<const R: usize, const C: usize> IndexMut<(usize, usize)> for Matrix<R, C> {
+    fn index_mut(&mut self, index: (usize, usize)) -> &mut Self::Output {
+        &mut self.rows[index.0][index.1]
+    }
+}
+
+impl<const R: usize, const C: usize, const K: usize> Mul<Matrix<C, K>> for Matrix<R, C> {
+    type Output = Matrix<R, K>;
+    fn mul(self, rhs: Matrix<C, K>) -> Self::Output {
+        let mut result = Matrix::<R, K>::ZERO;
+        for (out_row, row) in result.rows.iter_mut().zip(self.rows.iter()) {
+            for (k, val) in out_row.iter_mut().enumerate() {
+                *val = row.iter().zip(rhs.rows.iter()).map(|(a, rhs_row)| a * rhs_row[k]).sum();
+            }
+        }
+        result
+    }
+}
+
+impl<const R: usize, const C: usize> Mul<VectorN<C>> for Matrix<R, C> {
+    type Output = VectorN<R>;
+    fn mul(self, rhs: VectorN<C>) -> Self::Output {
+        VectorN::new(self.rows.map(|row| rhs.dot(row)))
+    }
+}
+
+impl<const R: usize, const C: usize> Mul<f32> for Matrix<R, C> {
+    type Output = Self;
+    fn mul(self, rhs: f32) -> Self::Output {
+        self.map(|val| val * rhs)
+    }
+}
+
+impl<const R: usize, const C: usize> Div<f32> for Matrix<R, C> {
+    type Output = Self;
+    fn div(self, rhs: f32) -> Self::Output {
+        self.map(|val| val / rhs)
+    }
+}
+
+impl<const R: usize, const C: usize> Add for Matrix<R, C> {
+    type Output = Self;
+    fn add(mut self, rhs: Self) -> Self::Output {
+        self += rhs;
+        self
+    }
+}
+
+impl<const R: usize, const C: usize> AddAssign for Matrix<R, C> {
+    fn add_assign(&mut self, rhs: Self) {
+        for (row, rhs_row) in self.rows.iter_mut().zip(rhs.rows.iter()) {
+            row.iter_mut().zip(rhs_row.iter()).for_each(|(a, b)| *a += b);
+        }
+    }
+}
+
+impl<const R: usize, const C: usize> Sub for Matrix<R, C> {
+    type Output = Self;
+    fn sub(mut self, rhs: Self) -> Self::Output {
+        self -= rhs;
+        self
+    }
+}
+
+impl<const R: usize, const C: usize> SubAssign for Matrix<R, C> {
+    fn sub_assign(&mut self, rhs: Self) {
+        for (row, rhs_row) in self.rows.iter_mut().zip(rhs.rows.iter()) {
+            row.iter_mut().zip(rhs_row.iter()).for_each(|(a, b)| *a -= b);
+        }
+    }
+}
+
+impl<const R: usize, const C: usize> Neg for Matrix<R, C> {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        self.map(|val| -val)
+    }
+}
+
+impl<const R: usize, const C: usize> Display for Matrix<R, C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[")?;
+        for (i, row) in self.rows.iter().enumerate() {
+            let separator = if i + 1 < R {","} else {""};
+            write!(f, "\n   {}{}", VectorN::new(*row), separator)?;
+        }
+        write!(f, "\n]")
+    }
+}
@@ -1,300 +1,347 @@
 #![allow(dead_code)]
-use super::super::vectors::Vector3;
+use super::super::vectors::{Vector3, DVector3};
 use super::*;
-use std::{ops::*, f32::consts::PI, fmt::Display};
+use std::{ops::*, fmt::Display};
 
-#[derive(Default, Clone, Copy, Debug, PartialEq, PartialOrd)]
-pub struct Matrix3 {
-    pub x: Vector3, 
-    pub y: Vector3,
-    pub z: Vector3,
-}
-
-impl Matrix3 {
-    pub const IDENTITY: Matrix3 = Matrix3 {
-        x: Vector3::X,
-        y: Vector3::Y,
-        z: Vector3::Z
-    };
-
-    pub const ONE: Matrix3 = Matrix3 {
-        x: Vector3::ONE,
-        y: Vector3::ONE,
-        z: Vector3::ONE
-    };
 
-    pub const EPSILON: Matrix3 = Matrix3 {
-        x: Vector3::EPSILON,
-        y: Vector3::EPSILON,
-        z: Vector3::EPSILON,
-    };
-
-    pub const fn new(
-        r0c0: f32, r0c1: f32 , r0c2: f32,
-        r1c0: f32, r1c1: f32 , r1c2: f32,
-        r2c0: f32, r2c1: f32 , r2c2: f32,
-    ) -> Self {
-        Matrix3 {
-            x: Vector3::new(r0c0, r0c1, r0c2),
-            y: Vector3::new(r1c0, r1c1, r1c2),
-            z: Vector3::new(r2c0, r2c1, r2c2),
+macro_rules! matrix3 {
+    ($(#[$attr:meta])* $name:ident, $scalar:ident, $vector3:ident, $matrix2:ident, $matrix4:ident) => {
+        $(#[$attr])*
+        #[derive(Default, Clone, Copy, Debug, PartialEq, PartialOrd)]
+        pub struct $name {
+            pub x: $vector3, 
+            pub y: $vector3,
+            pub z: $vector3,
         }
-    }
 
-    pub fn from_rows(
-        r0: impl Into<Vector3>,
-        r1: impl Into<Vector3>,
-        r2: impl Into<Vector3>,
-    ) -> Self {
-        Matrix3 {
-            x: r0.into(),
-            y: r1.into(),
-            z: r2.into()
+        impl $name {
+            pub const IDENTITY: $name = $name {
+                x: $vector3::X,
+                y: $vector3::Y,
+                z: $vector3::Z
+            };
+
+            pub const ONE: $name = $name {
+                x: $vector3::ONE,
+                y: $vector3::ONE,
+                z: $vector3::ONE
+            };
+
+            pub const EPSILON: $name = $name {
+                x: $vector3::EPSILON,
+                y: $vector3::EPSILON,
+                z: $vector3::EPSILON,
+            };
+
+            pub const fn new(
+                r0c0: $scalar, r0c1: $scalar , r0c2: $scalar,
+                r1c0: $scalar, r1c1: $scalar , r1c2: $scalar,
+                r2c0: $scalar, r2c1: $scalar , r2c2: $scalar,
+            ) -> Self {
+                $name {
+                    x: $vector3::new(r0c0, r0c1, r0c2),
+                    y: $vector3::new(r1c0, r1c1, r1c2),
+                    z: $vector3::new(r2c0, r2c1, r2c2),
+                }
+            }
+
+            pub fn from_rows(
+                r0: impl Into<$vector3>,
+                r1: impl Into<$vector3>,
+                r2: impl Into<$vector3>,
+            ) -> Self {
+                $name {
+                    x: r0.into(),
+                    y: r1.into(),
+                    z: r2.into()
+                }
+            }
+
+            pub fn from_columns(
+                c0: impl Into<$vector3>,
+                c1: impl Into<$vector3>,
+                c2: impl Into<$vector3>,
+            ) -> Self {
+                let mat = $name::from_rows(c0, c1, c2);
+                mat.transposed()
+            }
+
+            pub fn c0(&self) -> $vector3 {
+                $vector3::new(self.x.x, self.y.x, self.z.x)
+            }
+
+            pub fn c1(&self) -> $vector3 {
+                $vector3::new(self.x.y, self.y.y, self.z.y)
+            }
+
+            pub fn c2(&self) -> $vector3 {
+                $vector3::new(self.x.z, self.y.z, self.z.z)
+            }
+
+
+            /// creates a rotation maxtrix for anticlockwise angle around y axis
+            pub fn from_angle_y(angle: $scalar) -> Self{
+                $name::new(
+                    angle.cos(), 0.0, angle.sin(),
+                    0.0, 1.0, 0.0,
+                    -angle.sin(), 0.0, angle.cos()
+                )
+            }
+
+            /// creates a rotation maxtrix for anticlockwise angle around x axis
+            pub fn from_angle_x(angle: $scalar) -> Self {
+                $name::new(
+                    1.0, 0.0, 0.0,
+                    0.0, angle.cos(), -angle.sin(),
+                    0.0, angle.sin(), angle.cos()
+                )
+            }
+
+            /// creates a rotation maxtrix for anticlockwise angle around z axis
+            pub fn from_angle_z(angle: $scalar) -> Self {
+                $name::new(
+                    angle.cos(), -angle.sin(), 0.0,
+                    angle.sin(), angle.cos(), 0.0,
+                    0.0, 0.0, 1.0
+                )
+            }
+
+            /// creates a rotation matrix for anticlockwise rotation of angle around the specified axis
+            pub fn from_angle_and_axis(angle: $scalar, axis: impl Into<$vector3>) -> Self {
+                let mut axis: $vector3 = axis.into();
+                axis.normalise();
+                if angle == 0.0 {return $name::IDENTITY;}
+                $name::new(
+                    angle.cos() + axis.x.powi(2) * (1.0 - angle.cos()),
+                    axis.x * axis.y * (1.0 - angle.cos()) - axis.z * angle.sin(),
+                    axis.x * axis.z * (1.0 - angle.cos()) + axis.y * angle.sin(),
+
+                    axis.y * axis.x * (1.0 - angle.cos()) + axis.z * angle.sin(),
+                    angle.cos() + axis.y.powi(2) * (1.0 - angle.cos()),
+                    axis.y * axis.z * (1.0 - angle.cos()) - axis.x * angle.sin(),
+
+                    axis.z * axis.x * (1.0 - angle.cos()) - axis.y * angle.sin(),
+                    axis.z * axis.y * (1.0 - angle.cos()) + axis.x * angle.sin(),
+                    angle.cos() + axis.z.powi(2) * (1.0 - angle.cos())
+                )
+            }
+
+            pub fn from_euler_angles(angles: impl Into<$vector3>) -> $name {
+                let angles = angles.into();
+                let x = angles.x;
+                let y = angles.y;
+                let z = angles.z;
+                $name::new(
+                    y.cos() * z.cos(),
+                    x.sin() * y.sin() * z.cos() - x.cos() * z.sin(),
+                    x.cos() * y.sin() * z.cos() + x.sin() * z.sin(),
+
+                    y.cos() * z.sin(),
+                    x.sin() * y.sin() * z.sin() + x.cos() * z.cos(),
+                    x.cos() * y.sin() * z.sin() - x.sin() * z.cos(),
+
+                    -(y.sin()),
+                    x.sin() * y.cos(),
+                    x.cos() * y.cos()
+                )
+            }
+
+            // calculates the euler angles required to create a specific matrix
+            pub fn euler_angles_from(rot: $name) -> $vector3 {
+                let mut angles = $vector3::ZERO;
+
+                // special cases
+                if rot.z.x == 1.0{
+                    angles.y = -std::$scalar::consts::PI / 2.0;
+                    angles.x = -(rot.x.y).atan2(-rot.x.z);
+                    return angles;
+                }
+                if rot.z.x == -1.0 {
+                    angles.y = std::$scalar::consts::PI / 2.0;
+                    angles.x = rot.x.y.atan2(rot.x.z);
+                    return angles;
+                }
+
+                // get y angle
+                angles.y = -rot.z.x.asin();
+
+                // get x angle
+                angles.x = (rot.z.y / angles.y.cos()).atan2(rot.z.z / angles.y.cos());
+
+                // get z angle
+                angles.z = (rot.y.x / angles.y.cos()).atan2(rot.x.x / angles.y.cos());
+
+                angles
+            }   
+
+
+            // creates a transform matrix for scaling by specied multiplier
+            pub fn from_scale(scale: $scalar) -> Self{
+                $name::new(
+                    scale, 0.0, 0.0,
+                    0.0, scale, 0.0,
+                    0.0, 0.0, scale
+                )
+            }
+
+            pub fn transposed(&self) -> $name{
+                $name::new(
+                    self.x.x, self.y.x, self.z.x,
+                    self.x.y, self.y.y, self.z.y,
+                    self.x.z, self.y.z, self.z.z
+                )
+            }
+
+            // returns the determinant of a given matrix
+            pub fn determinant(&self) -> $scalar {
+                self.x.x * (self.y.y * self.z.z - self.z.y * self.y.z)
+                    - self.x.y * (self.y.x * self.z.z - self.z.x * self.y.z)
+                    + self.x.z * (self.y.x * self.z.y - self.z.x * self.y.y)
+            }
+
+            // returns the inverse the given matrix, equivelent to matrix^-1
+            pub fn inverted(&self) -> $name{
+                let det = self.determinant();
+                if det == 0.0 {return self.clone();}
+
+                let c0  = self.y.cross(self.z);
+                let c1  = self.z.cross(self.x);
+                let c2  = self.x.cross(self.y);
+                $name::from_columns(c0, c1, c2) / det
+            }
+
+            /// returns the inverse if the absolute value of the determinant is above the tolerance, otherwise None
+            pub fn try_inverted(&self, tolerance: $scalar) -> Option<$name> {
+                if self.determinant().abs() <= tolerance {return None;}
+                Some(self.inverted())
+            }
+
+            pub fn extend(&self) -> $matrix4 {
+                $matrix4::new(
+                    self.x.x, self.x.y, self.x.z, 0.0,
+                    self.y.x, self.y.y, self.y.z, 0.0,
+                    self.z.x, self.z.y, self.z.z, 0.0,
+                    0.0, 0.0, 0.0, 1.0,
+                )
+            }
+
+            pub fn truncate(&self) -> $matrix2 {
+                $matrix2::new(
+                    self.x.x, self.x.y,
+                    self.y.x, self.y.y,
+                )
+            }
         }
-    }
-
-    pub fn from_columns(
-        c0: impl Into<Vector3>,
-        c1: impl Into<Vector3>,
-        c2: impl Into<Vector3>,
-    ) -> Self {
-        let mat = Matrix3::from_rows(c0, c1, c2);
-        mat.transposed()
-    }
-
-    pub fn c0(&self) -> Vector3 {
-        Vector3::new(self.x.x, self.y.x, self.z.x)
-    }
-
-    pub fn c1(&self) -> Vector3 {
-        Vector3::new(self.x.y, self.y.y, self.z.y)
-    }
-
-    pub fn c2(&self) -> Vector3 {
-        Vector3::new(self.x.z, self.y.z, self.z.z)
-    }
 
 
-    /// creates a rotation maxtrix for anticlockwise angle around y axis
-    pub fn from_angle_y(angle: f32) -> Self{
-        Matrix3::new(
-            angle.cos(), 0.0, angle.sin(),
-            0.0, 1.0, 0.0,
-            -angle.sin(), 0.0, angle.cos()
-        )
-    }
-
-    /// creates a rotation maxtrix for anticlockwise angle around x axis
-    pub fn from_angle_x(angle: f32) -> Self {
-        Matrix3::new(
-            1.0, 0.0, 0.0,
-            0.0, angle.cos(), -angle.sin(),
-            0.0, angle.sin(), angle.cos()
-        )
-    }
-
-    /// creates a rotation maxtrix for anticlockwise angle around z axis
-    pub fn from_angle_z(angle: f32) -> Self {
-        Matrix3::new(
-            angle.cos(), -angle.sin(), 0.0,
-            angle.sin(), angle.cos(), 0.0,
-            0.0, 0.0, 1.0
-        )
-    }
-
-    /// creates a rotation matrix for anticlockwise rotation of angle around the specified axis
-    pub fn from_angle_and_axis(angle: f32, axis: impl Into<Vector3>) -> Self {
-        let mut axis: Vector3 = axis.into();
-        axis.normalise();
-        if angle == 0.0 {return Matrix3::IDENTITY;}
-        Matrix3::new(
-            angle.cos() + axis.x.powi(2) * (1.0 - angle.cos()),
-            axis.x * axis.y * (1.0 - angle.cos()) - axis.z * angle.sin(),
-            axis.x * axis.z * (1.0 - angle.cos()) + axis.y * angle.sin(),
-
-            axis.y * axis.x * (1.0 - angle.cos()) + axis.z * angle.sin(),
-            angle.cos() + axis.y.powi(2) * (1.0 - angle.cos()),
-            axis.y * axis.z * (1.0 - angle.cos()) - axis.x * angle.sin(),
-
-            axis.z * axis.x * (1.0 - angle.cos()) - axis.y * angle.sin(),
-            axis.z * axis.y * (1.0 - angle.cos()) + axis.x * angle.sin(),
-            angle.cos() + axis.z.powi(2) * (1.0 - angle.cos())
-        )
-    }
-
-    pub fn from_euler_angles(angles: impl Into<Vector3>) -> Matrix3 {
-        let angles = angles.into();
-        let x = angles.x;
-        let y = angles.y;
-        let z = angles.z;
-        Matrix3::new(
-            y.cos() * z.cos(),
-            x.sin() * y.sin() * z.cos() - x.cos() * z.sin(),
-            x.cos() * y.sin() * z.cos() + x.sin() * z.sin(),
-
-            y.cos() * z.sin(),
-            x.sin() * y.sin() * z.sin() + x.cos() * z.cos(),
-            x.cos() * y.sin() * z.sin() - x.sin() * z.cos(),
-
-            -(y.sin()),
-            x.sin() * y.cos(),
-            x.cos() * y.cos()
-        )
-    }
-
-    // calculates the euler angles required to create a specific matrix
-    pub fn euler_angles_from(rot: Matrix3) -> Vector3 {
-        let mut angles = Vector3::ZERO;
-
-        // special cases
-        if rot.z.x == 1.0{
-            angles.y = -PI / 2.0;
-            angles.x = -(rot.x.y).atan2(-rot.x.z);
-            return angles;
+        /// builds a matrix from rows, such as the arrays given by `Vector3::outer_product`
+        impl From<[$vector3; 3]> for $name {
+            fn from(value: [$vector3; 3]) -> Self {
+                $name::from_rows(value[0], value[1], value[2])
+            }
         }
-        if rot.z.x == -1.0 {
-            angles.y = PI / 2.0;
-            angles.x = rot.x.y.atan2(rot.x.z);
-            return angles;
-        }
-
-        // get y angle
-        angles.y = -rot.z.x.asin();
-
-        // get x angle
-        angles.x = (rot.z.y / angles.y.cos()).atan2(rot.z.z / angles.y.cos());
-
-        // get z angle
-        angles.z = (rot.y.x / angles.y.cos()).atan2(rot.x.x / angles.y.cos());
 
-        angles
-    }   
+        impl Mul for $name {
+            type Output = $name;
+            fn mul(self, rhs: Self) -> Self::Output {
 
+                $name::new(
+                    self.x.dot(rhs.c0()), self.x.dot(rhs.c1()), self.x.dot(rhs.c2()),
+                    self.y.dot(rhs.c0()), self.y.dot(rhs.c1()), self.y.dot(rhs.c2()),
+                    self.z.dot(rhs.c0()), self.z.dot(rhs.c1()), self.z.dot(rhs.c2()),
+                )
+            }
+        }
 
-    // creates a transform matrix for scaling by specied multiplier
-    pub fn from_scale(scale: f32) -> Self{
-        Matrix3::new(
-            scale, 0.0, 0.0,
-            0.0, scale, 0.0,
-            0.0, 0.0, scale
-        )
-    }
-
-    pub fn transposed(&self) -> Matrix3{
-        Matrix3::new(
-            self.x.x, self.y.x, self.z.x,
-            self.x.y, self.y.y, self.z.y,
-            self.x.z, self.y.z, self.z.z
-        )
-    }
-
-    // returns the determinant of a given matrix
-    pub fn determinant(&self) -> f32 {
-        self.x.x * (self.y.y * self.z.z - self.z.y * self.y.z)
-            - self.x.y * (self.y.x * self.z.z - self.z.x * self.y.z)
-            + self.x.z * (self.y.x * self.z.y - self.z.x * self.y.y)
-    }
-
-    // returns the inverse the given matrix, equivelent to matrix^-1
-    pub fn inverted(&self) -> Matrix3{
-        let det = self.determinant();
-        if det == 0.0 {return self.clone();}
-
-        let c0  = self.y.cross(self.z);
-        let c1  = self.z.cross(self.x);
-        let c2  = self.x.cross(self.y);
-        Matrix3::from_columns(c0, c1, c2) / det
-    }
-
-    pub fn extend(&self) -> Matrix4 {
-        Matrix4::new(
-            self.x.x, self.x.y, self.x.z, 0.0,
-            self.y.x, self.y.y, self.y.z, 0.0,
-            self.z.x, self.z.y, self.z.z, 0.0,
-            0.0, 0.0, 0.0, 1.0,
-        )
-    }
+        impl Add for $name {
+            type Output = $name;
+            fn add(self, rhs: Self) -> Self::Output {
+                $name::from_rows(
+                    self.x + rhs.x,
+                    self.y + rhs.y,
+                    self.z + rhs.z
+                )
+            }
+        }
 
-    pub fn truncate(&self) -> Matrix2 {
-        Matrix2::new(
-            self.x.x, self.x.y,
-            self.y.x, self.y.y,
-        )
-    }
-}
+        impl Sub for $name {
+            type Output = $name;
+            fn sub(self, rhs: Self) -> Self::Output {
+                $name::from_rows(
+                    self.x - rhs.x,
+                    self.y - rhs.y,
+                    self.z - rhs.z
+                )
+            }
+        }
 
+        impl Mul<$vector3> for $name {
+            type Output = $vector3;
+            fn mul(self, rhs: $vector3) -> Self::Output {
+                $vector3::new(
+                    rhs.dot(self.x),
+                    rhs.dot(self.y),
+                    rhs.dot(self.z)
+                )
+            }
+        }
 
+        impl Mul<$scalar> for $name {
+            type Output = $name;
+            fn mul(self, rhs: $scalar) -> Self::Output {
+                $name::from_rows(
+                    self.x * rhs,
+                    self.y * rhs,
+                    self.z * rhs,
+                )
+            }
+        }
 
-impl Mul for Matrix3 {
-    type Output = Matrix3;
-    fn mul(self, rhs: Self) -> Self::Output {
+        impl Div<$scalar> for $name {
+            type Output = $name;
+            fn div(self, rhs: $scalar) -> Self::Output {
+                $name::from_rows(
+                    self.x / rhs,
+                    self.y / rhs,
+                    self.z / rhs,
+                )
+            }
+        }
 
-        Matrix3::new(
-            self.x.dot(rhs.c0()), self.x.dot(rhs.c1()), self.x.dot(rhs.c2()),
-            self.y.dot(rhs.c0()), self.y.dot(rhs.c1()), self.y.dot(rhs.c2()),
-            self.z.dot(rhs.c0()), self.z.dot(rhs.c1()), self.z.dot(rhs.c2()),
-        )
-    }
-}
 
-impl Add for Matrix3 {
-    type Output = Matrix3;
-    fn add(self, rhs: Self) -> Self::Output {
-        Matrix3::from_rows(
-            self.x + rhs.x,
-            self.y + rhs.y,
-            self.z + rhs.z
-        )
-    }
+        impl Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "[\n   {},\n   {},\n   {}\n]", self.x, self.y, self.z)
+            }
+        }
+    };
 }
 
-impl Sub for Matrix3 {
-    type Output = Matrix3;
-    fn sub(self, rhs: Self) -> Self::Output {
-        Matrix3::from_rows(
-            self.x - rhs.x,
-            self.y - rhs.y,
-            self.z - rhs.z
-        )
-    }
-}
+matrix3!(Matrix3, f32, Vector3, Matrix2, Matrix4);
+matrix3!(
+    /// a 3x3 matrix of f64s with all the same methods as `Matrix3`, such as for rotating `DVector3`s
+    DMatrix3, f64, DVector3, DMatrix2, DMatrix4
+);
 
-impl Mul<Vector3> for Matrix3 {
-    type Output = Vector3;
-    fn mul(self, rhs: Vector3) -> Self::Output {
-        Vector3::new(
-            rhs.dot(self.x),
-            rhs.dot(self.y),
-            rhs.dot(self.z)
-        )
-    }
-}
 
-impl Mul<f32> for Matrix3 {
-    type Output = Matrix3;
-    fn mul(self, rhs: f32) -> Self::Output {
-        Matrix3::from_rows(
-            self.x * rhs,
-            self.y * rhs,
-            self.z * rhs,
-        )
+// precision conversions, only widening is done implicitly
+impl From<Matrix3> for DMatrix3 {
+    fn from(value: Matrix3) -> Self {
+        DMatrix3 {
+            x: value.x.into(),
+            y: value.y.into(),
+            z: value.z.into()
+        }
     }
 }
 
-impl Div<f32> for Matrix3 {
-    type Output = Matrix3;
-    fn div(self, rhs: f32) -> Self::Output {
-        Matrix3::from_rows(
-            self.x / rhs,
-            self.y / rhs,
-            self.z / rhs,
-        )
+impl DMatrix3 {
+    /// rounds each element to the nearest f32
+    pub fn to_single(&self) -> Matrix3 {
+        Matrix3 {
+            x: self.x.to_single(),
+            y: self.y.to_single(),
+            z: self.z.to_single()
+        }
     }
 }
-
-
-impl Display for Matrix3 {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "[\n   {},\n   {},\n   {}\n]", self.x, self.y, self.z)
-    }
-}
\ No newline at end of file
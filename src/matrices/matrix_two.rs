@@ -1,136 +1,174 @@
 use std::{ops::*, fmt::Display};
 
-use crate::{Matrix3, Vector2};
+use crate::{Matrix3, Vector2, DMatrix3, DVector2};
 
 
-#[derive(Default, Clone, Copy, Debug, PartialEq, PartialOrd)]
-pub struct Matrix2 {
-    pub x: Vector2,
-    pub y: Vector2
-}
-
-impl Matrix2 {
-    pub const IDENTITY: Matrix2 = Matrix2 {
-        x: Vector2::X,
-        y: Vector2::Y
-    };
-
-    pub const ONE: Matrix2 = Matrix2 {
-        x: Vector2::ONE,
-        y: Vector2::ONE
-    };
+macro_rules! matrix2 {
+    ($(#[$attr:meta])* $name:ident, $scalar:ident, $vector2:ident, $matrix3:ident) => {
+        $(#[$attr])*
+        #[derive(Default, Clone, Copy, Debug, PartialEq, PartialOrd)]
+        pub struct $name {
+            pub x: $vector2,
+            pub y: $vector2
+        }
 
-    pub const EPSILON: Matrix2 = Matrix2 {
-        x: Vector2::EPSILON,
-        y: Vector2::EPSILON
-    };
+        impl $name {
+            pub const IDENTITY: $name = $name {
+                x: $vector2::X,
+                y: $vector2::Y
+            };
+
+            pub const ONE: $name = $name {
+                x: $vector2::ONE,
+                y: $vector2::ONE
+            };
+
+            pub const EPSILON: $name = $name {
+                x: $vector2::EPSILON,
+                y: $vector2::EPSILON
+            };
+
+            pub const fn new(
+                r0c0: $scalar, r0c1: $scalar,
+                r1c0: $scalar, r1c1: $scalar,
+            ) -> $name{
+                $name {
+                    x: $vector2::new(r0c0, r0c1),
+                    y: $vector2::new(r1c0, r1c1)
+                }
+            }
+
+            pub fn from_rows(
+                x: impl Into<$vector2>,
+                y: impl Into<$vector2>
+            ) -> $name {
+                $name {
+                    x: x.into(),
+                    y: y.into()
+                }
+            }
+
+            pub fn c0(&self) -> $vector2 {
+                $vector2::new(self.x.x, self.y.x)
+            }
+
+            pub fn c1(&self) -> $vector2 {
+                $vector2::new(self.x.y, self.y.y)
+            }
+
+            pub fn det(&self) -> $scalar {
+                self.x.x * self.y.y - self.x.y * self.y.x
+            }
+
+            pub fn inverted(&self) -> $name {
+                $name::new(
+                    self.y.y, -self.x.y,
+                    -self.y.x, self.x.x
+                ) / self.det()
+            }
+
+            /// returns the inverse if the absolute value of the determinant is above the tolerance, otherwise None
+            pub fn try_inverted(&self, tolerance: $scalar) -> Option<$name> {
+                if self.det().abs() <= tolerance {return None;}
+                Some(self.inverted())
+            }
+
+            pub fn extend(&self) -> $matrix3 {
+                $matrix3::new(
+                    self.x.x, self.x.y, 0.0,
+                    self.y.x, self.y.y, 0.0,
+                    0.0, 0.0, 1.0
+                )
+            }
+        }
 
-    pub const fn new(
-        r0c0: f32, r0c1: f32,
-        r1c0: f32, r1c1: f32,
-    ) -> Matrix2{
-        Matrix2 {
-            x: Vector2::new(r0c0, r0c1),
-            y: Vector2::new(r1c0, r1c1)
+        impl Mul for $name {
+            type Output = Self;
+            fn mul(self, rhs: Self) -> Self::Output {
+                $name::new(
+                    self.x.dot(rhs.c0()), self.x.dot(rhs.c1()),
+                    self.y.dot(rhs.c0()), self.y.dot(rhs.c1()) 
+                )
+            }
         }
-    }
 
-    pub fn from_rows(
-        x: impl Into<Vector2>,
-        y: impl Into<Vector2>
-    ) -> Matrix2 {
-        Matrix2 {
-            x: x.into(),
-            y: y.into()
+        impl Add for $name {
+            type Output = Self;
+            fn add(self, rhs: Self) -> Self::Output {
+                $name::from_rows(
+                    self.x + rhs.x,
+                    self.y + rhs.y
+                )
+            }
         }
-    }
 
-    pub fn c0(&self) -> Vector2 {
-        Vector2::new(self.x.x, self.y.x)
-    }
+        impl Sub for $name {
+            type Output = Self;
+            fn sub(self, rhs: Self) -> Self::Output {
+                $name::from_rows(
+                    self.x - rhs.x,
+                    self.y - rhs.y
+                )
+            }
+        }
 
-    pub fn c1(&self) -> Vector2 {
-        Vector2::new(self.x.y, self.y.y)
-    }
 
-    pub fn det(&self) -> f32 {
-        self.x.x * self.y.y - self.x.y * self.y.x
-    }
+        impl Mul<$scalar> for $name {
+            type Output = Self;
+            fn mul(self, rhs: $scalar) -> Self::Output {
+                $name {
+                    x: self.x * rhs,
+                    y: self.y * rhs
+                }
+            }
+        }
 
-    pub fn inverted(&self) -> Matrix2 {
-        Matrix2::new(
-            self.y.y, -self.x.y,
-            -self.y.x, self.x.x
-        ) / self.det()
-    }
+        impl Mul<$vector2> for $name {
+            type Output = $vector2;
+            fn mul(self, rhs: $vector2) -> Self::Output {
+                $vector2::new(self.x.dot(rhs), self.y.dot(rhs))
+            }
+        }
 
-    pub fn extend(&self) -> Matrix3 {
-        Matrix3::new(
-            self.x.x, self.x.y, 0.0,
-            self.y.x, self.y.y, 0.0,
-            0.0, 0.0, 1.0
-        )
-    }
-}
 
-impl Mul for Matrix2 {
-    type Output = Self;
-    fn mul(self, rhs: Self) -> Self::Output {
-        Matrix2::new(
-            self.x.dot(rhs.c0()), self.x.dot(rhs.c1()),
-            self.y.dot(rhs.c0()), self.y.dot(rhs.c1()) 
-        )
-    }
-}
+        impl Div<$scalar> for $name {
+            type Output = $name;
+            fn div(self, rhs: $scalar) -> Self::Output {
+                $name::from_rows(self.x / rhs, self.y / rhs)
+            }
+        }
 
-impl Add for Matrix2 {
-    type Output = Self;
-    fn add(self, rhs: Self) -> Self::Output {
-        Matrix2::from_rows(
-            self.x + rhs.x,
-            self.y + rhs.y
-        )
-    }
+        impl Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "[\n   {},\n   {}\n]", self.x, self.y)
+            }
+        }
+    };
 }
 
-impl Sub for Matrix2 {
-    type Output = Self;
-    fn sub(self, rhs: Self) -> Self::Output {
-        Matrix2::from_rows(
-            self.x - rhs.x,
-            self.y - rhs.y
-        )
-    }
-}
+matrix2!(Matrix2, f32, Vector2, Matrix3);
+matrix2!(
+    /// a 2x2 matrix of f64s with all the same methods as `Matrix2`
+    DMatrix2, f64, DVector2, DMatrix3
+);
 
 
-impl Mul<f32> for Matrix2 {
-    type Output = Self;
-    fn mul(self, rhs: f32) -> Self::Output {
-        Matrix2 {
-            x: self.x * rhs,
-            y: self.y * rhs
+// precision conversions, only widening is done implicitly
+impl From<Matrix2> for DMatrix2 {
+    fn from(value: Matrix2) -> Self {
+        DMatrix2 {
+            x: value.x.into(),
+            y: value.y.into()
         }
     }
 }
 
-impl Mul<Vector2> for Matrix2 {
-    type Output = Vector2;
-    fn mul(self, rhs: Vector2) -> Self::Output {
-        Vector2::new(self.x.dot(rhs), self.y.dot(rhs))
-    }
-}
-
-
-impl Div<f32> for Matrix2 {
-    type Output = Matrix2;
-    fn div(self, rhs: f32) -> Self::Output {
-        Matrix2::from_rows(self.x / rhs, self.y / rhs)
+impl DMatrix2 {
+    /// rounds each element to the nearest f32
+    pub fn to_single(&self) -> Matrix2 {
+        Matrix2 {
+            x: self.x.to_single(),
+            y: self.y.to_single()
+        }
     }
 }
-
-impl Display for Matrix2 {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "[\n   {},\n   {}\n]", self.x, self.y)
-    }
-}
\ No newline at end of file
@@ -2,8 +2,16 @@ mod matrix_two;
 mod matrix_three;
 mod matrix_four;
 mod matrix_n_m;
+mod matrix_r_c;
+mod least_squares;
+mod eigen;
+mod svd;
 
-pub use matrix_two::Matrix2;
-pub use matrix_three::Matrix3;
-pub use matrix_four::Matrix4;
-pub use matrix_n_m::MaxtrixNM;
\ No newline at end of file
+pub use matrix_two::{Matrix2, DMatrix2};
+pub use matrix_three::{Matrix3, DMatrix3};
+pub use matrix_four::{Matrix4, DMatrix4, Handedness, DepthRange, AffineDecomposition, DAffineDecomposition};
+pub use matrix_n_m::{MaxtrixNM, MatrixIndexError, MatrixArithmeticError, LUDecomposition};
+pub use matrix_r_c::Matrix;
+pub use least_squares::{QRDecomposition, LeastSquaresSolution, least_squares, polynomial_fit};
+pub use eigen::SymmetricEigen;
+pub use svd::SingularValueDecomposition;
\ No newline at end of file
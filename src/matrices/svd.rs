@@ -0,0 +1,252 @@
+use super::{MaxtrixNM, Matrix3, Matrix4, DMatrix3, DMatrix4};
+use super::super::vectors::{Vector3, Vector4, DVector3, DVector4};
+
+
+const MAX_SWEEPS: usize = 60;
+
+/// the result of `MaxtrixNM::svd`, where A = U * diag(singular_values) * V^T
+///
+/// for an m by n matrix with k = min(m, n), U is m by k and V is n by k, both with orthonormal columns.
+/// The singular values are in descending order
+#[derive(Debug, Clone)]
+pub struct SingularValueDecomposition {
+    pub u: MaxtrixNM,
+    pub singular_values: Vec<f32>,
+    pub v: MaxtrixNM,
+}
+
+
+macro_rules! one_sided_jacobi {
+    ($name:ident, $complement:ident, $scalar:ident) => {
+        // one sided jacobi svd, rotates pairs of columns until they are all orthogonal to each other.
+        // takes the columns of a tall (or square) matrix and returns the columns of U, the singular values and the columns of V
+        fn $name(mut columns: Vec<Vec<$scalar>>) -> (Vec<Vec<$scalar>>, Vec<$scalar>, Vec<Vec<$scalar>>) {
+            let n = columns.len();
+            let m = columns.first().map_or(0, |col| col.len());
+            let mut v: Vec<Vec<$scalar>> = (0..n).map(|row| (0..n).map(|col| if row == col {1.0} else {0.0}).collect()).collect();
+
+            for _ in 0..MAX_SWEEPS {
+                let mut rotated = false;
+                for i in 0..n {
+                    for j in (i + 1)..n {
+                        let alpha: $scalar = columns[i].iter().map(|val| val * val).sum();
+                        let beta: $scalar = columns[j].iter().map(|val| val * val).sum();
+                        let gamma: $scalar = columns[i].iter().zip(&columns[j]).map(|(a, b)| a * b).sum();
+                        if gamma.abs() <= $scalar::EPSILON * (alpha * beta).sqrt() || gamma == 0.0 {continue;}
+                        rotated = true;
+
+                        let zeta = (beta - alpha) / (2.0 * gamma);
+                        let t = zeta.signum() / (zeta.abs() + (1.0 + zeta * zeta).sqrt());
+                        let c = 1.0 / (1.0 + t * t).sqrt();
+                        let s = c * t;
+
+                        for target in [&mut columns, &mut v] {
+                            let (left, right) = target.split_at_mut(j);
+                            for (a, b) in left[i].iter_mut().zip(right[0].iter_mut()) {
+                                let (old_a, old_b) = (*a, *b);
+                                *a = c * old_a - s * old_b;
+                                *b = s * old_a + c * old_b;
+                            }
+                        }
+                    }
+                }
+                if !rotated {break;}
+            }
+
+            let mut singular_values: Vec<$scalar> = columns.iter().map(|col| col.iter().map(|val| val * val).sum::<$scalar>().sqrt()).collect();
+            let mut order: Vec<usize> = (0..n).collect();
+            order.sort_by(|a, b| singular_values[*b].total_cmp(&singular_values[*a]));
+
+            let max = order.first().map_or(0.0, |i| singular_values[*i]);
+            let tolerance = max * (m.max(n) as $scalar) * $scalar::EPSILON;
+
+            let mut u: Vec<Vec<$scalar>> = Vec::new();
+            for i in order.iter() {
+                let sigma = singular_values[*i];
+                if sigma > tolerance && sigma > 0.0 {
+                    u.push(columns[*i].iter().map(|val| val / sigma).collect());
+                } else {
+                    // columns for zero singular values carry no information, so fill them in with whatever keeps U orthonormal
+                    u.push($complement(&u, m));
+                }
+            }
+            let v = order.iter().map(|i| v[*i].clone()).collect();
+            singular_values = order.iter().map(|i| singular_values[*i]).collect();
+
+            (u, singular_values, v)
+        }
+
+        // finds a unit vector orthogonal to all of the given orthonormal vectors, by projecting the axes off them and taking the longest remainder
+        fn $complement(basis: &[Vec<$scalar>], size: usize) -> Vec<$scalar> {
+            let mut best = vec![0.0; size];
+            let mut best_length = 0.0;
+            for axis in 0..size {
+                let mut candidate = vec![0.0; size];
+                candidate[axis] = 1.0;
+                for vector in basis {
+                    let dot: $scalar = candidate.iter().zip(vector).map(|(a, b)| a * b).sum();
+                    candidate.iter_mut().zip(vector).for_each(|(a, b)| *a -= dot * b);
+                }
+                let length = candidate.iter().map(|val| val * val).sum::<$scalar>().sqrt();
+                if length > best_length {
+                    best_length = length;
+                    best = candidate;
+                }
+            }
+            if best_length > 0.0 {
+                best.iter_mut().for_each(|val| *val /= best_length);
+            }
+            best
+        }
+    };
+}
+
+one_sided_jacobi!(one_sided_jacobi, orthonormal_complement, f32);
+one_sided_jacobi!(one_sided_jacobi_f64, orthonormal_complement_f64, f64);
+
+// turns a list of column vectors into a matrix
+fn from_column_list(columns: &[Vec<f32>], height: usize) -> MaxtrixNM {
+    let mut result = MaxtrixNM::new_empty(columns.len(), height);
+    for (x, column) in columns.iter().enumerate() {
+        for (y, val) in column.iter().enumerate() {
+            result.matrix[y][x] = *val;
+        }
+    }
+    result
+}
+
+
+impl MaxtrixNM {
+    /// singular value decomposition using one sided jacobi rotations
+    pub fn svd(&self) -> SingularValueDecomposition {
+        // the algorithm works on columns so wide matrices are done through their transpose
+        if self.width > self.height {
+            let transposed = self.transpose().svd();
+            return SingularValueDecomposition {
+                u: transposed.v,
+                singular_values: transposed.singular_values,
+                v: transposed.u,
+            };
+        }
+
+        let columns = (0..self.width).map(|x| self.get_column(x).unwrap()).collect();
+        let (u, singular_values, v) = one_sided_jacobi(columns);
+        SingularValueDecomposition {
+            u: from_column_list(&u, self.height),
+            singular_values,
+            v: from_column_list(&v, self.width),
+        }
+    }
+
+    /// the Moore-Penrose pseudo inverse, which is the inverse for invertible matrices and the closest thing to one otherwise
+    ///
+    /// singular values that are tiny compared to the largest are treated as zero
+    pub fn pseudo_inverse(&self) -> MaxtrixNM {
+        let SingularValueDecomposition {u, singular_values, v} = self.svd();
+        let max = singular_values.first().copied().unwrap_or(0.0);
+        let tolerance = max * (self.width.max(self.height) as f32) * f32::EPSILON;
+
+        // V * inverse(S) * U^T
+        let mut result = MaxtrixNM::new_empty(self.height, self.width);
+        for (k, sigma) in singular_values.iter().enumerate() {
+            if *sigma <= tolerance {continue;}
+            for (row, result_row) in result.matrix.iter_mut().enumerate() {
+                let v_val = v.matrix[row][k] / sigma;
+                for (col, val) in result_row.iter_mut().enumerate() {
+                    *val += v_val * u.matrix[col][k];
+                }
+            }
+        }
+        result
+    }
+}
+
+macro_rules! svd3 {
+    ($name:ident, $scalar:ident, $vector3:ident, $jacobi:ident) => {
+        impl $name {
+            /// singular value decomposition, giving (U, singular values, V) where self = U * diag(singular values) * V^T
+            ///
+            /// the singular values are in descending order and U and V are orthogonal
+            pub fn svd(&self) -> ($name, $vector3, $name) {
+                let columns = [self.c0(), self.c1(), self.c2()].iter().map(|col| vec![col.x, col.y, col.z]).collect();
+                let (u, sigma, v) = $jacobi(columns);
+                let to_matrix = |cols: Vec<Vec<$scalar>>| $name::from_columns(
+                    [cols[0][0], cols[0][1], cols[0][2]],
+                    [cols[1][0], cols[1][1], cols[1][2]],
+                    [cols[2][0], cols[2][1], cols[2][2]],
+                );
+                (to_matrix(u), $vector3::new(sigma[0], sigma[1], sigma[2]), to_matrix(v))
+            }
+
+            /// the Moore-Penrose pseudo inverse, singular values that are tiny compared to the largest are treated as zero
+            pub fn pseudo_inverse(&self) -> $name {
+                let (u, sigma, v) = self.svd();
+                let tolerance = sigma.x * 3.0 * $scalar::EPSILON;
+                let invert = |val: $scalar| if val > tolerance {1.0 / val} else {0.0};
+                // V * inverse(S) * U^T
+                $name::from_columns(v.c0() * invert(sigma.x), v.c1() * invert(sigma.y), v.c2() * invert(sigma.z)) * u.transposed()
+            }
+
+            /// splits the matrix into self = rotation * stretch, where rotation is a proper rotation matrix and stretch is symmetric
+            ///
+            /// useful for getting the pure rotation back out of a matrix that has been skewed or scaled, any reflection ends up in the stretch
+            pub fn polar_decomposition(&self) -> ($name, $name) {
+                let (mut u, mut sigma, v) = self.svd();
+                // make sure the rotation doesn't contain a reflection by flipping the least important axis
+                if (u * v.transposed()).determinant() < 0.0 {
+                    u = $name::from_columns(u.c0(), u.c1(), -u.c2());
+                    sigma.z = -sigma.z;
+                }
+                let rotation = u * v.transposed();
+                let stretch = v * $name::new(
+                    sigma.x, 0.0, 0.0,
+                    0.0, sigma.y, 0.0,
+                    0.0, 0.0, sigma.z
+                ) * v.transposed();
+                (rotation, stretch)
+            }
+        }
+    };
+}
+
+macro_rules! svd4 {
+    ($name:ident, $scalar:ident, $vector4:ident, $jacobi:ident) => {
+        impl $name {
+            /// singular value decomposition, giving (U, singular values, V) where self = U * diag(singular values) * V^T
+            ///
+            /// the singular values are in descending order and U and V are orthogonal
+            pub fn svd(&self) -> ($name, $vector4, $name) {
+                let columns = [self.c0(), self.c1(), self.c2(), self.c3()].iter().map(|col| vec![col.x, col.y, col.z, col.w]).collect();
+                let (u, sigma, v) = $jacobi(columns);
+                // the columns go in as rows then get flipped over
+                let to_matrix = |cols: Vec<Vec<$scalar>>| $name::from_rows(
+                    [cols[0][0], cols[0][1], cols[0][2], cols[0][3]],
+                    [cols[1][0], cols[1][1], cols[1][2], cols[1][3]],
+                    [cols[2][0], cols[2][1], cols[2][2], cols[2][3]],
+                    [cols[3][0], cols[3][1], cols[3][2], cols[3][3]],
+                ).transposed();
+                (to_matrix(u), $vector4::new(sigma[0], sigma[1], sigma[2], sigma[3]), to_matrix(v))
+            }
+
+            /// the Moore-Penrose pseudo inverse, singular values that are tiny compared to the largest are treated as zero
+            pub fn pseudo_inverse(&self) -> $name {
+                let (u, sigma, v) = self.svd();
+                let tolerance = sigma.x * 4.0 * $scalar::EPSILON;
+                let invert = |val: $scalar| if val > tolerance {1.0 / val} else {0.0};
+                // V * inverse(S) * U^T, built from the rows of (V * inverse(S))^T
+                let scaled = $name::from_rows(
+                    v.c0() * invert(sigma.x),
+                    v.c1() * invert(sigma.y),
+                    v.c2() * invert(sigma.z),
+                    v.c3() * invert(sigma.w),
+                ).transposed();
+                scaled * u.transposed()
+            }
+        }
+    };
+}
+
+svd3!(Matrix3, f32, Vector3, one_sided_jacobi);
+svd3!(DMatrix3, f64, DVector3, one_sided_jacobi_f64);
+svd4!(Matrix4, f32, Vector4, one_sided_jacobi);
+svd4!(DMatrix4, f64, DVector4, one_sided_jacobi_f64);
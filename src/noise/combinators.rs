@@ -0,0 +1,313 @@
+use std::cell::Cell;
+use super::{NoiseFn, lerp, interp_by_fn};
+
+
+/// the same value everywhere
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct Constant(pub f32);
+
+impl NoiseFn for Constant {
+    fn sample2d(&self, _x: f32, _y: f32) -> f32 {
+        self.0
+    }
+
+    fn sample3d(&self, _x: f32, _y: f32, _z: f32) -> f32 {
+        self.0
+    }
+}
+
+
+/// noise made from a 2d and a 3d closure, so seeded generators or captured parameters can be used in a noise graph
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FnNoise<F2: Fn(f32, f32) -> f32, F3: Fn(f32, f32, f32) -> f32> {
+    pub sample2d: F2,
+    pub sample3d: F3,
+}
+
+impl<F2: Fn(f32, f32) -> f32, F3: Fn(f32, f32, f32) -> f32> FnNoise<F2, F3> {
+    pub fn new(sample2d: F2, sample3d: F3) -> Self {
+        FnNoise {sample2d, sample3d}
+    }
+}
+
+impl<F2: Fn(f32, f32) -> f32, F3: Fn(f32, f32, f32) -> f32> NoiseFn for FnNoise<F2, F3> {
+    fn sample2d(&self, x: f32, y: f32) -> f32 {
+        (self.sample2d)(x, y)
+    }
+
+    fn sample3d(&self, x: f32, y: f32, z: f32) -> f32 {
+        (self.sample3d)(x, y, z)
+    }
+}
+
+
+/// the two noises added together
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AddNoise<A: NoiseFn, B: NoiseFn> {
+    pub a: A,
+    pub b: B,
+}
+
+impl<A: NoiseFn, B: NoiseFn> NoiseFn for AddNoise<A, B> {
+    fn sample2d(&self, x: f32, y: f32) -> f32 {
+        self.a.sample2d(x, y) + self.b.sample2d(x, y)
+    }
+
+    fn sample3d(&self, x: f32, y: f32, z: f32) -> f32 {
+        self.a.sample3d(x, y, z) + self.b.sample3d(x, y, z)
+    }
+}
+
+
+/// the two noises multiplied together
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MultiplyNoise<A: NoiseFn, B: NoiseFn> {
+    pub a: A,
+    pub b: B,
+}
+
+impl<A: NoiseFn, B: NoiseFn> NoiseFn for MultiplyNoise<A, B> {
+    fn sample2d(&self, x: f32, y: f32) -> f32 {
+        self.a.sample2d(x, y) * self.b.sample2d(x, y)
+    }
+
+    fn sample3d(&self, x: f32, y: f32, z: f32) -> f32 {
+        self.a.sample3d(x, y, z) * self.b.sample3d(x, y, z)
+    }
+}
+
+
+/// multiplies the noise by the scale then adds the bias
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScaleBias<N: NoiseFn> {
+    pub source: N,
+    pub scale: f32,
+    pub bias: f32,
+}
+
+impl<N: NoiseFn> NoiseFn for ScaleBias<N> {
+    fn sample2d(&self, x: f32, y: f32) -> f32 {
+        self.source.sample2d(x, y) * self.scale + self.bias
+    }
+
+    fn sample3d(&self, x: f32, y: f32, z: f32) -> f32 {
+        self.source.sample3d(x, y, z) * self.scale + self.bias
+    }
+}
+
+
+/// keeps the noise between the min and max
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClampNoise<N: NoiseFn> {
+    pub source: N,
+    pub min: f32,
+    pub max: f32,
+}
+
+impl<N: NoiseFn> NoiseFn for ClampNoise<N> {
+    fn sample2d(&self, x: f32, y: f32) -> f32 {
+        self.source.sample2d(x, y).clamp(self.min, self.max)
+    }
+
+    fn sample3d(&self, x: f32, y: f32, z: f32) -> f32 {
+        self.source.sample3d(x, y, z).clamp(self.min, self.max)
+    }
+}
+
+
+/// remaps the noise through a curve of control points, each one an input value and the output it maps to
+///
+/// values between control points are interpolated with the interp fn, or lerped if there isn't one,
+/// and values past the ends take the output of the end point
+#[derive(Debug, Clone)]
+pub struct Curve<N: NoiseFn> {
+    pub source: N,
+    points: Vec<(f32, f32)>,
+    pub interp_fn: Option<fn(f32) -> f32>,
+}
+
+impl<N: NoiseFn> Curve<N> {
+    pub fn new(source: N, mut points: Vec<(f32, f32)>) -> Self {
+        points.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Curve {
+            source,
+            points,
+            interp_fn: None,
+        }
+    }
+
+    /// the control points, sorted by their input value
+    pub fn get_control_points(&self) -> &Vec<(f32, f32)> {
+        &self.points
+    }
+
+    pub fn add_control_point(&mut self, input: f32, output: f32) {
+        let index = self.points.partition_point(|point| point.0 < input);
+        self.points.insert(index, (input, output));
+    }
+
+    /// the value unchanged if there are no control points
+    pub fn remap(&self, value: f32) -> f32 {
+        let index = self.points.partition_point(|point| point.0 < value);
+        if self.points.is_empty() {return value;}
+        if index == 0 {return self.points[0].1;}
+        if index == self.points.len() {return self.points[index - 1].1;}
+
+        let ((low_in, low_out), (high_in, high_out)) = (self.points[index - 1], self.points[index]);
+        let position = (value - low_in) / (high_in - low_in);
+        match self.interp_fn {
+            Some(func) => interp_by_fn(low_out, high_out, position, func),
+            None => lerp(low_out, high_out, position),
+        }
+    }
+}
+
+impl<N: NoiseFn> NoiseFn for Curve<N> {
+    fn sample2d(&self, x: f32, y: f32) -> f32 {
+        self.remap(self.source.sample2d(x, y))
+    }
+
+    fn sample3d(&self, x: f32, y: f32, z: f32) -> f32 {
+        self.remap(self.source.sample3d(x, y, z))
+    }
+}
+
+
+/// blends from the low noise to the high noise as the control noise goes from -1 to 1
+///
+/// the blend uses the interp fn, or lerps if there isn't one
+#[derive(Debug, Clone, Copy)]
+pub struct Blend<L: NoiseFn, H: NoiseFn, C: NoiseFn> {
+    pub low: L,
+    pub high: H,
+    pub control: C,
+    pub interp_fn: Option<fn(f32) -> f32>,
+}
+
+impl<L: NoiseFn, H: NoiseFn, C: NoiseFn> Blend<L, H, C> {
+    fn blend(&self, low: f32, high: f32, control: f32) -> f32 {
+        let position = (control + 1.0) * 0.5;
+        match self.interp_fn {
+            Some(func) => interp_by_fn(low, high, position, func),
+            None => lerp(low, high, position),
+        }
+    }
+}
+
+impl<L: NoiseFn, H: NoiseFn, C: NoiseFn> NoiseFn for Blend<L, H, C> {
+    fn sample2d(&self, x: f32, y: f32) -> f32 {
+        self.blend(self.low.sample2d(x, y), self.high.sample2d(x, y), self.control.sample2d(x, y))
+    }
+
+    fn sample3d(&self, x: f32, y: f32, z: f32) -> f32 {
+        self.blend(self.low.sample3d(x, y, z), self.high.sample3d(x, y, z), self.control.sample3d(x, y, z))
+    }
+}
+
+
+/// the low noise where the control noise is below the threshold and the high noise where it is above
+///
+/// within the falloff either side of the threshold the two are blended with the interp fn, or lerped if there isn't one
+#[derive(Debug, Clone, Copy)]
+pub struct Select<L: NoiseFn, H: NoiseFn, C: NoiseFn> {
+    pub low: L,
+    pub high: H,
+    pub control: C,
+    pub threshold: f32,
+    pub falloff: f32,
+    pub interp_fn: Option<fn(f32) -> f32>,
+}
+
+impl<L: NoiseFn, H: NoiseFn, C: NoiseFn> Select<L, H, C> {
+    // only samples the noise that is needed
+    fn select(&self, low: impl Fn() -> f32, high: impl Fn() -> f32, control: f32) -> f32 {
+        let falloff = self.falloff.abs();
+        if control <= self.threshold - falloff {return low();}
+        if control >= self.threshold + falloff {return high();}
+
+        let position = (control - (self.threshold - falloff)) / (2.0 * falloff);
+        match self.interp_fn {
+            Some(func) => interp_by_fn(low(), high(), position, func),
+            None => lerp(low(), high(), position),
+        }
+    }
+}
+
+impl<L: NoiseFn, H: NoiseFn, C: NoiseFn> NoiseFn for Select<L, H, C> {
+    fn sample2d(&self, x: f32, y: f32) -> f32 {
+        self.select(|| self.low.sample2d(x, y), || self.high.sample2d(x, y), self.control.sample2d(x, y))
+    }
+
+    fn sample3d(&self, x: f32, y: f32, z: f32) -> f32 {
+        self.select(|| self.low.sample3d(x, y, z), || self.high.sample3d(x, y, z), self.control.sample3d(x, y, z))
+    }
+}
+
+
+// where the displacement noise is sampled for each axis, far enough apart that the axes move independently
+const TURBULENCE_OFFSETS: [[f32; 3]; 3] = [
+    [12.4142, 65.5361, 31.3377],
+    [26.5192, 18.1934, 71.8463],
+    [53.8205, 11.2307, 42.5913],
+];
+
+/// moves the point the source noise is sampled at by the displacement noise times the power, warping its shape
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DomainTurbulence<N: NoiseFn, D: NoiseFn> {
+    pub source: N,
+    pub displacement: D,
+    pub power: f32,
+}
+
+impl<N: NoiseFn, D: NoiseFn> NoiseFn for DomainTurbulence<N, D> {
+    fn sample2d(&self, x: f32, y: f32) -> f32 {
+        let [dx, dy] = [TURBULENCE_OFFSETS[0], TURBULENCE_OFFSETS[1]]
+            .map(|offset| self.displacement.sample2d(x + offset[0], y + offset[1]) * self.power);
+        self.source.sample2d(x + dx, y + dy)
+    }
+
+    fn sample3d(&self, x: f32, y: f32, z: f32) -> f32 {
+        let [dx, dy, dz] = TURBULENCE_OFFSETS
+            .map(|offset| self.displacement.sample3d(x + offset[0], y + offset[1], z + offset[2]) * self.power);
+        self.source.sample3d(x + dx, y + dy, z + dz)
+    }
+}
+
+
+/// remembers the last point sampled in 2d and in 3d, so noise used more than once in a graph is only worked out once
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cache<N: NoiseFn> {
+    pub source: N,
+    last2d: Cell<Option<([f32; 2], f32)>>,
+    last3d: Cell<Option<([f32; 3], f32)>>,
+}
+
+impl<N: NoiseFn> Cache<N> {
+    pub fn new(source: N) -> Self {
+        Cache {
+            source,
+            last2d: Cell::new(None),
+            last3d: Cell::new(None),
+        }
+    }
+}
+
+impl<N: NoiseFn> NoiseFn for Cache<N> {
+    fn sample2d(&self, x: f32, y: f32) -> f32 {
+        if let Some((point, val)) = self.last2d.get() {
+            if point == [x, y] {return val;}
+        }
+        let val = self.source.sample2d(x, y);
+        self.last2d.set(Some(([x, y], val)));
+        val
+    }
+
+    fn sample3d(&self, x: f32, y: f32, z: f32) -> f32 {
+        if let Some((point, val)) = self.last3d.get() {
+            if point == [x, y, z] {return val;}
+        }
+        let val = self.source.sample3d(x, y, z);
+        self.last3d.set(Some(([x, y, z], val)));
+        val
+    }
+}
@@ -0,0 +1,168 @@
+use super::{Vector3, ChaChaRng, SeedableRng, Rng, NoiseFn};
+
+
+/// how the octaves of a `Fractal` are added together
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FractalType {
+    /// fractal brownian motion, the octaves are just added up, outputs between -1 and 1
+    #[default]
+    Fbm,
+    /// sharp ridges where the base noise crosses zero, each octave is weighted by the one before
+    /// so the detail builds up along the ridges, outputs between -1 and 1
+    RidgedMulti,
+    /// the absolute value of each octave, giving puffy rounded lumps, outputs between -1 and 1
+    Billow,
+    /// the absolute value of each octave without remapping, outputs between 0 and 1
+    Turbulence,
+}
+
+
+/// layers several octaves of a base noise, each one at a higher frequency and lower amplitude than the last
+///
+/// every octave after the first is sampled at a different offset picked from the seed,
+/// so the octaves of the same base noise do not line up with each other
+///
+/// the output ranges on `FractalType` assume the base noise is between -1 and 1, like all the noise in this module
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fractal<N: NoiseFn> {
+    base: N,
+    fractal_type: FractalType,
+    octaves: usize,
+    lacunarity: f32,
+    gain: f32,
+    seed: u64,
+    offsets: Vec<Vector3>,
+}
+
+impl<N: NoiseFn> Fractal<N> {
+    /// 6 octaves, a lacunarity of 2 and a gain of 0.5
+    pub fn new(base: N, fractal_type: FractalType) -> Self {
+        let mut fractal = Fractal {
+            base,
+            fractal_type,
+            octaves: 6,
+            lacunarity: 2.0,
+            gain: 0.5,
+            seed: 0,
+            offsets: Vec::new(),
+        };
+        fractal.make_offsets();
+        fractal
+    }
+
+    pub fn fbm(base: N) -> Self {
+        Fractal::new(base, FractalType::Fbm)
+    }
+
+    pub fn ridged_multi(base: N) -> Self {
+        Fractal::new(base, FractalType::RidgedMulti)
+    }
+
+    pub fn billow(base: N) -> Self {
+        Fractal::new(base, FractalType::Billow)
+    }
+
+    pub fn turbulence(base: N) -> Self {
+        Fractal::new(base, FractalType::Turbulence)
+    }
+
+    pub fn get_base(&self) -> &N {
+        &self.base
+    }
+
+    pub fn get_fractal_type(&self) -> FractalType {
+        self.fractal_type
+    }
+
+    pub fn set_fractal_type(&mut self, fractal_type: FractalType) {
+        self.fractal_type = fractal_type;
+    }
+
+    pub fn get_octaves(&self) -> usize {
+        self.octaves
+    }
+
+    /// the number of layers of noise, at least 1
+    pub fn set_octaves(&mut self, octaves: usize) {
+        self.octaves = octaves.max(1);
+        self.make_offsets();
+    }
+
+    pub fn get_lacunarity(&self) -> f32 {
+        self.lacunarity
+    }
+
+    /// how much the frequency is multiplied by for each octave
+    pub fn set_lacunarity(&mut self, lacunarity: f32) {
+        self.lacunarity = lacunarity;
+    }
+
+    pub fn get_gain(&self) -> f32 {
+        self.gain
+    }
+
+    /// how much the amplitude is multiplied by for each octave, also called persistence
+    pub fn set_gain(&mut self, gain: f32) {
+        self.gain = gain;
+    }
+
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// picks the offsets each octave is sampled at
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.make_offsets();
+    }
+
+    // the first octave is left where it is, so a single octave is the same as the base noise
+    fn make_offsets(&mut self) {
+        let mut rng = ChaChaRng::seed_from_u64(self.seed);
+        self.offsets = (0..self.octaves).map(|octave| {
+            let offset = Vector3::new(rng.gen_range(-256.0..256.0), rng.gen_range(-256.0..256.0), rng.gen_range(-256.0..256.0));
+            if octave == 0 {Vector3::ZERO} else {offset}
+        }).collect();
+    }
+
+    // adds up the octaves, with the sample fn given the frequency and offset of each octave
+    fn layer(&self, sample: impl Fn(f32, Vector3) -> f32) -> f32 {
+        let (mut total, mut amplitude, mut frequency, mut max_total) = (0.0, 1.0, 1.0, 0.0);
+        // the ridged multifractal weights each octave by the last
+        let mut weight = 1.0;
+
+        for offset in &self.offsets {
+            let val = sample(frequency, *offset);
+            total += amplitude * match self.fractal_type {
+                FractalType::Fbm => val,
+                FractalType::Billow => val.abs() * 2.0 - 1.0,
+                FractalType::Turbulence => val.abs(),
+                FractalType::RidgedMulti => {
+                    let signal = (1.0 - val.abs()).powi(2) * weight;
+                    weight = (signal * 2.0).clamp(0.0, 1.0);
+                    signal
+                },
+            };
+            max_total += amplitude;
+            amplitude *= self.gain;
+            frequency *= self.lacunarity;
+        }
+
+        let total = if max_total == 0.0 {0.0} else {total / max_total};
+        match self.fractal_type {
+            FractalType::RidgedMulti => total * 2.0 - 1.0,
+            _ => total,
+        }
+    }
+}
+
+
+impl<N: NoiseFn> NoiseFn for Fractal<N> {
+    fn sample2d(&self, x: f32, y: f32) -> f32 {
+        self.layer(|frequency, offset| self.base.sample2d(x * frequency + offset.x, y * frequency + offset.y))
+    }
+
+    fn sample3d(&self, x: f32, y: f32, z: f32) -> f32 {
+        self.layer(|frequency, offset| self.base.sample3d(x * frequency + offset.x, y * frequency + offset.y, z * frequency + offset.z))
+    }
+}
@@ -1,16 +1,25 @@
-use super::{Vector3, lerp, interp_by_fn};
+use super::{Vector2, Vector3, Vector3Int, Vector4, ChaChaRng, SeedableRng, Rng, lerp, interp_by_fn};
 
 mod simplex;
 pub use simplex::*;
 mod voronoi;
+pub use voronoi::{WorleyNoise, WorleySample2d, WorleySample3d, DistanceMetric};
+mod noise_fn;
+pub use noise_fn::NoiseFn;
+mod combinators;
+pub use combinators::{Constant, FnNoise, AddNoise, MultiplyNoise, ScaleBias, ClampNoise, Curve, Blend, Select, DomainTurbulence, Cache};
+mod fractal;
+pub use fractal::{Fractal, FractalType};
 
 
+/// lerps from the low noise to the high noise by the selector noise, the noises can be any closures
+/// so seeded noise can be captured, see `Blend` for the `NoiseFn` version
 pub fn selector_noise_2d(
     x: f32,
     y: f32,
-    low_noise: fn(f32, f32) -> f32,
-    high_noise: fn(f32, f32) -> f32,
-    selector_noise: fn(f32, f32) -> f32,
+    low_noise: impl Fn(f32, f32) -> f32,
+    high_noise: impl Fn(f32, f32) -> f32,
+    selector_noise: impl Fn(f32, f32) -> f32,
     interp_fn: Option<fn(f32) -> f32>,
 ) -> f32 {
     if let Some(func) = interp_fn {
@@ -20,13 +29,14 @@ pub fn selector_noise_2d(
     }
 }
 
+/// the 3d version of `selector_noise_2d`
 pub fn selector_noise_3d(
     x: f32,
     y: f32,
     z: f32,
-    low_noise: fn(f32, f32, f32) -> f32,
-    high_noise: fn(f32, f32, f32) -> f32,
-    selector_noise: fn(f32, f32, f32) -> f32,
+    low_noise: impl Fn(f32, f32, f32) -> f32,
+    high_noise: impl Fn(f32, f32, f32) -> f32,
+    selector_noise: impl Fn(f32, f32, f32) -> f32,
     interp_fn: Option<fn(f32) -> f32>,
 ) -> f32 {
     if let Some(func) = interp_fn {
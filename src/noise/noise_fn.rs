@@ -0,0 +1,124 @@
+use super::{SimplexNoise, WorleyNoise};
+use super::combinators::*;
+
+
+/// something that can be sampled as 2d and 3d noise, so it can be layered and combined with other noise
+///
+/// the provided methods wrap the noise in a combinator so they can be chained into a graph,
+/// e.g. `SimplexNoise::new(1).scale_bias(0.5, 0.5).multiply(WorleyNoise::new(1)).cached()`
+pub trait NoiseFn {
+    fn sample2d(&self, x: f32, y: f32) -> f32;
+    fn sample3d(&self, x: f32, y: f32, z: f32) -> f32;
+
+    fn add<B: NoiseFn>(self, other: B) -> AddNoise<Self, B>
+    where
+        Self: Sized,
+    {
+        AddNoise {a: self, b: other}
+    }
+
+    fn multiply<B: NoiseFn>(self, other: B) -> MultiplyNoise<Self, B>
+    where
+        Self: Sized,
+    {
+        MultiplyNoise {a: self, b: other}
+    }
+
+    fn scale_bias(self, scale: f32, bias: f32) -> ScaleBias<Self>
+    where
+        Self: Sized,
+    {
+        ScaleBias {source: self, scale, bias}
+    }
+
+    fn clamp(self, min: f32, max: f32) -> ClampNoise<Self>
+    where
+        Self: Sized,
+    {
+        ClampNoise {source: self, min, max}
+    }
+
+    /// see `Curve`, the points are each an input value and the output it maps to
+    fn curve(self, points: Vec<(f32, f32)>) -> Curve<Self>
+    where
+        Self: Sized,
+    {
+        Curve::new(self, points)
+    }
+
+    /// this noise where the control is -1 and the high noise where it is 1, lerped in between
+    fn blend<H: NoiseFn, C: NoiseFn>(self, high: H, control: C) -> Blend<Self, H, C>
+    where
+        Self: Sized,
+    {
+        Blend {low: self, high, control, interp_fn: None}
+    }
+
+    /// this noise where the control is below the threshold and the high noise where it is above, with no falloff
+    fn select<H: NoiseFn, C: NoiseFn>(self, high: H, control: C, threshold: f32) -> Select<Self, H, C>
+    where
+        Self: Sized,
+    {
+        Select {low: self, high, control, threshold, falloff: 0.0, interp_fn: None}
+    }
+
+    fn domain_turbulence<D: NoiseFn>(self, displacement: D, power: f32) -> DomainTurbulence<Self, D>
+    where
+        Self: Sized,
+    {
+        DomainTurbulence {source: self, displacement, power}
+    }
+
+    fn cached(self) -> Cache<Self>
+    where
+        Self: Sized,
+    {
+        Cache::new(self)
+    }
+}
+
+
+impl<N: NoiseFn + ?Sized> NoiseFn for &N {
+    fn sample2d(&self, x: f32, y: f32) -> f32 {
+        (**self).sample2d(x, y)
+    }
+
+    fn sample3d(&self, x: f32, y: f32, z: f32) -> f32 {
+        (**self).sample3d(x, y, z)
+    }
+}
+
+impl<N: NoiseFn + ?Sized> NoiseFn for Box<N> {
+    fn sample2d(&self, x: f32, y: f32) -> f32 {
+        (**self).sample2d(x, y)
+    }
+
+    fn sample3d(&self, x: f32, y: f32, z: f32) -> f32 {
+        (**self).sample3d(x, y, z)
+    }
+}
+
+
+impl NoiseFn for SimplexNoise {
+    fn sample2d(&self, x: f32, y: f32) -> f32 {
+        SimplexNoise::sample2d(self, x, y)
+    }
+
+    fn sample3d(&self, x: f32, y: f32, z: f32) -> f32 {
+        SimplexNoise::sample3d(self, x, y, z)
+    }
+}
+
+/// samples as the distance to the closest feature point, F1, remapped to -1..1 to match the other noise.
+/// 0 is -1 and 1 is the length of a cell's diagonal under the distance metric, as the closest point can't be further away than that
+impl NoiseFn for WorleyNoise {
+    fn sample2d(&self, x: f32, y: f32) -> f32 {
+        let f1 = WorleyNoise::sample2d(self, x, y).f1;
+        f1 / self.get_distance_metric().distance(&[1.0; 2]) * 2.0 - 1.0
+    }
+
+    fn sample3d(&self, x: f32, y: f32, z: f32) -> f32 {
+        let f1 = WorleyNoise::sample3d(self, x, y, z).f1;
+        f1 / self.get_distance_metric().distance(&[1.0; 3]) * 2.0 - 1.0
+    }
+}
@@ -1,4 +1,6 @@
-use super::Vector3;
+use super::{Vector3, Vector4, ChaChaRng, SeedableRng};
+use rand::seq::SliceRandom;
+use std::f32::consts::TAU;
 
 pub fn grad3(index: i32) -> Vector3{
 
@@ -19,40 +21,40 @@ pub fn grad3(index: i32) -> Vector3{
     }
 }
 
-// const GRAD4: [Vector4; 32] = [
-//     Vector4{x: 0.0, y: 1.0, z: 1.0, w: 1.0},
-//     Vector4{x: 0.0, y: 1.0, z: 1.0, w: -1.0},
-//     Vector4{x: 0.0, y: 1.0, z: -1.0, w: 1.0},
-//     Vector4{x: 0.0, y: 1.0, z: -1.0, w: -1.0},
-//     Vector4{x: 0.0, y: -1.0, z: 1.0, w: 1.0},
-//     Vector4{x: 0.0, y: -1.0, z: 1.0, w: -1.0},
-//     Vector4{x: 0.0, y: -1.0, z: -1.0, w: 1.0},
-//     Vector4{x: 0.0, y: -1.0, z: -1.0, w: -1.0},
-//     Vector4{x: 1.0, y: 0.0, z: 1.0, w: 1.0},
-//     Vector4{x: 1.0, y: 0.0, z: 1.0, w: -1.0},
-//     Vector4{x: 1.0, y: 0.0, z: -1.0, w: 1.0},
-//     Vector4{x: 1.0, y: 0.0, z: -1.0, w: -1.0},
-//     Vector4{x: -1.0, y: 0.0, z: 1.0, w: 1.0},
-//     Vector4{x: -1.0, y: 0.0, z: 1.0, w: -1.0},
-//     Vector4{x: -1.0, y: 0.0, z: -1.0, w: 1.0},
-//     Vector4{x: -1.0, y: 0.0, z: -1.0, w: -1.0},
-//     Vector4{x: 1.0, y: 1.0, z: 0.0, w: 1.0},
-//     Vector4{x: 1.0, y: 1.0, z: 0.0, w: -1.0},
-//     Vector4{x: 1.0, y: -1.0, z: 0.0, w: 1.0},
-//     Vector4{x: 1.0, y: -1.0, z: 0.0, w: -1.0},
-//     Vector4{x: -1.0, y: 1.0, z: 0.0, w: 1.0},
-//     Vector4{x: -1.0, y: 1.0, z: 0.0, w: -1.0},
-//     Vector4{x: -1.0, y: -1.0, z: 0.0, w: 1.0},
-//     Vector4{x: -1.0, y: -1.0, z: 0.0, w: -1.0},
-//     Vector4{x: 1.0, y: 1.0, z: 1.0, w: 0.0},
-//     Vector4{x: 1.0, y: 1.0, z: -1.0, w: 0.0},
-//     Vector4{x: 1.0, y: -1.0, z: 1.0, w: 0.0},
-//     Vector4{x: 1.0, y: -1.0, z: -1.0, w: 0.0},
-//     Vector4{x: -1.0, y: 1.0, z: 1.0, w: 0.0},
-//     Vector4{x: -1.0, y: 1.0, z: -1.0, w: 0.0},
-//     Vector4{x: -1.0, y: -1.0, z: 1.0, w: 0.0},
-//     Vector4{x: -1.0, y: -1.0, z: -1.0, w: 0.0},
-// ];
+const GRAD4: [Vector4; 32] = [
+    Vector4{x: 0.0, y: 1.0, z: 1.0, w: 1.0},
+    Vector4{x: 0.0, y: 1.0, z: 1.0, w: -1.0},
+    Vector4{x: 0.0, y: 1.0, z: -1.0, w: 1.0},
+    Vector4{x: 0.0, y: 1.0, z: -1.0, w: -1.0},
+    Vector4{x: 0.0, y: -1.0, z: 1.0, w: 1.0},
+    Vector4{x: 0.0, y: -1.0, z: 1.0, w: -1.0},
+    Vector4{x: 0.0, y: -1.0, z: -1.0, w: 1.0},
+    Vector4{x: 0.0, y: -1.0, z: -1.0, w: -1.0},
+    Vector4{x: 1.0, y: 0.0, z: 1.0, w: 1.0},
+    Vector4{x: 1.0, y: 0.0, z: 1.0, w: -1.0},
+    Vector4{x: 1.0, y: 0.0, z: -1.0, w: 1.0},
+    Vector4{x: 1.0, y: 0.0, z: -1.0, w: -1.0},
+    Vector4{x: -1.0, y: 0.0, z: 1.0, w: 1.0},
+    Vector4{x: -1.0, y: 0.0, z: 1.0, w: -1.0},
+    Vector4{x: -1.0, y: 0.0, z: -1.0, w: 1.0},
+    Vector4{x: -1.0, y: 0.0, z: -1.0, w: -1.0},
+    Vector4{x: 1.0, y: 1.0, z: 0.0, w: 1.0},
+    Vector4{x: 1.0, y: 1.0, z: 0.0, w: -1.0},
+    Vector4{x: 1.0, y: -1.0, z: 0.0, w: 1.0},
+    Vector4{x: 1.0, y: -1.0, z: 0.0, w: -1.0},
+    Vector4{x: -1.0, y: 1.0, z: 0.0, w: 1.0},
+    Vector4{x: -1.0, y: 1.0, z: 0.0, w: -1.0},
+    Vector4{x: -1.0, y: -1.0, z: 0.0, w: 1.0},
+    Vector4{x: -1.0, y: -1.0, z: 0.0, w: -1.0},
+    Vector4{x: 1.0, y: 1.0, z: 1.0, w: 0.0},
+    Vector4{x: 1.0, y: 1.0, z: -1.0, w: 0.0},
+    Vector4{x: 1.0, y: -1.0, z: 1.0, w: 0.0},
+    Vector4{x: 1.0, y: -1.0, z: -1.0, w: 0.0},
+    Vector4{x: -1.0, y: 1.0, z: 1.0, w: 0.0},
+    Vector4{x: -1.0, y: 1.0, z: -1.0, w: 0.0},
+    Vector4{x: -1.0, y: -1.0, z: 1.0, w: 0.0},
+    Vector4{x: -1.0, y: -1.0, z: -1.0, w: 0.0},
+];
 
 const PERM: [i32; 512] = [
     151, 160, 137, 91, 90, 15,
@@ -105,12 +107,111 @@ fn unskew_val(dimension: u32) -> f32 {
 //     PERM[index]
 // }
 
-fn hash(hash: i32) -> i32 {
-    PERM[hash as usize]
+fn hash(perm: &[i32; 512], hash: i32) -> i32 {
+    perm[hash as usize]
 }
 
 /// 2d simplex noise function, takes in an x and y value and outputs a value between -1 and 1
+///
+/// this uses the default permutation table, the same as `SimplexNoise::default()`
 pub fn simplex2d(x: f32, y: f32) -> f32 {
+    simplex2d_with(&PERM, x, y)
+}
+
+/// 3d simplex noise function, takes in an x, y and z value and outputs a value between -1 and 1
+///
+/// this uses the default permutation table, the same as `SimplexNoise::default()`
+pub fn simplex3d(x: f32, y: f32, z: f32) -> f32 {
+    simplex3d_with(&PERM, x, y, z)
+}
+
+/// 4d simplex noise function, takes in an x, y, z and w value and outputs a value between -1 and 1
+///
+/// good for animating 3d noise with time as the 4th axis, or for looping 2d noise with `looping_simplex2d`
+pub fn simplex4d(x: f32, y: f32, z: f32, w: f32) -> f32 {
+    simplex4d_with(&PERM, x, y, z, w)
+}
+
+/// 2d simplex noise that repeats every `period_x` along x and every `period_y` along y, so it can be tiled without seams
+///
+/// the square is wrapped round a torus in 4d space and the noise is sampled from that,
+/// at the same scale as `simplex2d` would be
+pub fn looping_simplex2d(x: f32, y: f32, period_x: f32, period_y: f32) -> f32 {
+    let [x, y, z, w] = torus_point(x, y, period_x, period_y);
+    simplex4d(x, y, z, w)
+}
+
+// the point on a torus in 4d space, with each circle having the circumference of its period
+fn torus_point(x: f32, y: f32, period_x: f32, period_y: f32) -> [f32; 4] {
+    let (angle_x, angle_y) = (x / period_x * TAU, y / period_y * TAU);
+    let (radius_x, radius_y) = (period_x / TAU, period_y / TAU);
+    [angle_x.cos() * radius_x, angle_x.sin() * radius_x, angle_y.cos() * radius_y, angle_y.sin() * radius_y]
+}
+
+
+/// simplex noise with its own permutation table shuffled from a seed, so different seeds give different noise
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimplexNoise {
+    seed: Option<u64>,
+    perm: [i32; 512],
+}
+
+impl Default for SimplexNoise {
+    /// the same noise as the `simplex2d` and `simplex3d` functions
+    fn default() -> Self {
+        SimplexNoise {
+            seed: None,
+            perm: PERM
+        }
+    }
+}
+
+impl SimplexNoise {
+    pub fn new(seed: u64) -> Self {
+        let mut rng = ChaChaRng::seed_from_u64(seed);
+        let mut shuffled: Vec<i32> = (0..256).collect();
+        shuffled.shuffle(&mut rng);
+
+        // doubled up so the hashing can index past 255 without wrapping
+        let mut perm = [0; 512];
+        for (i, val) in perm.iter_mut().enumerate() {
+            *val = shuffled[i & 255];
+        }
+        SimplexNoise {
+            seed: Some(seed),
+            perm
+        }
+    }
+
+    /// the seed this was made with, None for the default table
+    pub fn get_seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    /// outputs a value between -1 and 1
+    pub fn sample2d(&self, x: f32, y: f32) -> f32 {
+        simplex2d_with(&self.perm, x, y)
+    }
+
+    /// outputs a value between -1 and 1
+    pub fn sample3d(&self, x: f32, y: f32, z: f32) -> f32 {
+        simplex3d_with(&self.perm, x, y, z)
+    }
+
+    /// outputs a value between -1 and 1
+    pub fn sample4d(&self, x: f32, y: f32, z: f32, w: f32) -> f32 {
+        simplex4d_with(&self.perm, x, y, z, w)
+    }
+
+    /// the seeded version of `looping_simplex2d`
+    pub fn sample_looping2d(&self, x: f32, y: f32, period_x: f32, period_y: f32) -> f32 {
+        let [x, y, z, w] = torus_point(x, y, period_x, period_y);
+        self.sample4d(x, y, z, w)
+    }
+}
+
+
+fn simplex2d_with(perm: &[i32; 512], x: f32, y: f32) -> f32 {
     let (n0, n1, n2): (f32, f32, f32);
 
     let skew = skew_val(2);
@@ -149,9 +250,9 @@ pub fn simplex2d(x: f32, y: f32) -> f32 {
     // get hashed gradient indices
     let ii = i & 255;
     let jj = j & 255;
-    let gi0 = hash(ii + hash(jj));
-    let gi1 = hash(ii + i1 + hash(jj + j1));
-    let gi2 = hash(ii + 1 + hash(jj + 1));
+    let gi0 = hash(perm, ii + hash(perm, jj));
+    let gi1 = hash(perm, ii + i1 + hash(perm, jj + j1));
+    let gi2 = hash(perm, ii + 1 + hash(perm, jj + 1));
 
 
     let mut t0 = 0.5 - x0 * x0 - y0 * y0;
@@ -181,8 +282,7 @@ pub fn simplex2d(x: f32, y: f32) -> f32 {
     45.23065 * (n0 + n1 + n2)
 }
 
-/// 3d simplex noise function, takes in an x, y and z value and outputs a value between -1 and 1
-pub fn simplex3d(x: f32, y: f32, z: f32) -> f32 {
+fn simplex3d_with(perm: &[i32; 512], x: f32, y: f32, z: f32) -> f32 {
     let (n0, n1, n2, n3): (f32, f32, f32, f32);
 
     let skew = skew_val(3);
@@ -236,10 +336,10 @@ pub fn simplex3d(x: f32, y: f32, z: f32) -> f32 {
     let ii = i & 255;
     let jj = j & 255;
     let kk = k & 255;
-    let gi0 = hash(ii + hash(jj + hash(kk)));
-    let gi1 = hash(ii + i1 + hash(jj + j1 + hash(kk + k1)));
-    let gi2 = hash(ii + i2 + hash(jj + j2 + hash(kk + k2)));
-    let gi3 = hash(ii + 1 + hash(jj + 1 + hash(kk + 1)));
+    let gi0 = hash(perm, ii + hash(perm, jj + hash(perm, kk)));
+    let gi1 = hash(perm, ii + i1 + hash(perm, jj + j1 + hash(perm, kk + k1)));
+    let gi2 = hash(perm, ii + i2 + hash(perm, jj + j2 + hash(perm, kk + k2)));
+    let gi3 = hash(perm, ii + 1 + hash(perm, jj + 1 + hash(perm, kk + 1)));
 
     // calculate corner contribution
     let mut t0 = 0.5 - x0 * x0 - y0 * y0 - z0 * z0;
@@ -272,4 +372,52 @@ pub fn simplex3d(x: f32, y: f32, z: f32) -> f32 {
 
 
     32.0 * (n0 + n1 + n2 + n3)
-}
\ No newline at end of file
+}
+
+
+fn simplex4d_with(perm: &[i32; 512], x: f32, y: f32, z: f32, w: f32) -> f32 {
+    let skew = skew_val(4);
+    let unskew = unskew_val(4);
+
+    // skew for simplex cell coords
+    let s = (x + y + z + w) * skew;
+    let i = (x + s).floor() as i32;
+    let j = (y + s).floor() as i32;
+    let k = (z + s).floor() as i32;
+    let l = (w + s).floor() as i32;
+
+    // unskew for 4d space coords
+    let t = (i + j + k + l) as f32 * unskew;
+    let corner = Vector4::new(x - (i as f32 - t), y - (j as f32 - t), z - (k as f32 - t), w - (l as f32 - t));
+
+    // rank each axis by how far into the cell it is, the simplex goes through the axes from largest to smallest
+    let offsets = [corner.x, corner.y, corner.z, corner.w];
+    let mut rank = [0; 4];
+    for a in 0..4 {
+        for b in (a + 1)..4 {
+            if offsets[a] > offsets[b] {rank[a] += 1} else {rank[b] += 1}
+        }
+    }
+    // the n-th corner steps along every axis ranked at least 4 - n
+    let step = |n: i32| rank.map(|r| if r >= 4 - n {1} else {0});
+
+    // hashed indices
+    let (ii, jj, kk, ll) = (i & 255, j & 255, k & 255, l & 255);
+    let gradient_index = |[di, dj, dk, dl]: [i32; 4]| {
+        hash(perm, ii + di + hash(perm, jj + dj + hash(perm, kk + dk + hash(perm, ll + dl)))) % 32
+    };
+
+    // calculate corner contributions
+    let mut total = 0.0;
+    for n in 0..5 {
+        let offset = step(n);
+        let position = corner - Vector4::new(offset[0] as f32, offset[1] as f32, offset[2] as f32, offset[3] as f32) + Vector4::ONE * (n as f32 * unskew);
+
+        let mut falloff = 0.6 - position.dot(position);
+        if falloff < 0.0 {continue;}
+        falloff *= falloff;
+        total += falloff * falloff * GRAD4[gradient_index(offset) as usize].dot(position);
+    }
+
+    27.0 * total
+}
@@ -1,30 +1,242 @@
+use super::{Vector2, Vector3, Vector3Int};
 
 
+/// how the distance from a sample to a feature point is measured, which changes the shape of the cells
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DistanceMetric {
+    /// straight line distance, gives the usual rounded cells
+    #[default]
+    Euclidean,
+    /// the sum of the distance along each axis, gives diamond shaped cells
+    Manhattan,
+    /// the largest distance along any axis, gives square cells
+    Chebyshev,
+}
 
+impl DistanceMetric {
+    /// the distance for a point offset by the given amount along each axis
+    pub fn distance(&self, offset: &[f32]) -> f32 {
+        match self {
+            DistanceMetric::Euclidean => offset.iter().map(|val| val * val).sum::<f32>().sqrt(),
+            DistanceMetric::Manhattan => offset.iter().map(|val| val.abs()).sum(),
+            DistanceMetric::Chebyshev => offset.iter().fold(0.0, |max, val| val.abs().max(max)),
+        }
+    }
+}
 
-// pub trait VoronoiNoiseNDimensional {
-//     type DimensionalVector;
 
-//     fn new() -> Self
-//     where:
-//         Self: Sized,
-//     {
+/// the result of sampling 2d worley noise
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WorleySample2d {
+    /// the distance to the closest feature point
+    pub f1: f32,
+    /// the distance to the second closest feature point
+    pub f2: f32,
+    /// the grid cell the closest feature point belongs to
+    pub cell: [i32; 2],
+    /// a random id for the cell, the same for every sample in it
+    pub cell_id: u64,
+    /// the position of the closest feature point
+    pub feature_point: Vector2,
+}
 
-//     }
+impl WorleySample2d {
+    /// zero on the edges between cells, good for cracks and stone textures
+    pub fn f2_minus_f1(&self) -> f32 {
+        self.f2 - self.f1
+    }
 
-//     fn get_closest(position: Self::DimensionalVector) {
+    /// the cell id as a value between 0 and 1, good for picking a biome or colour per cell
+    pub fn cell_value(&self) -> f32 {
+        unit_float(self.cell_id)
+    }
+}
 
-//     }
-// }
 
+/// the result of sampling 3d worley noise
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WorleySample3d {
+    /// the distance to the closest feature point
+    pub f1: f32,
+    /// the distance to the second closest feature point
+    pub f2: f32,
+    /// the grid cell the closest feature point belongs to
+    pub cell: Vector3Int,
+    /// a random id for the cell, the same for every sample in it
+    pub cell_id: u64,
+    /// the position of the closest feature point
+    pub feature_point: Vector3,
+}
 
-// pub struct VoronoiNoise {
+impl WorleySample3d {
+    /// zero on the edges between cells, good for cracks and stone textures
+    pub fn f2_minus_f1(&self) -> f32 {
+        self.f2 - self.f1
+    }
 
-// }
+    /// the cell id as a value between 0 and 1, good for picking a biome or colour per cell
+    pub fn cell_value(&self) -> f32 {
+        unit_float(self.cell_id)
+    }
+}
 
 
-// impl VoronoiNoise {
-//     pub fn new() {
+/// worley (cellular) noise, each unit grid cell has one feature point in it placed from the seed
+/// and samples measure the distance to the closest ones
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WorleyNoise {
+    seed: u64,
+    metric: DistanceMetric,
+    jitter: f32,
+}
 
-//     }
-// }
\ No newline at end of file
+impl Default for WorleyNoise {
+    fn default() -> Self {
+        WorleyNoise::new(0)
+    }
+}
+
+impl WorleyNoise {
+    /// euclidean distance with full jitter
+    pub fn new(seed: u64) -> Self {
+        WorleyNoise {
+            seed,
+            metric: DistanceMetric::Euclidean,
+            jitter: 1.0,
+        }
+    }
+
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn get_distance_metric(&self) -> DistanceMetric {
+        self.metric
+    }
+
+    pub fn set_distance_metric(&mut self, metric: DistanceMetric) {
+        self.metric = metric;
+    }
+
+    pub fn get_jitter(&self) -> f32 {
+        self.jitter
+    }
+
+    /// how far the feature points can move from the centre of their cells, clamped between 0 and 1
+    ///
+    /// 0 puts every point in the centre for a regular grid, 1 lets them go anywhere in their cell
+    pub fn set_jitter(&mut self, jitter: f32) {
+        self.jitter = jitter.clamp(0.0, 1.0);
+    }
+
+    pub fn sample2d(&self, x: f32, y: f32) -> WorleySample2d {
+        let closest = self.closest_features([x, y]);
+        WorleySample2d {
+            f1: closest.f1,
+            f2: closest.f2,
+            cell: closest.cell,
+            cell_id: closest.cell_id,
+            feature_point: closest.feature_point.into(),
+        }
+    }
+
+    pub fn sample3d(&self, x: f32, y: f32, z: f32) -> WorleySample3d {
+        let closest = self.closest_features([x, y, z]);
+        WorleySample3d {
+            f1: closest.f1,
+            f2: closest.f2,
+            cell: closest.cell.into(),
+            cell_id: closest.cell_id,
+            feature_point: closest.feature_point.into(),
+        }
+    }
+
+    /// the feature point in a cell and the cell's id
+    fn feature_point<const N: usize>(&self, cell: [i32; N]) -> ([f32; N], u64) {
+        let cell_id = cell.iter().fold(self.seed, |hash, val| split_mix(hash ^ *val as u32 as u64));
+        let mut hash = cell_id;
+        let point = cell.map(|val| {
+            hash = split_mix(hash);
+            val as f32 + 0.5 + (unit_float(hash) - 0.5) * self.jitter
+        });
+        (point, cell_id)
+    }
+
+    /// searches outwards a ring of cells at a time, until no cell further out could be closer than the second closest point
+    fn closest_features<const N: usize>(&self, point: [f32; N]) -> ClosestFeatures<N> {
+        let home = point.map(|val| val.floor() as i32);
+        let fraction: [f32; N] = std::array::from_fn(|i| point[i] - home[i] as f32);
+        // every cell in a ring is at least this much further along some axis than the ring before
+        let edge_gap = fraction.iter().fold(1.0, |min: f32, val| min.min(*val).min(1.0 - val));
+
+        let mut closest = ClosestFeatures {
+            f1: f32::MAX,
+            f2: f32::MAX,
+            cell: home,
+            cell_id: 0,
+            feature_point: point,
+        };
+
+        for ring in 0i32.. {
+            if ring > 0 && (ring - 1) as f32 + edge_gap >= closest.f2 {break;}
+
+            let side = 2 * ring + 1;
+            for index in 0..side.pow(N as u32) {
+                let mut remaining = index;
+                let offset: [i32; N] = std::array::from_fn(|_| {
+                    let val = remaining % side - ring;
+                    remaining /= side;
+                    val
+                });
+                if offset.iter().all(|val| val.abs() != ring) {continue;}
+
+                // skip the cell if even its nearest edge is too far away
+                let gap: [f32; N] = std::array::from_fn(|i| match offset[i] {
+                    0 => 0.0,
+                    val if val > 0 => val as f32 - fraction[i],
+                    val => fraction[i] - val as f32 - 1.0,
+                });
+                if self.metric.distance(&gap) >= closest.f2 {continue;}
+
+                let cell: [i32; N] = std::array::from_fn(|i| home[i] + offset[i]);
+                let (feature_point, cell_id) = self.feature_point(cell);
+                let difference: [f32; N] = std::array::from_fn(|i| feature_point[i] - point[i]);
+                let dist = self.metric.distance(&difference);
+
+                if dist < closest.f1 {
+                    closest.f2 = closest.f1;
+                    closest.f1 = dist;
+                    closest.cell = cell;
+                    closest.cell_id = cell_id;
+                    closest.feature_point = feature_point;
+                } else if dist < closest.f2 {
+                    closest.f2 = dist;
+                }
+            }
+        }
+        closest
+    }
+}
+
+
+struct ClosestFeatures<const N: usize> {
+    f1: f32,
+    f2: f32,
+    cell: [i32; N],
+    cell_id: u64,
+    feature_point: [f32; N],
+}
+
+
+// splitmix64, a fast hash that spreads every bit of the input over the output
+fn split_mix(value: u64) -> u64 {
+    let mut value = value.wrapping_add(0x9E3779B97F4A7C15);
+    value = (value ^ (value >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    value = (value ^ (value >> 27)).wrapping_mul(0x94D049BB133111EB);
+    value ^ (value >> 31)
+}
+
+// the top 24 bits of the hash as a value between 0 and 1, not including 1
+fn unit_float(hash: u64) -> f32 {
+    (hash >> 40) as f32 / (1u64 << 24) as f32
+}
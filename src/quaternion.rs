@@ -0,0 +1,389 @@
+use serde::{Deserialize, Serialize};
+
+use super::{Vector3, Vector4, Matrix3, Matrix4};
+use std::fmt::Display;
+use std::ops::*;
+
+
+/// A rotation quaternion, stored as a vector part (x, y, z) and a scalar part (w)
+///
+/// Rotations follow the same conventions as the Matrix3 rotation constructors, so `Quaternion::from_angle_and_axis(a, axis)` rotates vectors the same way as `Matrix3::from_angle_and_axis(a, axis)`
+///
+/// Multiplying two quaternions composes them so that `a * b` applies `b` first and then `a`, the same as matrix multiplication
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Deserialize, Serialize)]
+pub struct Quaternion {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub w: f32,
+}
+
+
+impl Quaternion {
+    pub const IDENTITY: Quaternion = Quaternion{x: 0.0, y: 0.0, z: 0.0, w: 1.0};
+    pub const ZERO: Quaternion = Quaternion{x: 0.0, y: 0.0, z: 0.0, w: 0.0};
+    pub const EPSILON: Quaternion = Quaternion{x: f32::EPSILON, y: f32::EPSILON, z: f32::EPSILON, w: f32::EPSILON};
+
+    pub const fn new(x: f32, y: f32, z: f32, w: f32) -> Self {
+        Quaternion {
+            x,
+            y,
+            z,
+            w,
+        }
+    }
+
+    /// creates a quaternion from a vector part and a scalar part
+    pub fn from_parts(vector: impl Into<Vector3>, scalar: f32) -> Self {
+        let vector: Vector3 = vector.into();
+        Quaternion::new(vector.x, vector.y, vector.z, scalar)
+    }
+
+    /// creates a rotation quaternion for anticlockwise rotation of angle around the specified axis
+    pub fn from_angle_and_axis(angle: f32, axis: impl Into<Vector3>) -> Self {
+        let axis: Vector3 = axis.into();
+        if angle == 0.0 || axis.sqr_magnitude() == 0.0 {return Quaternion::IDENTITY;}
+        let half = angle / 2.0;
+        Quaternion::from_parts(axis.normalised() * half.sin(), half.cos())
+    }
+
+    /// creates a rotation quaternion for anticlockwise angle around x axis
+    pub fn from_angle_x(angle: f32) -> Self {
+        Quaternion::from_angle_and_axis(angle, Vector3::X)
+    }
+
+    /// creates a rotation quaternion for anticlockwise angle around y axis
+    pub fn from_angle_y(angle: f32) -> Self {
+        Quaternion::from_angle_and_axis(angle, Vector3::Y)
+    }
+
+    /// creates a rotation quaternion for anticlockwise angle around z axis
+    pub fn from_angle_z(angle: f32) -> Self {
+        Quaternion::from_angle_and_axis(angle, Vector3::Z)
+    }
+
+    /// creates a rotation quaternion from euler angles, matching `Matrix3::from_euler_angles`
+    ///
+    /// the rotations are applied around x, then y, then z
+    pub fn from_euler_angles(angles: impl Into<Vector3>) -> Self {
+        let angles: Vector3 = angles.into();
+        let (sx, cx) = (angles.x / 2.0).sin_cos();
+        let (sy, cy) = (angles.y / 2.0).sin_cos();
+        let (sz, cz) = (angles.z / 2.0).sin_cos();
+
+        Quaternion::new(
+            sx * cy * cz - cx * sy * sz,
+            cx * sy * cz + sx * cy * sz,
+            cx * cy * sz - sx * sy * cz,
+            cx * cy * cz + sx * sy * sz
+        )
+    }
+
+    /// calculates the euler angles for this rotation, in the form used by `Matrix3::from_euler_angles`
+    pub fn euler_angles(&self) -> Vector3 {
+        Matrix3::euler_angles_from(self.to_matrix3())
+    }
+
+    /// returns the axis and anticlockwise angle of the rotation, the axis is x if there is no rotation
+    pub fn angle_and_axis(&self) -> (f32, Vector3) {
+        let q = self.normalised();
+        let sin_half = q.vector().magnitude();
+        if sin_half <= f32::EPSILON {
+            return (0.0, Vector3::X);
+        }
+        (2.0 * sin_half.atan2(q.w), q.vector() / sin_half)
+    }
+
+    /// creates the rotation quaternion equivalent to a pure rotation matrix
+    ///
+    /// this uses Shepperd's method, choosing the largest diagonal term to avoid dividing by small numbers
+    pub fn from_matrix3(rot: Matrix3) -> Self {
+        let trace = rot.x.x + rot.y.y + rot.z.z;
+
+        let q = if trace > 0.0 {
+            let s = (trace + 1.0).sqrt() * 2.0;
+            Quaternion::new(
+                (rot.z.y - rot.y.z) / s,
+                (rot.x.z - rot.z.x) / s,
+                (rot.y.x - rot.x.y) / s,
+                0.25 * s
+            )
+        } else if rot.x.x > rot.y.y && rot.x.x > rot.z.z {
+            let s = (1.0 + rot.x.x - rot.y.y - rot.z.z).sqrt() * 2.0;
+            Quaternion::new(
+                0.25 * s,
+                (rot.x.y + rot.y.x) / s,
+                (rot.x.z + rot.z.x) / s,
+                (rot.z.y - rot.y.z) / s
+            )
+        } else if rot.y.y > rot.z.z {
+            let s = (1.0 + rot.y.y - rot.x.x - rot.z.z).sqrt() * 2.0;
+            Quaternion::new(
+                (rot.x.y + rot.y.x) / s,
+                0.25 * s,
+                (rot.y.z + rot.z.y) / s,
+                (rot.x.z - rot.z.x) / s
+            )
+        } else {
+            let s = (1.0 + rot.z.z - rot.x.x - rot.y.y).sqrt() * 2.0;
+            Quaternion::new(
+                (rot.x.z + rot.z.x) / s,
+                (rot.y.z + rot.z.y) / s,
+                0.25 * s,
+                (rot.y.x - rot.x.y) / s
+            )
+        };
+
+        q.normalised()
+    }
+
+    /// creates the rotation matrix for this quaternion, the quaternion is assumed to be normalised
+    pub fn to_matrix3(&self) -> Matrix3 {
+        let (x, y, z, w) = (self.x, self.y, self.z, self.w);
+        Matrix3::new(
+            1.0 - 2.0 * (y * y + z * z), 2.0 * (x * y - z * w), 2.0 * (x * z + y * w),
+            2.0 * (x * y + z * w), 1.0 - 2.0 * (x * x + z * z), 2.0 * (y * z - x * w),
+            2.0 * (x * z - y * w), 2.0 * (y * z + x * w), 1.0 - 2.0 * (x * x + y * y)
+        )
+    }
+
+    /// creates a rotation transform matrix, the same as `Matrix4::from_rotation(self.to_matrix3())`
+    pub fn to_matrix4(&self) -> Matrix4 {
+        Matrix4::from_rotation(self.to_matrix3())
+    }
+
+    /// creates a rotation quaternion from the rotation part of a transform matrix, any translation is ignored
+    pub fn from_matrix4(transform: Matrix4) -> Self {
+        Quaternion::from_matrix3(transform.truncate().transposed())
+    }
+
+    pub fn vector(&self) -> Vector3 {
+        Vector3::new(self.x, self.y, self.z)
+    }
+
+    pub fn dot(&self, rhs: Quaternion) -> f32 {
+        self.x * rhs.x + self.y * rhs.y + self.z * rhs.z + self.w * rhs.w
+    }
+
+    pub fn sqr_magnitude(&self) -> f32 {
+        self.dot(*self)
+    }
+
+    pub fn magnitude(&self) -> f32 {
+        self.sqr_magnitude().sqrt()
+    }
+
+    pub fn normalise(&mut self) {
+        *self = self.normalised();
+    }
+
+    pub fn normalised(&self) -> Quaternion {
+        *self / self.magnitude()
+    }
+
+    pub fn conjugate(&self) -> Quaternion {
+        Quaternion::new(-self.x, -self.y, -self.z, self.w)
+    }
+
+    /// returns the inverse rotation, for normalised quaternions this is the same as the conjugate
+    pub fn inverse(&self) -> Quaternion {
+        self.conjugate() / self.sqr_magnitude()
+    }
+
+    /// rotates the given vector by this quaternion, the quaternion is assumed to be normalised
+    pub fn rotate_vector(&self, vector: impl Into<Vector3>) -> Vector3 {
+        let vector: Vector3 = vector.into();
+        let u = self.vector();
+        let t = u.cross(vector) * 2.0;
+        vector + t * self.w + u.cross(t)
+    }
+
+    /// returns the angle of the rotation needed to get from this quaternion to the other
+    pub fn angle_to(&self, other: Quaternion) -> f32 {
+        let dot = self.normalised().dot(other.normalised()).abs().min(1.0);
+        2.0 * dot.acos()
+    }
+
+    /// normalised linear interpolation between two rotations, taking the shortest path
+    /// - The position value is clamped between 0 and 1
+    pub fn nlerp(&self, other: Quaternion, position: f32) -> Quaternion {
+        let pos = position.clamp(0.0, 1.0);
+        let other = if self.dot(other) < 0.0 {-other} else {other};
+        (*self * (1.0 - pos) + other * pos).normalised()
+    }
+
+    /// spherical linear interpolation between two rotations, taking the shortest path at a constant angular speed
+    /// - The position value is clamped between 0 and 1
+    pub fn slerp(&self, other: Quaternion, position: f32) -> Quaternion {
+        let pos = position.clamp(0.0, 1.0);
+        let mut cos_theta = self.dot(other);
+        let other = if cos_theta < 0.0 {
+            cos_theta = -cos_theta;
+            -other
+        } else {other};
+
+        // close rotations can't be divided by sin theta accurately so fall back to nlerp
+        if cos_theta > 1.0 - 1e-4 {
+            return (*self * (1.0 - pos) + other * pos).normalised();
+        }
+
+        let theta = cos_theta.acos();
+        let sin_theta = theta.sin();
+        let a = ((1.0 - pos) * theta).sin() / sin_theta;
+        let b = (pos * theta).sin() / sin_theta;
+        *self * a + other * b
+    }
+}
+
+impl Default for Quaternion {
+    fn default() -> Self {
+        Quaternion::IDENTITY
+    }
+}
+
+//////////////////////////////////////////////////////////////////
+///////////////////////////////// from and into
+//////////////////////////////////////////////////////////////////
+
+impl From<Quaternion> for [f32; 4] {
+    fn from(value: Quaternion) -> Self {
+        [value.x, value.y, value.z, value.w]
+    }
+}
+
+impl From<[f32; 4]> for Quaternion {
+    fn from(value: [f32; 4]) -> Self {
+        Quaternion::new(value[0], value[1], value[2], value[3])
+    }
+}
+
+impl From<Vector4> for Quaternion {
+    fn from(value: Vector4) -> Self {
+        Quaternion::new(value.x, value.y, value.z, value.w)
+    }
+}
+
+impl From<Quaternion> for Vector4 {
+    fn from(value: Quaternion) -> Self {
+        Vector4::new(value.x, value.y, value.z, value.w)
+    }
+}
+
+impl From<Matrix3> for Quaternion {
+    fn from(value: Matrix3) -> Self {
+        Quaternion::from_matrix3(value)
+    }
+}
+
+impl From<Quaternion> for Matrix3 {
+    fn from(value: Quaternion) -> Self {
+        value.to_matrix3()
+    }
+}
+
+impl From<Matrix4> for Quaternion {
+    fn from(value: Matrix4) -> Self {
+        Quaternion::from_matrix4(value)
+    }
+}
+
+impl From<Quaternion> for Matrix4 {
+    fn from(value: Quaternion) -> Self {
+        value.to_matrix4()
+    }
+}
+
+//////////////////////////////////////////////////////////////////
+///////////////////////////////// arithmetic operations
+//////////////////////////////////////////////////////////////////
+
+impl Mul for Quaternion {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self {
+            x: self.w * rhs.x + self.x * rhs.w + self.y * rhs.z - self.z * rhs.y,
+            y: self.w * rhs.y - self.x * rhs.z + self.y * rhs.w + self.z * rhs.x,
+            z: self.w * rhs.z + self.x * rhs.y - self.y * rhs.x + self.z * rhs.w,
+            w: self.w * rhs.w - self.x * rhs.x - self.y * rhs.y - self.z * rhs.z,
+        }
+    }
+}
+
+impl MulAssign for Quaternion {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl Mul<Vector3> for Quaternion {
+    type Output = Vector3;
+    fn mul(self, rhs: Vector3) -> Self::Output {
+        self.rotate_vector(rhs)
+    }
+}
+
+impl Mul<f32> for Quaternion {
+    type Output = Self;
+    fn mul(self, rhs: f32) -> Self::Output {
+        Self {
+            x: self.x * rhs,
+            y: self.y * rhs,
+            z: self.z * rhs,
+            w: self.w * rhs,
+        }
+    }
+}
+
+impl Div<f32> for Quaternion {
+    type Output = Self;
+    fn div(self, rhs: f32) -> Self::Output {
+        Self {
+            x: self.x / rhs,
+            y: self.y / rhs,
+            z: self.z / rhs,
+            w: self.w / rhs,
+        }
+    }
+}
+
+impl Add for Quaternion {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+            z: self.z + rhs.z,
+            w: self.w + rhs.w,
+        }
+    }
+}
+
+impl Sub for Quaternion {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+            z: self.z - rhs.z,
+            w: self.w - rhs.w,
+        }
+    }
+}
+
+impl Neg for Quaternion {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Self {
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+            w: -self.w,
+        }
+    }
+}
+
+impl Display for Quaternion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}, {}, {}, {}]", self.x, self.y, self.z, self.w)
+    }
+}
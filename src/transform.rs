@@ -0,0 +1,377 @@
+use serde::{Deserialize, Serialize};
+
+use super::{Vector3, Matrix3, Matrix4, Quaternion};
+use std::ops::Mul;
+
+
+/// A position, rotation and scale, applied to points in the order scale, rotate, translate
+///
+/// The matrix for a transform is laid out the same as the other Matrix4 transforms, so it can be sent to the gpu as is
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Transform {
+    pub position: Vector3,
+    pub rotation: Quaternion,
+    pub scale: Vector3,
+}
+
+impl Transform {
+    pub const IDENTITY: Transform = Transform {
+        position: Vector3::ZERO,
+        rotation: Quaternion::IDENTITY,
+        scale: Vector3::ONE,
+    };
+
+    pub fn new(position: impl Into<Vector3>, rotation: Quaternion, scale: impl Into<Vector3>) -> Self {
+        Transform {
+            position: position.into(),
+            rotation,
+            scale: scale.into(),
+        }
+    }
+
+    pub fn from_position(position: impl Into<Vector3>) -> Self {
+        Transform {
+            position: position.into(),
+            ..Transform::IDENTITY
+        }
+    }
+
+    /// splits an affine matrix into a transform, returns None if the matrix can't be decomposed
+    pub fn from_matrix(matrix: Matrix4) -> Option<Self> {
+        let parts = matrix.decompose()?;
+        Some(Transform {
+            position: parts.translation,
+            rotation: Quaternion::from_matrix3(parts.rotation),
+            scale: parts.scale,
+        })
+    }
+
+    /// the matrix taking points from this transform's local space into its parent's space
+    pub fn matrix(&self) -> Matrix4 {
+        Matrix4::from_nonuniform_scale(self.scale) * self.rotation.to_matrix4() * Matrix4::from_translation(self.position)
+    }
+
+    /// the matrix taking points from the parent's space into this transform's local space
+    pub fn inverse_matrix(&self) -> Matrix4 {
+        Matrix4::from_translation(-self.position) * self.rotation.conjugate().to_matrix4() * Matrix4::from_nonuniform_scale(Vector3::ONE / self.scale)
+    }
+
+    /// returns the transform that undoes this one
+    ///
+    /// this is only exact when the scale is uniform, as a rotation followed by a non uniform scale can't be written as a single transform. Use `inverse_matrix` when it has to be exact
+    pub fn inverse(&self) -> Transform {
+        let rotation = self.rotation.conjugate();
+        let scale = Vector3::ONE / self.scale;
+        Transform {
+            position: -(rotation * self.position) * scale,
+            rotation,
+            scale,
+        }
+    }
+
+    pub fn right(&self) -> Vector3 {
+        self.rotation * Vector3::X
+    }
+
+    pub fn up(&self) -> Vector3 {
+        self.rotation * Vector3::Y
+    }
+
+    /// transforms face down their local -z axis, the same as the view space of `Matrix4::look_at_rh`
+    pub fn forward(&self) -> Vector3 {
+        self.rotation * -Vector3::Z
+    }
+
+    /// rotates the transform so that it faces the target, keeping its up direction as close to the given up as possible
+    pub fn look_at(&mut self, target: impl Into<Vector3>, up: impl Into<Vector3>) {
+        let (target, up): (Vector3, Vector3) = (target.into(), up.into());
+        let f = (target - self.position).normalised();
+        let s = f.cross(up).normalised();
+        let u = s.cross(f);
+        self.rotation = Quaternion::from_matrix3(Matrix3::from_columns(s, u, -f));
+    }
+
+    /// takes a point from local space into parent space
+    pub fn transform_point(&self, point: impl Into<Vector3>) -> Vector3 {
+        let point: Vector3 = point.into();
+        self.rotation * (point * self.scale) + self.position
+    }
+
+    /// takes a vector from local space into parent space, ignoring the position
+    pub fn transform_vector(&self, vector: impl Into<Vector3>) -> Vector3 {
+        let vector: Vector3 = vector.into();
+        self.rotation * (vector * self.scale)
+    }
+
+    /// takes a point from parent space into local space
+    pub fn inverse_transform_point(&self, point: impl Into<Vector3>) -> Vector3 {
+        let point: Vector3 = point.into();
+        (self.rotation.conjugate() * (point - self.position)) / self.scale
+    }
+
+    /// takes a vector from parent space into local space, ignoring the position
+    pub fn inverse_transform_vector(&self, vector: impl Into<Vector3>) -> Vector3 {
+        let vector: Vector3 = vector.into();
+        (self.rotation.conjugate() * vector) / self.scale
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Transform::IDENTITY
+    }
+}
+
+/// `parent * child` gives the child transform in the parent's parent space
+///
+/// like `inverse` this is only exact if the parent has a uniform scale, a TransformHierarchy uses matrices so is always exact
+impl Mul for Transform {
+    type Output = Transform;
+    fn mul(self, rhs: Self) -> Self::Output {
+        Transform {
+            position: self.transform_point(rhs.position),
+            rotation: (self.rotation * rhs.rotation).normalised(),
+            scale: self.scale * rhs.scale,
+        }
+    }
+}
+
+impl From<Transform> for Matrix4 {
+    fn from(value: Transform) -> Self {
+        value.matrix()
+    }
+}
+
+
+
+#[derive(Debug)]
+pub struct TransformHierarchyError;
+
+/// A handle to a transform in a TransformHierarchy, handles to removed transforms stay invalid even if the slot is reused
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TransformId {
+    index: usize,
+    generation: u32,
+}
+
+#[derive(Debug, Clone)]
+struct TransformNode {
+    local: Transform,
+    parent: Option<TransformId>,
+    children: Vec<TransformId>,
+    world: Matrix4,
+    dirty: bool,
+}
+
+#[derive(Debug, Clone)]
+struct TransformSlot {
+    generation: u32,
+    node: Option<TransformNode>,
+}
+
+/// A flat arena of transforms with parent child links
+///
+/// World matrices are cached and only recalculated when the transform or one of its parents has changed since they were last asked for
+#[derive(Default, Debug, Clone)]
+pub struct TransformHierarchy {
+    slots: Vec<TransformSlot>,
+    free: Vec<usize>,
+}
+
+impl TransformHierarchy {
+    pub fn new() -> Self {
+        TransformHierarchy::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.slots.len() - self.free.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn contains(&self, id: TransformId) -> bool {
+        self.node(id).is_some()
+    }
+
+    fn node(&self, id: TransformId) -> Option<&TransformNode> {
+        let slot = self.slots.get(id.index)?;
+        if slot.generation != id.generation {return None;}
+        slot.node.as_ref()
+    }
+
+    fn node_mut(&mut self, id: TransformId) -> Option<&mut TransformNode> {
+        let slot = self.slots.get_mut(id.index)?;
+        if slot.generation != id.generation {return None;}
+        slot.node.as_mut()
+    }
+
+    fn insert(&mut self, local: Transform, parent: Option<TransformId>) -> TransformId {
+        let node = TransformNode {
+            local,
+            parent,
+            children: Vec::new(),
+            world: Matrix4::IDENTITY,
+            dirty: true,
+        };
+
+        let id = if let Some(index) = self.free.pop() {
+            let slot = &mut self.slots[index];
+            slot.node = Some(node);
+            TransformId {index, generation: slot.generation}
+        } else {
+            self.slots.push(TransformSlot {generation: 0, node: Some(node)});
+            TransformId {index: self.slots.len() - 1, generation: 0}
+        };
+
+        if let Some(parent) = parent {
+            self.node_mut(parent).unwrap().children.push(id);
+        }
+        id
+    }
+
+    /// adds a transform with no parent
+    pub fn add_root(&mut self, local: Transform) -> TransformId {
+        self.insert(local, None)
+    }
+
+    /// adds a transform as a child of the parent, the given transform is relative to the parent
+    pub fn add_child(&mut self, parent: TransformId, local: Transform) -> Result<TransformId, TransformHierarchyError> {
+        if !self.contains(parent) {return Err(TransformHierarchyError);}
+        Ok(self.insert(local, Some(parent)))
+    }
+
+    /// removes the transform and all of its children, returning the local transform that was removed
+    pub fn remove(&mut self, id: TransformId) -> Option<Transform> {
+        let node = self.node(id)?;
+        let (local, parent) = (node.local, node.parent);
+
+        if let Some(parent) = parent {
+            self.node_mut(parent).unwrap().children.retain(|child| *child != id);
+        }
+
+        let mut to_remove = vec![id];
+        while let Some(next) = to_remove.pop() {
+            let slot = &mut self.slots[next.index];
+            if let Some(node) = slot.node.take() {
+                to_remove.extend(node.children);
+            }
+            slot.generation = slot.generation.wrapping_add(1);
+            self.free.push(next.index);
+        }
+
+        Some(local)
+    }
+
+    pub fn parent(&self, id: TransformId) -> Option<TransformId> {
+        self.node(id)?.parent
+    }
+
+    pub fn children(&self, id: TransformId) -> &[TransformId] {
+        match self.node(id) {
+            Some(node) => &node.children,
+            None => &[],
+        }
+    }
+
+    /// moves the transform to a new parent, or makes it a root if the parent is None
+    ///
+    /// the local transform is kept, so the transform will move in world space. Fails if either handle is invalid or the new parent is a child of the transform
+    pub fn set_parent(&mut self, id: TransformId, parent: Option<TransformId>) -> Result<(), TransformHierarchyError> {
+        if !self.contains(id) {return Err(TransformHierarchyError);}
+        if let Some(new_parent) = parent {
+            // walk up from the new parent to make sure this won't make a loop
+            let mut current = Some(new_parent);
+            while let Some(ancestor) = current {
+                if ancestor == id || !self.contains(ancestor) {return Err(TransformHierarchyError);}
+                current = self.parent(ancestor);
+            }
+        }
+
+        if let Some(old_parent) = self.parent(id) {
+            self.node_mut(old_parent).unwrap().children.retain(|child| *child != id);
+        }
+        if let Some(new_parent) = parent {
+            self.node_mut(new_parent).unwrap().children.push(id);
+        }
+        self.node_mut(id).unwrap().parent = parent;
+        self.mark_dirty(id);
+        Ok(())
+    }
+
+    pub fn local(&self, id: TransformId) -> Option<Transform> {
+        Some(self.node(id)?.local)
+    }
+
+    pub fn set_local(&mut self, id: TransformId, local: Transform) -> Result<(), TransformHierarchyError> {
+        self.node_mut(id).ok_or(TransformHierarchyError)?.local = local;
+        self.mark_dirty(id);
+        Ok(())
+    }
+
+    // a dirty transform always has dirty children, so we can stop at anything already dirty
+    fn mark_dirty(&mut self, id: TransformId) {
+        let mut to_mark = vec![id];
+        while let Some(next) = to_mark.pop() {
+            let node = self.node_mut(next).unwrap();
+            if node.dirty && next != id {continue;}
+            node.dirty = true;
+            to_mark.extend(node.children.iter().copied());
+        }
+    }
+
+    /// the matrix taking points from the transform's local space into world space
+    pub fn world_matrix(&mut self, id: TransformId) -> Option<Matrix4> {
+        let node = self.node(id)?;
+        if !node.dirty {return Some(node.world);}
+
+        let (local, parent) = (node.local.matrix(), node.parent);
+        let world = match parent {
+            Some(parent) => local * self.world_matrix(parent)?,
+            None => local,
+        };
+
+        let node = self.node_mut(id).unwrap();
+        node.world = world;
+        node.dirty = false;
+        Some(world)
+    }
+
+    /// the matrix taking points from world space into the transform's local space
+    pub fn world_to_local_matrix(&mut self, id: TransformId) -> Option<Matrix4> {
+        Some(self.world_matrix(id)?.inverted())
+    }
+
+    /// the transform's position, rotation and scale in world space
+    pub fn world_transform(&mut self, id: TransformId) -> Option<Transform> {
+        Transform::from_matrix(self.world_matrix(id)?)
+    }
+
+    pub fn local_to_world_point(&mut self, id: TransformId, point: impl Into<Vector3>) -> Option<Vector3> {
+        Some(self.world_matrix(id)?.transform_point(point))
+    }
+
+    pub fn local_to_world_vector(&mut self, id: TransformId, vector: impl Into<Vector3>) -> Option<Vector3> {
+        Some(self.world_matrix(id)?.transform_vector(vector))
+    }
+
+    pub fn world_to_local_point(&mut self, id: TransformId, point: impl Into<Vector3>) -> Option<Vector3> {
+        Some(self.world_to_local_matrix(id)?.transform_point(point))
+    }
+
+    pub fn world_to_local_vector(&mut self, id: TransformId, vector: impl Into<Vector3>) -> Option<Vector3> {
+        Some(self.world_to_local_matrix(id)?.transform_vector(vector))
+    }
+
+    /// recalculates every out of date world matrix, useful once per frame before the matrices are read
+    pub fn update_world_matrices(&mut self) {
+        for index in 0..self.slots.len() {
+            let slot = &self.slots[index];
+            if slot.node.is_some() {
+                let id = TransformId {index, generation: slot.generation};
+                self.world_matrix(id);
+            }
+        }
+    }
+}
@@ -1,10 +1,12 @@
 mod vector_two;
 mod vector_three;
 mod vector_four;
+mod vector_n;
 
 mod vector_three_int;
 
-pub use vector_three::Vector3;
-pub use vector_two::Vector2;
-pub use vector_four::Vector4;
+pub use vector_three::{Vector3, DVector3};
+pub use vector_two::{Vector2, DVector2};
+pub use vector_four::{Vector4, DVector4};
+pub use vector_n::VectorN;
 pub use vector_three_int::Vector3Int;
\ No newline at end of file
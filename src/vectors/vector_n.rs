@@ -0,0 +1,232 @@
+use super::{Vector2, Vector3, Vector4};
+use std::fmt::Display;
+use std::ops::*;
+
+
+/// a vector with any number of components, stored on the stack
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct VectorN<const N: usize> {
+    pub components: [f32; N],
+}
+
+impl<const N: usize> Default for VectorN<N> {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+
+impl<const N: usize> VectorN<N> {
+    pub const ZERO: VectorN<N> = VectorN{components: [0.0; N]};
+    pub const ONE: VectorN<N> = VectorN{components: [1.0; N]};
+
+    pub const fn new(components: [f32; N]) -> Self {
+        VectorN {
+            components
+        }
+    }
+
+    pub const fn splat(value: f32) -> Self {
+        VectorN {
+            components: [value; N]
+        }
+    }
+
+    /// a vector with a 1 at the index and 0 everywhere else
+    pub fn unit(index: usize) -> Self {
+        let mut components = [0.0; N];
+        components[index] = 1.0;
+        VectorN {
+            components
+        }
+    }
+
+    pub const fn len(&self) -> usize {
+        N
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        N == 0
+    }
+
+    pub fn dot(&self, rhs: impl Into<VectorN<N>>) -> f32 {
+        let rhs = rhs.into();
+        self.components.iter().zip(rhs.components.iter()).map(|(a, b)| a * b).sum()
+    }
+
+    pub fn sqr_magnitude(&self) -> f32 {
+        self.dot(*self)
+    }
+
+    pub fn magnitude(&self) -> f32 {
+        self.sqr_magnitude().sqrt()
+    }
+
+    pub fn normalise(&mut self) {
+        let length = self.magnitude();
+        self.components.iter_mut().for_each(|val| *val /= length);
+    }
+
+    pub fn normalised(&self) -> Self {
+        *self / self.magnitude()
+    }
+
+    pub fn sum(&self) -> f32 {
+        self.components.iter().sum()
+    }
+
+    pub fn component_mul(&self, rhs: impl Into<VectorN<N>>) -> Self {
+        let rhs = rhs.into();
+        let mut result = *self;
+        result.components.iter_mut().zip(rhs.components.iter()).for_each(|(a, b)| *a *= b);
+        result
+    }
+
+    pub fn map(&self, f: impl Fn(f32) -> f32) -> Self {
+        VectorN {
+            components: self.components.map(f)
+        }
+    }
+}
+
+
+impl<const N: usize> From<[f32; N]> for VectorN<N> {
+    fn from(value: [f32; N]) -> Self {
+        VectorN::new(value)
+    }
+}
+
+impl<const N: usize> From<VectorN<N>> for [f32; N] {
+    fn from(value: VectorN<N>) -> Self {
+        value.components
+    }
+}
+
+impl From<Vector2> for VectorN<2> {
+    fn from(value: Vector2) -> Self {
+        VectorN::new([value.x, value.y])
+    }
+}
+
+impl From<VectorN<2>> for Vector2 {
+    fn from(value: VectorN<2>) -> Self {
+        let [x, y] = value.components;
+        Vector2::new(x, y)
+    }
+}
+
+impl From<Vector3> for VectorN<3> {
+    fn from(value: Vector3) -> Self {
+        VectorN::new([value.x, value.y, value.z])
+    }
+}
+
+impl From<VectorN<3>> for Vector3 {
+    fn from(value: VectorN<3>) -> Self {
+        let [x, y, z] = value.components;
+        Vector3::new(x, y, z)
+    }
+}
+
+impl From<Vector4> for VectorN<4> {
+    fn from(value: Vector4) -> Self {
+        VectorN::new([value.x, value.y, value.z, value.w])
+    }
+}
+
+impl From<VectorN<4>> for Vector4 {
+    fn from(value: VectorN<4>) -> Self {
+        let [x, y, z, w] = value.components;
+        Vector4::new(x, y, z, w)
+    }
+}
+
+
+impl<const N: usize> Index<usize> for VectorN<N> {
+    type Output = f32;
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.components[index]
+    }
+}
+
+impl<const N: usize> IndexMut<usize> for VectorN<N> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.components[index]
+    }
+}
+
+impl<const N: usize> Add for VectorN<N> {
+    type Output = Self;
+    fn add(mut self, rhs: Self) -> Self::Output {
+        self += rhs;
+        self
+    }
+}
+
+impl<const N: usize> AddAssign for VectorN<N> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.components.iter_mut().zip(rhs.components.iter()).for_each(|(a, b)| *a += b);
+    }
+}
+
+impl<const N: usize> Sub for VectorN<N> {
+    type Output = Self;
+    fn sub(mut self, rhs: Self) -> Self::Output {
+        self -= rhs;
+        self
+    }
+}
+
+impl<const N: usize> SubAssign for VectorN<N> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.components.iter_mut().zip(rhs.components.iter()).for_each(|(a, b)| *a -= b);
+    }
+}
+
+impl<const N: usize> Mul<f32> for VectorN<N> {
+    type Output = Self;
+    fn mul(mut self, rhs: f32) -> Self::Output {
+        self *= rhs;
+        self
+    }
+}
+
+impl<const N: usize> MulAssign<f32> for VectorN<N> {
+    fn mul_assign(&mut self, rhs: f32) {
+        self.components.iter_mut().for_each(|val| *val *= rhs);
+    }
+}
+
+impl<const N: usize> Div<f32> for VectorN<N> {
+    type Output = Self;
+    fn div(mut self, rhs: f32) -> Self::Output {
+        self /= rhs;
+        self
+    }
+}
+
+impl<const N: usize> DivAssign<f32> for VectorN<N> {
+    fn div_assign(&mut self, rhs: f32) {
+        self.components.iter_mut().for_each(|val| *val /= rhs);
+    }
+}
+
+impl<const N: usize> Neg for VectorN<N> {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        self.map(|val| -val)
+    }
+}
+
+impl<const N: usize> Display for VectorN<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[")?;
+        for (i, val) in self.components.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", val)?;
+        }
+        write!(f, "]")
+    }
+}
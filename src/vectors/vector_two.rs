@@ -1,242 +1,267 @@
 use std::{ops::*, fmt::Display};
-use std::f32::EPSILON;
 use serde::{Deserialize, Serialize};
 
-use super::Vector3;
+use super::{Vector3, DVector3};
 
 
-#[derive(Default, Clone, Copy, Debug, PartialEq, PartialOrd, Deserialize, Serialize)]
-pub struct Vector2 {
-    pub x: f32, 
-    pub y: f32,
-}
+macro_rules! vector2 {
+    ($(#[$attr:meta])* $name:ident, $scalar:ident, $vector3:ident) => {
+        $(#[$attr])*
+        #[derive(Default, Clone, Copy, Debug, PartialEq, PartialOrd, Deserialize, Serialize)]
+        pub struct $name {
+            pub x: $scalar, 
+            pub y: $scalar,
+        }
 
 
-impl Vector2 {
-    pub const X: Vector2 = Vector2{x: 1.0, y: 0.0};
-    pub const Y: Vector2 = Vector2{x: 0.0, y: 1.0};
-    pub const ZERO: Vector2 = Vector2{x: 0.0, y: 0.0};
-    pub const ONE: Vector2 = Vector2{x: 1.0, y: 1.0};
-    pub const EPSILON: Vector2 = Vector2{x: EPSILON, y: EPSILON};
+        impl $name {
+            pub const X: $name = $name{x: 1.0, y: 0.0};
+            pub const Y: $name = $name{x: 0.0, y: 1.0};
+            pub const ZERO: $name = $name{x: 0.0, y: 0.0};
+            pub const ONE: $name = $name{x: 1.0, y: 1.0};
+            pub const EPSILON: $name = $name{x: $scalar::EPSILON, y: $scalar::EPSILON};
 
-    pub const fn new(x: f32, y: f32) -> Self {
-        Vector2 {
-            x,
-            y,
-        }
-    }
+            pub const fn new(x: $scalar, y: $scalar) -> Self {
+                $name {
+                    x,
+                    y,
+                }
+            }
 
 
-    pub fn sqr_magnitude(&self) -> f32 {
-        self.x * self.x + self.y * self.y
-    }
+            pub fn sqr_magnitude(&self) -> $scalar {
+                self.x * self.x + self.y * self.y
+            }
 
-    pub fn magnitude(&self) -> f32 {
-        self.sqr_magnitude().sqrt()
-    }
+            pub fn magnitude(&self) -> $scalar {
+                self.sqr_magnitude().sqrt()
+            }
 
 
-    pub fn normalised(&self) -> Vector2{
-        let length = self.magnitude();
+            pub fn normalised(&self) -> $name{
+                let length = self.magnitude();
 
-        Vector2::new(
-            self.x / length,
-            self.y / length,
-        )
-    }
+                $name::new(
+                    self.x / length,
+                    self.y / length,
+                )
+            }
 
-    pub fn normalise(&mut self){
-        let length = self.magnitude();
+            pub fn normalise(&mut self){
+                let length = self.magnitude();
 
-        self.x /= length;
-        self.y /= length;
-    }
+                self.x /= length;
+                self.y /= length;
+            }
 
 
-    pub fn dot(&self, rhs: impl Into<Vector2>) -> f32{
-        let rhs: Vector2 = rhs.into();
-        self.x * rhs.x + self.y * rhs.y
-    }
+            pub fn dot(&self, rhs: impl Into<$name>) -> $scalar{
+                let rhs: $name = rhs.into();
+                self.x * rhs.x + self.y * rhs.y
+            }
 
 
-    pub fn sum(&self) -> f32 {
-        self.x + self.y
-    }
+            pub fn sum(&self) -> $scalar {
+                self.x + self.y
+            }
 
 
-    pub fn floor(&self) -> Vector2 {
-        Vector2::new(self.x.floor(), self.y.floor())
-    }
+            pub fn floor(&self) -> $name {
+                $name::new(self.x.floor(), self.y.floor())
+            }
 
 
-    pub fn to_isize_array(&self) -> [isize; 2] {
-        [self.x.round() as isize, self.y.round() as isize]
-    }
+            pub fn to_isize_array(&self) -> [isize; 2] {
+                [self.x.round() as isize, self.y.round() as isize]
+            }
 
-    // extends to a vector 3 adding a zero on the end
-    pub fn extend(&self) -> Vector3 {
-        Vector3::new(self.x, self.y, 0.0)
-    }
+            // extends to a vector 3 adding a zero on the end
+            pub fn extend(&self) -> $vector3 {
+                $vector3::new(self.x, self.y, 0.0)
+            }
 
-    pub fn extend_with(&self, extension: f32) -> Vector3 {
-        Vector3::new(self.x, self.y, extension)
-    }
-}
+            pub fn extend_with(&self, extension: $scalar) -> $vector3 {
+                $vector3::new(self.x, self.y, extension)
+            }
+        }
 
-//////////////////////////////////////////////////////////////////
-///////////////////////////////// from and into
-//////////////////////////////////////////////////////////////////
+        //////////////////////////////////////////////////////////////////
+        ///////////////////////////////// from and into
+        //////////////////////////////////////////////////////////////////
 
-impl Into<[f32; 2]> for Vector2 {
-    fn into(self) -> [f32; 2] {
-        [self.x, self.y]
-    }
-}
+        impl Into<[$scalar; 2]> for $name {
+            fn into(self) -> [$scalar; 2] {
+                [self.x, self.y]
+            }
+        }
 
 
-impl From<[f32; 2]> for Vector2 {
-    fn from(value: [f32; 2]) -> Self {
-        Vector2::new(value[0], value[1])
-    }
-}
+        impl From<[$scalar; 2]> for $name {
+            fn from(value: [$scalar; 2]) -> Self {
+                $name::new(value[0], value[1])
+            }
+        }
 
-impl From<[i32; 2]> for Vector2 {
-    fn from(value: [i32; 2]) -> Self {
-        Vector2::new(value[0] as f32, value[1] as f32)
-    }
-}
+        impl From<[i32; 2]> for $name {
+            fn from(value: [i32; 2]) -> Self {
+                $name::new(value[0] as $scalar, value[1] as $scalar)
+            }
+        }
 
-//////////////////////////////////////////////////////////////////
-///////////////////////////////// arithmetic operations
-//////////////////////////////////////////////////////////////////
+        //////////////////////////////////////////////////////////////////
+        ///////////////////////////////// arithmetic operations
+        //////////////////////////////////////////////////////////////////
 
 
-impl Add for Vector2 {
-    type Output = Self;
-    fn add(self, rhs: Self) -> Self::Output {
-        Self {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y,
+        impl Add for $name {
+            type Output = Self;
+            fn add(self, rhs: Self) -> Self::Output {
+                Self {
+                    x: self.x + rhs.x,
+                    y: self.y + rhs.y,
+                }
+            }
         }
-    }
-}
 
-impl AddAssign for Vector2 {
-    fn add_assign(&mut self, rhs: Self) {
-        *self = Self {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y
+        impl AddAssign for $name {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = Self {
+                    x: self.x + rhs.x,
+                    y: self.y + rhs.y
+                }
+            }
         }
-    }
-}
 
-impl Sub for Vector2 {
-    type Output = Self;
-    fn sub(self, rhs: Self) -> Self::Output {
-        Self {
-            x: self.x - rhs.x,
-            y: self.y - rhs.y,
+        impl Sub for $name {
+            type Output = Self;
+            fn sub(self, rhs: Self) -> Self::Output {
+                Self {
+                    x: self.x - rhs.x,
+                    y: self.y - rhs.y,
+                }
+            }
         }
-    }
-}
 
-impl SubAssign for Vector2 {
-    fn sub_assign(&mut self, rhs: Self) {
-        *self = Self {
-            x: self.x - rhs.x,
-            y: self.y - rhs.y
+        impl SubAssign for $name {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = Self {
+                    x: self.x - rhs.x,
+                    y: self.y - rhs.y
+                }
+            }
         }
-    }
-}
 
-impl Mul for Vector2 {
-    type Output = Self;
-    fn mul(self, rhs: Self) -> Self::Output {
-        Self {
-            x: self.x * rhs.x,
-            y: self.y * rhs.y
+        impl Mul for $name {
+            type Output = Self;
+            fn mul(self, rhs: Self) -> Self::Output {
+                Self {
+                    x: self.x * rhs.x,
+                    y: self.y * rhs.y
+                }
+            }
         }
-    }
-}
 
-impl MulAssign for Vector2 {
-    fn mul_assign(&mut self, rhs: Self) {
-        *self = Self{
-            x: self.x * rhs.x,
-            y: self.y * rhs.y
+        impl MulAssign for $name {
+            fn mul_assign(&mut self, rhs: Self) {
+                *self = Self{
+                    x: self.x * rhs.x,
+                    y: self.y * rhs.y
+                }
+            }
         }
-    }
-}
 
-impl Mul<f32> for Vector2 {
-    type Output = Self;
-    fn mul(self, rhs: f32) -> Self::Output {
-        Self {
-            x: self.x * rhs,
-            y: self.y * rhs,
+        impl Mul<$scalar> for $name {
+            type Output = Self;
+            fn mul(self, rhs: $scalar) -> Self::Output {
+                Self {
+                    x: self.x * rhs,
+                    y: self.y * rhs,
+                }
+            }
         }
-    }
-}
 
-impl MulAssign<f32> for Vector2 {
-    fn mul_assign(&mut self, rhs: f32) {
-        *self = Self {
-            x: self.x * rhs,
-            y: self.y * rhs,
+        impl MulAssign<$scalar> for $name {
+            fn mul_assign(&mut self, rhs: $scalar) {
+                *self = Self {
+                    x: self.x * rhs,
+                    y: self.y * rhs,
+                }
+            }
         }
-    }
-}
 
-impl Div for Vector2 {
-    type Output = Self;
-    fn div(self, rhs: Self) -> Self::Output {
-        Self {
-            x: self.x / rhs.x,
-            y: self.y / rhs.y
+        impl Div for $name {
+            type Output = Self;
+            fn div(self, rhs: Self) -> Self::Output {
+                Self {
+                    x: self.x / rhs.x,
+                    y: self.y / rhs.y
+                }
+            }
         }
-    }
-}
 
-impl DivAssign for Vector2 {
-    fn div_assign(&mut self, rhs: Self) {
-        *self = Self{
-            x: self.x / rhs.x,
-            y: self.y / rhs.y
+        impl DivAssign for $name {
+            fn div_assign(&mut self, rhs: Self) {
+                *self = Self{
+                    x: self.x / rhs.x,
+                    y: self.y / rhs.y
+                }
+            }
         }
-    }
-}
 
-impl Div<f32> for Vector2 {
-    type Output = Self;
-    fn div(self, rhs: f32) -> Self::Output {
-        Self {
-            x: self.x / rhs,
-            y: self.y / rhs,
+        impl Div<$scalar> for $name {
+            type Output = Self;
+            fn div(self, rhs: $scalar) -> Self::Output {
+                Self {
+                    x: self.x / rhs,
+                    y: self.y / rhs,
+                }
+            }
         }
-    }
-}
 
-impl DivAssign<f32> for Vector2 {
-    fn div_assign(&mut self, rhs: f32) {
-        *self = Self {
-            x: self.x / rhs,
-            y: self.y / rhs,
+        impl DivAssign<$scalar> for $name {
+            fn div_assign(&mut self, rhs: $scalar) {
+                *self = Self {
+                    x: self.x / rhs,
+                    y: self.y / rhs,
+                }
+            }
         }
-    }
-}
 
-impl Neg for Vector2 {
-    type Output = Self;
-    fn neg(self) -> Self::Output {
-        Self {
-            x: -self.x,
-            y: -self.y,
+        impl Neg for $name {
+            type Output = Self;
+            fn neg(self) -> Self::Output {
+                Self {
+                    x: -self.x,
+                    y: -self.y,
+                }
+            }
         }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "[{}, {}]", self.x, self.y)
+            }
+        }
+    };
+}
+
+vector2!(Vector2, f32, Vector3);
+vector2!(
+    /// a 2d vector of f64s with all the same methods as `Vector2`
+    DVector2, f64, DVector3
+);
+
+
+// precision conversions, only widening is done implicitly
+impl From<Vector2> for DVector2 {
+    fn from(value: Vector2) -> Self {
+        DVector2::new(value.x as f64, value.y as f64)
     }
 }
 
-impl Display for Vector2 {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "[{}, {}]", self.x, self.y)
+impl DVector2 {
+    /// rounds each component to the nearest f32
+    pub fn to_single(&self) -> Vector2 {
+        Vector2::new(self.x as f32, self.y as f32)
     }
-}
\ No newline at end of file
+}
@@ -1,5 +1,35 @@
 use rust_maths::*;
 
+fn vectors_close(a: Vector3, b: Vector3) -> bool {
+    vectors_within(a, b, 1e-4)
+}
+
+fn vectors_within(a: Vector3, b: Vector3, tolerance: f32) -> bool {
+    (a - b).magnitude() < tolerance
+}
+
+// a sphere made of rings of triangles
+fn sphere_mesh(radius: f32, rings: u32, segments: u32) -> MeshCollider {
+    let mut vertices = Vec::new();
+    for ring in 0..=rings {
+        let theta = ring as f32 / rings as f32 * std::f32::consts::PI;
+        for segment in 0..segments {
+            let phi = segment as f32 / segments as f32 * std::f32::consts::TAU;
+            vertices.push(Vector3::new(theta.sin() * phi.cos(), theta.cos(), theta.sin() * phi.sin()) * radius);
+        }
+    }
+
+    let mut indices = Vec::new();
+    for ring in 0..rings {
+        for segment in 0..segments {
+            let (a, b) = (ring * segments + segment, ring * segments + (segment + 1) % segments);
+            let (c, d) = (a + segments, b + segments);
+            indices.extend([a, c, b, b, c, d]);
+        }
+    }
+    MeshCollider::new(vertices, indices)
+}
+
 
 ///////////////////////////////////////////////////////////////////////////////////////
 ////////////////////////////////// AABB ///////////////////////////////////////////////
@@ -40,6 +70,101 @@ mod aabb_collider_tests {
         assert_eq!(hit.hit_position, [0, 0, 0].into());
         assert_eq!(hit.hit_distance, 10.0);
     }
+
+    #[test]
+    fn normal_test() {
+        let bounds = AABoundingBox::new([-1, -1, -1], [1, 1, 1]);
+        assert_eq!(bounds.check_ray([5, 0, 0], [-1, 0, 0], None).unwrap().hit_normal, Vector3::X);
+        assert_eq!(bounds.check_ray([-5, 0, 0], [1, 0, 0], None).unwrap().hit_normal, -Vector3::X);
+        assert_eq!(bounds.check_ray([0, 5, 0], [0, -1, 0], None).unwrap().hit_normal, Vector3::Y);
+        assert_eq!(bounds.check_ray([0, 0, -5], [0, 0, 1], None).unwrap().hit_normal, -Vector3::Z);
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////
+////////////////////////////////// OBB ////////////////////////////////////////////////
+///////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod obb_collider_tests {
+    use super::*;
+    use std::f32::consts::FRAC_PI_4;
+
+    #[test]
+    fn rotated_ray_test() {
+        // a 2 by 2 by 2 box turned 45 degrees around y, so its corner points along x
+        let bounds = OrientedBoundingBox::new(Vector3::ZERO, Vector3::ONE, Matrix3::from_angle_y(FRAC_PI_4));
+        let hit = bounds.check_ray([10, 0, 0], -Vector3::X, None).unwrap();
+
+        assert!((hit.hit_distance - (10.0 - 2_f32.sqrt())).abs() < 1e-4);
+        assert!(vectors_close(hit.hit_position, Vector3::X * 2_f32.sqrt()));
+        // the corner is right at the edge of the box, above it the ray misses
+        assert!(bounds.check_ray([10.0, 1.1, 0.0], -Vector3::X, None).is_none());
+        assert!(bounds.check_ray([10, 0, 0], -Vector3::X, Some(5.0)).is_none());
+    }
+
+    #[test]
+    fn ray_normal_test() {
+        let bounds = OrientedBoundingBox::new([0, 0, 0], [2, 1, 1], Matrix3::from_angle_z(FRAC_PI_4));
+        let up = Matrix3::from_angle_z(FRAC_PI_4) * Vector3::Y;
+        let hit = bounds.check_ray(up * 5.0, -up, None).unwrap();
+
+        assert!((hit.hit_distance - 4.0).abs() < 1e-4);
+        assert!(vectors_close(hit.hit_normal, up));
+    }
+
+    #[test]
+    fn obb_obb_test() {
+        let a = OrientedBoundingBox::new([0, 0, 0], [1, 1, 1], Matrix3::IDENTITY);
+        let turned = Matrix3::from_angle_z(FRAC_PI_4);
+
+        // the turned box reaches sqrt(2) along x
+        assert!(a.is_intersecting_obb(&OrientedBoundingBox::new([2.3, 0.0, 0.0], [1, 1, 1], turned)));
+        assert!(!a.is_intersecting_obb(&OrientedBoundingBox::new([2.5, 0.0, 0.0], [1, 1, 1], turned)));
+        // separated on the diagonal, where the aabbs of the two would overlap
+        let diagonal = OrientedBoundingBox::new([1.6, 1.6, 0.0], [1.0, 0.2, 1.0], Matrix3::from_angle_z(-FRAC_PI_4));
+        assert!(!a.is_intersecting_obb(&diagonal));
+        assert!(a.bounding_box().is_intersecting_box(diagonal.bounding_box()));
+        assert!(!a.intersects(&diagonal));
+    }
+
+    #[test]
+    fn conversion_test() {
+        let aabb = AABoundingBox::new([1, 2, 3], [3, 6, 5]);
+        let obb = OrientedBoundingBox::from(aabb);
+
+        assert_eq!(obb.centre, Vector3::new(2.0, 4.0, 4.0));
+        assert_eq!(AABoundingBox::from(obb), aabb);
+
+        let turned = OrientedBoundingBox::new([0, 0, 0], [1, 1, 1], Matrix3::from_angle_y(FRAC_PI_4));
+        let bounds = turned.bounding_box();
+        assert!(vectors_close(bounds.max_corner, Vector3::new(2_f32.sqrt(), 1.0, 2_f32.sqrt())));
+        assert!(turned.corners().iter().all(|corner| bounds.contains_point(*corner + corner.normalised() * -1e-4)));
+    }
+
+    #[test]
+    fn from_points_test() {
+        // a long thin cloud of points along a diagonal
+        let rotation = Matrix3::from_euler_angles([0.3, 0.9, -0.4]);
+        let mut points = Vec::new();
+        for i in 0..20 {
+            for corner in AABoundingBox::new([-0.5, -0.25, -0.1], [0.5, 0.25, 0.1]).corners() {
+                points.push(rotation * (corner + Vector3::X * (i as f32 - 9.5)) + Vector3::new(5.0, 0.0, 1.0));
+            }
+        }
+        let obb = OrientedBoundingBox::from_points(&points);
+
+        assert!((obb.basis.determinant() - 1.0).abs() < 1e-4);
+        assert!(vectors_close(obb.centre, Vector3::new(5.0, 0.0, 1.0)));
+        assert!((obb.half_extents.x.max(obb.half_extents.y).max(obb.half_extents.z) - 10.0).abs() < 1e-3);
+        assert!(points.iter().all(|point| obb.contains_point(*point + (obb.centre - *point) * 1e-4)));
+
+        let volume = obb.half_extents.x * obb.half_extents.y * obb.half_extents.z * 8.0;
+        let aabb = AABoundingBox::from_points(points.clone());
+        let aabb_size = aabb.max_corner - aabb.min_corner;
+        assert!(volume < aabb_size.x * aabb_size.y * aabb_size.z);
+        assert!((volume - 20.0 * 0.5 * 0.2).abs() < 1e-2);
+    }
 }
 
 ///////////////////////////////////////////////////////////////////////////////////////
@@ -81,6 +206,217 @@ mod sphere_collider_tests {
 }
 
 
+///////////////////////////////////////////////////////////////////////////////////////
+////////////////////////////////// capsule ////////////////////////////////////////////
+///////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod capsule_collider_tests {
+    use super::*;
+
+    #[test]
+    fn side_hit_test() {
+        let capsule = CapsuleCollider::new([0, -2, 0], [0, 2, 0], 1.0);
+        let hit = capsule.check_ray([5, 1, 0], -Vector3::X, None).unwrap();
+
+        assert!((hit.hit_distance - 4.0).abs() < 1e-4);
+        assert!(vectors_close(hit.hit_normal, Vector3::X));
+    }
+
+    #[test]
+    fn end_hit_test() {
+        let capsule = CapsuleCollider::new([0, -2, 0], [0, 2, 0], 1.0);
+        let hit = capsule.check_ray([0, 10, 0], -Vector3::Y, None).unwrap();
+        assert!((hit.hit_distance - 7.0).abs() < 1e-4);
+        assert!(vectors_close(hit.hit_normal, Vector3::Y));
+
+        // past the end of the cylinder part, where only the rounded end is hit
+        let hit = capsule.check_ray([5.0, 2.6, 0.0], -Vector3::X, None).unwrap();
+        assert!(vectors_close(hit.hit_position, Vector3::new(0.8, 2.6, 0.0)));
+        assert!(vectors_close(hit.hit_normal, Vector3::new(0.8, 0.6, 0.0)));
+    }
+
+    #[test]
+    fn miss_test() {
+        let capsule = CapsuleCollider::new([0, -2, 0], [0, 2, 0], 1.0);
+        assert!(capsule.check_ray([5.0, 3.1, 0.0], -Vector3::X, None).is_none());
+        assert!(capsule.check_ray([5, 0, 0], Vector3::X, None).is_none());
+        assert!(capsule.check_ray([5, 0, 0], -Vector3::X, Some(3.0)).is_none());
+
+        let inside = capsule.check_ray([0, 0, 0], Vector3::X, None).unwrap();
+        assert_eq!(inside.hit_distance, 0.0);
+        assert_eq!(inside.hit_normal, Vector3::ZERO);
+    }
+
+    #[test]
+    fn bounds_test() {
+        let capsule = CapsuleCollider::new([0, -2, 0], [3, 2, 0], 1.0);
+        let bounds = capsule.bounding_box();
+        assert_eq!(bounds.min_corner, Vector3::new(-1.0, -3.0, -1.0));
+        assert_eq!(bounds.max_corner, Vector3::new(4.0, 3.0, 1.0));
+
+        let sphere = capsule.bounding_sphere();
+        assert_eq!(sphere.centre, Vector3::new(1.5, 0.0, 0.0));
+        assert_eq!(sphere.radius, 3.5);
+    }
+
+    #[test]
+    fn sphere_test() {
+        let capsule = CapsuleCollider::new([0, -2, 0], [0, 2, 0], 1.0);
+        assert!(capsule.intersects(&BoundingSphere::new([1.9, 2.0, 0.0], 1.0)));
+        assert!(!capsule.intersects(&BoundingSphere::new([2.1, 2.0, 0.0], 1.0)));
+        assert!(BoundingSphere::new([0.0, 3.9, 0.0], 1.0).intersects(&capsule));
+        assert!(!BoundingSphere::new([0.0, 4.1, 0.0], 1.0).intersects(&capsule));
+    }
+}
+
+
+///////////////////////////////////////////////////////////////////////////////////////
+////////////////////////////////// cylinder ///////////////////////////////////////////
+///////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod cylinder_collider_tests {
+    use super::*;
+
+    #[test]
+    fn side_hit_test() {
+        let cylinder = CylinderCollider::new([0, 0, -2], [0, 0, 2], 1.0);
+        let hit = cylinder.check_ray([0, 5, 1], -Vector3::Y, None).unwrap();
+
+        assert!((hit.hit_distance - 4.0).abs() < 1e-4);
+        assert!(vectors_close(hit.hit_normal, Vector3::Y));
+    }
+
+    #[test]
+    fn cap_hit_test() {
+        let cylinder = CylinderCollider::new([0, 0, -2], [0, 0, 2], 1.0);
+        let hit = cylinder.check_ray([0.5, 0.0, 5.0], -Vector3::Z, None).unwrap();
+        assert!((hit.hit_distance - 3.0).abs() < 1e-4);
+        assert!(vectors_close(hit.hit_normal, Vector3::Z));
+
+        let hit = cylinder.check_ray([0.0, 0.5, -5.0], Vector3::Z, None).unwrap();
+        assert!(vectors_close(hit.hit_position, Vector3::new(0.0, 0.5, -2.0)));
+        assert!(vectors_close(hit.hit_normal, -Vector3::Z));
+
+        // coming in at an angle
+        let hit = cylinder.check_ray([-2.5, 0.0, 5.0], [1, 0, -1], None).unwrap();
+        assert!(vectors_close(hit.hit_position, Vector3::new(0.5, 0.0, 2.0)));
+        assert!(vectors_close(hit.hit_normal, Vector3::Z));
+    }
+
+    #[test]
+    fn miss_test() {
+        let cylinder = CylinderCollider::new([0, 0, -2], [0, 0, 2], 1.0);
+        // a capsule would be hit here but the cylinder has flat ends
+        assert!(cylinder.check_ray([0.0, 5.0, 2.5], -Vector3::Y, None).is_none());
+        assert!(cylinder.check_ray([1.5, 0.0, 5.0], -Vector3::Z, None).is_none());
+        assert!(cylinder.check_ray([0, 5, 0], -Vector3::Y, Some(3.0)).is_none());
+
+        let inside = cylinder.check_ray([0, 0, 1], Vector3::X, None).unwrap();
+        assert_eq!(inside.hit_distance, 0.0);
+        assert_eq!(inside.hit_normal, Vector3::ZERO);
+    }
+
+    #[test]
+    fn bounds_test() {
+        let cylinder = CylinderCollider::new([0, 0, -2], [0, 0, 2], 1.0);
+        let bounds = cylinder.bounding_box();
+        assert!(vectors_close(bounds.min_corner, Vector3::new(-1.0, -1.0, -2.0)));
+        assert!(vectors_close(bounds.max_corner, Vector3::new(1.0, 1.0, 2.0)));
+        assert!((cylinder.bounding_sphere().radius - 5_f32.sqrt()).abs() < 1e-4);
+
+        // tilted, the caps stick out further than the ends of the axis
+        let tilted = CylinderCollider::new([0, 0, 0], [3, 4, 0], 1.0);
+        let bounds = tilted.bounding_box();
+        assert!(vectors_close(bounds.min_corner, Vector3::new(-0.8, -0.6, -1.0)));
+        assert!(vectors_close(bounds.max_corner, Vector3::new(3.8, 4.6, 1.0)));
+    }
+
+    #[test]
+    fn sphere_test() {
+        let cylinder = CylinderCollider::new([0, 0, -2], [0, 0, 2], 1.0);
+        assert!(cylinder.intersects(&BoundingSphere::new([1.9, 0.0, 0.0], 1.0)));
+        assert!(!cylinder.intersects(&BoundingSphere::new([2.1, 0.0, 0.0], 1.0)));
+        // just off the corner of the cap, which a capsule would overlap
+        assert!(!BoundingSphere::new([1.6, 0.0, 2.6], 0.8).intersects(&cylinder));
+        assert!(BoundingSphere::new([0.5, 0.0, 2.9], 1.0).intersects(&cylinder));
+    }
+}
+
+
+///////////////////////////////////////////////////////////////////////////////////////
+////////////////////////////////// cone ///////////////////////////////////////////////
+///////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod cone_collider_tests {
+    use super::*;
+
+    #[test]
+    fn side_hit_test() {
+        // a cone with a 45 degree side, so the slope normal is halfway between out and up
+        let cone = ConeCollider::new([0, 0, 0], [0, 2, 0], 2.0);
+        let hit = cone.check_ray([5, 1, 0], -Vector3::X, None).unwrap();
+
+        assert!((hit.hit_distance - 4.0).abs() < 1e-4);
+        assert!(vectors_close(hit.hit_normal, Vector3::new(1.0, 1.0, 0.0).normalised()));
+
+        let hit = cone.check_ray([0, 5, 0], -Vector3::Y, None).unwrap();
+        assert!(vectors_close(hit.hit_position, Vector3::new(0.0, 2.0, 0.0)));
+    }
+
+    #[test]
+    fn base_hit_test() {
+        let cone = ConeCollider::new([0, 0, 0], [0, 2, 0], 2.0);
+        let hit = cone.check_ray([1, -3, 0], Vector3::Y, None).unwrap();
+
+        assert!((hit.hit_distance - 3.0).abs() < 1e-4);
+        assert!(vectors_close(hit.hit_normal, -Vector3::Y));
+    }
+
+    #[test]
+    fn miss_test() {
+        let cone = ConeCollider::new([0, 0, 0], [0, 2, 0], 2.0);
+        assert!(cone.check_ray([5.0, 1.5, 1.0], -Vector3::X, None).is_none());
+        // through the mirrored cone above the apex
+        assert!(cone.check_ray([5, 3, 0], -Vector3::X, None).is_none());
+        assert!(cone.check_ray([5, 1, 0], -Vector3::X, Some(3.0)).is_none());
+
+        let inside = cone.check_ray([0.0, 0.5, 0.0], Vector3::X, None).unwrap();
+        assert_eq!(inside.hit_distance, 0.0);
+        assert_eq!(inside.hit_normal, Vector3::ZERO);
+    }
+
+    #[test]
+    fn bounds_test() {
+        let cone = ConeCollider::new([0, 0, 0], [0, 2, 0], 2.0);
+        let bounds = cone.bounding_box();
+        assert!(vectors_close(bounds.min_corner, Vector3::new(-2.0, 0.0, -2.0)));
+        assert!(vectors_close(bounds.max_corner, Vector3::new(2.0, 2.0, 2.0)));
+        let sphere = cone.bounding_sphere();
+        assert!(vectors_close(sphere.centre, Vector3::ZERO));
+        assert!((sphere.radius - 2.0).abs() < 1e-4);
+
+        // a tall cone has its sphere through the apex and the rim
+        let tall = ConeCollider::new([0, 0, 0], [0, 4, 0], 2.0);
+        let sphere = tall.bounding_sphere();
+        assert!(vectors_close(sphere.centre, Vector3::new(0.0, 1.5, 0.0)));
+        assert!((sphere.radius - 2.5).abs() < 1e-4);
+    }
+
+    #[test]
+    fn sphere_test() {
+        let cone = ConeCollider::new([0, 0, 0], [0, 2, 0], 2.0);
+        // the slope is at distance sqrt(2) / 2 from (1, 2)
+        assert!(cone.intersects(&BoundingSphere::new([1, 2, 0], 0.75)));
+        assert!(!cone.intersects(&BoundingSphere::new([1, 2, 0], 0.65)));
+        assert!(BoundingSphere::new([0.0, -0.9, 0.0], 1.0).intersects(&cone));
+        assert!(!BoundingSphere::new([0.0, 3.1, 0.0], 1.0).intersects(&cone));
+    }
+}
+
+
 ///////////////////////////////////////////////////////////////////////////////////////
 ////////////////////////////////// Plane //////////////////////////////////////////////
 ///////////////////////////////////////////////////////////////////////////////////////
@@ -118,15 +454,138 @@ mod plane_collider_tests {
         assert_eq!(hit.hit_position, [0, 0, 0].into());
         assert_eq!(hit.hit_distance, 5.0);
     }
+
+    #[test]
+    fn negative_side_test() {
+        let plane = PlaneCollider::new([0, 0, 0], [5, 5]);
+        assert!(plane.check_ray([-1, 5, 1], [0, -1, 0], None).is_none());
+        assert!(plane.check_ray([1, 5, -1], [0, -1, 0], None).is_none());
+    }
+}
+
+
+///////////////////////////////////////////////////////////////////////////////////////
+////////////////////////////////// infinite plane /////////////////////////////////////
+///////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod infinite_plane_tests {
+    use super::*;
+
+    #[test]
+    fn distance_test() {
+        let plane = Plane::new([0, 2, 0], 4.0);
+        assert_eq!(plane.normal, Vector3::Y);
+        assert_eq!(plane.distance, 2.0);
+
+        assert_eq!(plane.signed_distance([3, 5, 1]), 3.0);
+        assert_eq!(plane.signed_distance([3, -1, 1]), -3.0);
+        assert_eq!(plane.project_point([3, 5, 1]), Vector3::new(3.0, 2.0, 1.0));
+        assert_eq!(plane.reflect_point([3, 5, 1]), Vector3::new(3.0, -1.0, 1.0));
+        assert_eq!(plane.reflect_direction([1, -1, 0]), Vector3::new(1.0, 1.0, 0.0));
+        assert_eq!(plane.flipped().signed_distance([3, 5, 1]), -3.0);
+    }
+
+    #[test]
+    fn from_points_test() {
+        let plane = Plane::from_points([1, 0, 0], [0, 1, 0], [0, 0, 1]).unwrap();
+        assert!(vectors_close(plane.normal, Vector3::ONE.normalised()));
+        assert!((plane.distance - 1.0 / 3_f32.sqrt()).abs() < 1e-5);
+        // going round the other way flips the normal
+        let flipped = Plane::from_points([1, 0, 0], [0, 0, 1], [0, 1, 0]).unwrap();
+        assert!(vectors_close(flipped.normal, -plane.normal));
+
+        assert!(Plane::from_points([0, 0, 0], [1, 1, 1], [2, 2, 2]).is_none());
+    }
+
+    #[test]
+    fn intersect_planes_test() {
+        let x = Plane::new([1, 0, 0], 1.0);
+        let y = Plane::new([0, 1, 0], 2.0);
+        let slope = Plane::from_point_normal([0, 0, 3], [1, 1, 1]);
+        let point = Plane::intersect_planes(&x, &y, &slope).unwrap();
+        assert!(vectors_close(point, Vector3::new(1.0, 2.0, 0.0)));
+
+        assert!(Plane::intersect_planes(&x, &Plane::new([2, 0, 0], 5.0), &y).is_none());
+    }
+
+    #[test]
+    fn ray_test() {
+        let plane = Plane::from_point_normal([0, 1, 0], [1, 1, 0]);
+        let hit = plane.check_ray([5, 1, 0], -Vector3::X, None).unwrap();
+        assert!(vectors_close(hit.hit_position, Vector3::new(0.0, 1.0, 0.0)));
+        assert!((hit.hit_distance - 5.0).abs() < 1e-4);
+        assert!(vectors_close(hit.hit_normal, Vector3::new(1.0, 1.0, 0.0).normalised()));
+
+        // from behind the normal faces back at the ray
+        let hit = plane.check_ray([-5, 1, 0], Vector3::X, None).unwrap();
+        assert!(vectors_close(hit.hit_normal, -Vector3::new(1.0, 1.0, 0.0).normalised()));
+
+        assert!(plane.check_ray([5, 1, 0], Vector3::X, None).is_none());
+        assert!(plane.check_ray([5, 1, 0], -Vector3::X, Some(4.0)).is_none());
+        assert!(plane.check_ray([5, 1, 0], [1, -1, 0], None).is_none());
+    }
+}
+
+
+///////////////////////////////////////////////////////////////////////////////////////
+////////////////////////////////// quad ///////////////////////////////////////////////
+///////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod quad_collider_tests {
+    use super::*;
+    use std::f32::consts::FRAC_PI_2;
+
+    #[test]
+    fn flat_test() {
+        let quad = QuadCollider::new([0, 0, 0], [4, 2], Matrix3::IDENTITY);
+        let hit = quad.check_ray([1.5, 3.0, -0.5], -Vector3::Y, None).unwrap();
+        assert_eq!(hit.hit_distance, 3.0);
+        assert_eq!(hit.hit_normal, Vector3::Y);
+
+        // either side of the centre
+        assert!(quad.check_ray([-2.1, 3.0, 0.0], -Vector3::Y, None).is_none());
+        assert!(quad.check_ray([0.0, 3.0, -1.1], -Vector3::Y, None).is_none());
+        assert!(quad.check_ray([0.0, 3.0, 0.9], -Vector3::Y, None).is_some());
+    }
+
+    #[test]
+    fn rotated_test() {
+        // stood up to face along x
+        let quad = QuadCollider::new([1, 0, 0], [4, 2], Matrix3::from_angle_z(-FRAC_PI_2));
+        assert!(vectors_close(quad.get_normal(), Vector3::X));
+
+        let hit = quad.check_ray([5.0, 1.5, 0.5], -Vector3::X, None).unwrap();
+        assert!(vectors_close(hit.hit_position, Vector3::new(1.0, 1.5, 0.5)));
+        assert!((hit.hit_distance - 4.0).abs() < 1e-4);
+        assert!(vectors_close(hit.hit_normal, Vector3::X));
+
+        assert!(quad.check_ray([5.0, 2.1, 0.0], -Vector3::X, None).is_none());
+        assert!(quad.check_ray([5.0, 0.0, -1.1], -Vector3::X, None).is_none());
+        assert!(quad.check_ray([-5.0, -1.5, 0.0], Vector3::X, None).is_some());
+    }
+
+    #[test]
+    fn bounds_test() {
+        let quad = QuadCollider::new([1, 0, 0], [4, 2], Matrix3::from_angle_z(-FRAC_PI_2));
+        let bounds = quad.bounding_box();
+        assert!(vectors_close(bounds.min_corner, Vector3::new(1.0, -2.0, -1.0)));
+        assert!(vectors_close(bounds.max_corner, Vector3::new(1.0, 2.0, 1.0)));
+
+        assert!(quad.corners().iter().all(|corner| quad.contains_point(*corner)));
+        assert!(vectors_close(quad.closest_point([3, 5, 0]), Vector3::new(1.0, 2.0, 0.0)));
+    }
 }
 
+
 ///////////////////////////////////////////////////////////////////////////////////////
 ////////////////////////////////// Mesh ///////////////////////////////////////////////
 ///////////////////////////////////////////////////////////////////////////////////////
 
 #[cfg(test)]
 mod mesh_collider_tests {
-    use super::{MeshCollider, Collider};
+    use super::{MeshCollider, Collider, Vector3, sphere_mesh};
 
     #[test]
     fn miss_mesh_test() {
@@ -194,4 +653,805 @@ mod mesh_collider_tests {
 
         assert_eq!(hit.hit_position, [-0.5, 0.0, -0.5].into());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn nearest_hit_test() {
+        // the big triangle is hit first but its centre is further from the ray than the small one's
+        let mesh = MeshCollider::new(
+            vec![[-1, 2, -10].into(), [-1, 2, 10].into(), [19, 2, 0].into(), [-1, 0, -1].into(), [-1, 0, 1].into(), [1, 0, 0].into()],
+            vec![0, 1, 2, 3, 4, 5]
+        );
+
+        let hit = mesh.check_ray([0, 5, 0], [0, -1, 0], None).unwrap();
+        assert_eq!(hit.hit_distance, 3.0);
+        assert_eq!(hit.hit_position, [0, 2, 0].into());
+    }
+
+    #[test]
+    fn bvh_matches_linear_test() {
+        let mesh = sphere_mesh(3.0, 24, 32);
+        assert!(mesh.get_bvh_depth() > 1);
+        assert!(mesh.get_bvh_depth() < 32);
+
+        for i in 0..200 {
+            let angle = i as f32 * 0.731;
+            let root = Vector3::new(angle.cos() * 6.0, (i as f32 * 0.37).sin() * 4.0, angle.sin() * 6.0);
+            let direction = (Vector3::new((i as f32 * 0.13).sin(), (i as f32 * 0.29).cos(), 0.5) - root).normalised();
+
+            let linear = mesh.get_triangles().iter()
+                .filter_map(|tri| tri.check_ray(root, direction, None))
+                .min_by(|a, b| a.hit_distance.total_cmp(&b.hit_distance));
+            let hit = mesh.check_ray(root, direction, None);
+
+            match (hit, linear) {
+                (Some(hit), Some(linear)) => assert!((hit.hit_distance - linear.hit_distance).abs() < 1e-4),
+                (hit, linear) => assert_eq!(hit.is_some(), linear.is_some()),
+            }
+        }
+
+        // from the inside it hits the far wall
+        let hit = mesh.check_ray([0, 0, 0], [1.0, 0.1, 0.2], None).unwrap();
+        assert!((hit.hit_distance - 3.0).abs() < 0.1);
+        assert!(mesh.check_ray([0, 0, 0], [1.0, 0.1, 0.2], Some(2.0)).is_none());
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////
+////////////////////////////////// Intersections //////////////////////////////////////
+///////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod intersection_tests {
+    use super::*;
+
+    fn pyramid() -> MeshCollider {
+        MeshCollider::new(
+            vec![[-2, 0, -2].into(), [-2, 0, 2].into(), [2, 0, -2].into(), [2, 0, 2].into(), [0, 5, 0].into()],
+            vec![0, 3, 2, 3, 1, 0, 0, 4, 1, 1, 4, 3, 3, 4, 2, 2, 4, 0]
+        )
+    }
+
+    // checks both orders give the same answer
+    fn overlapping<A: Intersects<B>, B: Intersects<A>>(a: &A, b: &B) -> bool {
+        let result = a.intersects(b);
+        assert_eq!(result, b.intersects(a));
+        result
+    }
+
+    #[test]
+    fn sphere_tests() {
+        let sphere = BoundingSphere::new([0, 0, 0], 1.0);
+
+        assert!(overlapping(&sphere, &BoundingSphere::new([1.5, 0.0, 0.0], 0.5)));
+        assert!(!overlapping(&sphere, &BoundingSphere::new([1.6, 0.0, 0.0], 0.5)));
+        assert!(overlapping(&sphere, &AABoundingBox::new([0.5, 0.5, 0.5], [2, 2, 2])));
+        assert!(!overlapping(&sphere, &AABoundingBox::new([0.7, 0.7, 0.7], [2, 2, 2])));
+        assert!(overlapping(&sphere, &PlaneCollider::new([-5.0, 0.9, -5.0], [10, 10])));
+        assert!(!overlapping(&sphere, &PlaneCollider::new([-5.0, 1.1, -5.0], [10, 10])));
+    }
+
+    #[test]
+    fn sphere_triangle_test() {
+        let tri = TriangleCollider::new([0, 0, 0], [4, 0, 0], [0, 0, 4]);
+
+        // above the face, past an edge and past a corner
+        assert!(overlapping(&BoundingSphere::new([1, 1, 1], 1.0), &tri));
+        assert!(!overlapping(&BoundingSphere::new([1.0, 1.1, 1.0], 1.0), &tri));
+        assert!(overlapping(&BoundingSphere::new([2.5, 0.0, 2.5], 0.75), &tri));
+        assert!(!overlapping(&BoundingSphere::new([3.0, 0.0, 3.0], 1.0), &tri));
+        assert!(!overlapping(&BoundingSphere::new([-1, -1, -1], 1.5), &tri));
+        assert!(overlapping(&BoundingSphere::new([-1, -1, -1], 1.8), &tri));
+    }
+
+    #[test]
+    fn box_triangle_test() {
+        let tri = TriangleCollider::new([0, 0, 0], [4, 0, 0], [0, 4, 0]);
+
+        assert!(overlapping(&AABoundingBox::new([1, 1, -1], [2, 2, 1]), &tri));
+        // the box corner is past the hypotenuse, only the edge cross product axes separate these
+        assert!(!overlapping(&AABoundingBox::new([2.1, 2.1, -1.0], [3, 3, 1]), &tri));
+        assert!(!overlapping(&AABoundingBox::new([1.0, 1.0, 0.1], [2, 2, 1]), &tri));
+        // a box containing the whole triangle
+        assert!(overlapping(&AABoundingBox::new([-1, -1, -1], [5, 5, 1]), &tri));
+    }
+
+    #[test]
+    fn triangle_triangle_test() {
+        let tri = TriangleCollider::new([0, 0, 0], [4, 0, 0], [0, 0, 4]);
+
+        assert!(overlapping(&tri, &TriangleCollider::new([1, -1, 1], [1, 1, 1], [2, 1, 0])));
+        assert!(!overlapping(&tri, &TriangleCollider::new([1.0, 0.5, 1.0], [1, 1, 1], [2, 1, 0])));
+        // coplanar triangles
+        assert!(overlapping(&tri, &TriangleCollider::new([1, 0, 1], [5, 0, 1], [1, 0, 5])));
+        assert!(!overlapping(&tri, &TriangleCollider::new([3, 0, 3], [5, 0, 3], [3, 0, 5])));
+    }
+
+    #[test]
+    fn mesh_tests() {
+        let mesh = pyramid();
+
+        assert!(overlapping(&mesh, &BoundingSphere::new([0, 6, 0], 1.5)));
+        assert!(!overlapping(&mesh, &BoundingSphere::new([2, 4, 2], 1.0)));
+        assert!(overlapping(&mesh, &AABoundingBox::new([-3.0, -1.0, -3.0], [3.0, 0.5, 3.0])));
+        assert!(overlapping(&mesh, &PlaneCollider::new([-2, 2, -2], [4, 4])));
+        // the plane is small enough to fit entirely inside the pyramid without touching a face
+        assert!(!overlapping(&mesh, &PlaneCollider::new([-0.5, 1.0, -0.5], [1, 1])));
+
+        let other = MeshCollider::new(
+            vec![[0.0, 2.0, -0.5].into(), [0.0, 2.0, 0.5].into(), [4, 2, 0].into()],
+            vec![0, 1, 2]
+        );
+        assert!(overlapping(&mesh, &other));
+        let moved = MeshCollider::new(
+            vec![[3.0, 2.0, -0.5].into(), [3.0, 2.0, 0.5].into(), [7, 2, 0].into()],
+            vec![0, 1, 2]
+        );
+        assert!(!overlapping(&mesh, &moved));
+    }
+
+    #[test]
+    fn large_mesh_test() {
+        // thousands of triangles, checked against testing every one of them
+        let mesh = sphere_mesh(3.0, 48, 64);
+        assert!(mesh.get_triangles().len() > 6000);
+        for i in 0..100 {
+            let angle = i as f32 * 0.731;
+            let centre = Vector3::new(angle.cos(), (i as f32 * 0.37).sin(), angle.sin()).normalised() * (2.0 + (i % 5) as f32 * 0.5);
+            let sphere = BoundingSphere::new(centre, 0.3);
+            let linear = mesh.get_triangles().iter().any(|tri| tri.intersects(&sphere));
+            assert_eq!(overlapping(&mesh, &sphere), linear);
+        }
+
+        // inside without touching the surface, then sharing its poles
+        assert!(!overlapping(&mesh, &sphere_mesh(1.0, 24, 32)));
+        assert!(overlapping(&mesh, &sphere_mesh(3.0, 12, 16)));
+
+        let (a, b) = (AABoundingBox::new([0, 0, 0], [2, 2, 2]), AABoundingBox::new([1, -1, 1], [3, 1, 3]));
+        assert_eq!(a.overlap(b), AABoundingBox::new([1, 0, 1], [2, 1, 2]));
+    }
+}
+
+
+///////////////////////////////////////////////////////////////////////////////////////
+////////////////////////////////// GJK ////////////////////////////////////////////////
+///////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod gjk_tests {
+    use super::*;
+
+    fn cube(centre: Vector3, half_size: f32) -> ConvexHull {
+        let mut points = Vec::new();
+        for corner in AABoundingBox::new(-Vector3::ONE * half_size, Vector3::ONE * half_size).corners() {
+            points.push(corner + centre);
+        }
+        ConvexHull::new(points)
+    }
+
+    #[test]
+    fn separated_test() {
+        let sphere = BoundingSphere::new([0, 0, 0], 1.0);
+        let bounds = AABoundingBox::new([3, -1, -1], [5, 1, 1]);
+
+        assert!(!gjk_intersects(&sphere, &bounds));
+        assert!(epa_contact_manifold(&sphere, &bounds).is_none());
+        let closest = gjk_closest_points(&sphere, &bounds).unwrap();
+        assert!((closest.distance - 2.0).abs() < 1e-3);
+        assert!(vectors_within(closest.point_a, Vector3::X, 1e-3));
+        assert!(vectors_within(closest.point_b, Vector3::X * 3.0, 1e-3));
+
+        // separated along a diagonal, so the closest point on the box is a corner
+        let closest = gjk_closest_points(&cube(Vector3::ZERO, 1.0), &cube(Vector3::ONE * 3.0, 1.0)).unwrap();
+        assert!((closest.distance - 3_f32.sqrt()).abs() < 1e-4);
+        assert!(vectors_within(closest.point_b, Vector3::ONE * 2.0, 1e-4));
+    }
+
+    #[test]
+    fn touching_test() {
+        let a = AABoundingBox::new([0, 0, 0], [1, 1, 1]);
+        let b = AABoundingBox::new([1, 0, 0], [2, 1, 1]);
+
+        assert!(gjk_intersects(&a, &b));
+        let contact = epa_contact_manifold(&a, &b).unwrap();
+        assert!(contact.depth.abs() < 1e-4);
+
+        let spheres = (BoundingSphere::new([0, 0, 0], 1.0), BoundingSphere::new([2, 0, 0], 1.0));
+        assert!(gjk_intersects(&spheres.0, &spheres.1));
+        assert!(epa_contact_manifold(&spheres.0, &spheres.1).unwrap().depth < 1e-3);
+    }
+
+    #[test]
+    fn sphere_penetration_test() {
+        let a = BoundingSphere::new([0, 0, 0], 1.0);
+        let b = BoundingSphere::new([1.5, 0.0, 0.0], 1.0);
+        let contact = epa_contact_manifold(&a, &b).unwrap();
+
+        assert!((contact.depth - 0.5).abs() < 1e-2);
+        assert!(vectors_within(contact.normal, Vector3::X, 1e-2));
+        assert_eq!(contact.points.len(), 1);
+        assert!(vectors_within(contact.points[0], Vector3::X * 0.75, 1e-2));
+    }
+
+    #[test]
+    fn deep_penetration_test() {
+        // the small box is mostly inside the big one, it is shallowest out of the top
+        let big = AABoundingBox::new([-5, -5, -5], [5, 5, 5]);
+        let small = cube(Vector3::new(0.0, 4.5, 0.0), 1.0);
+        let contact = epa_contact_manifold(&big, &small).unwrap();
+
+        assert!((contact.depth - 1.5).abs() < 1e-3);
+        assert!(vectors_within(contact.normal, Vector3::Y, 1e-3));
+
+        // a box resting into a face gives a contact for each corner
+        let resting = cube(Vector3::new(0.0, 5.9, 0.0), 1.0);
+        let contact = epa_contact_manifold(&big, &resting).unwrap();
+        assert!((contact.depth - 0.1).abs() < 1e-3);
+        assert_eq!(contact.points.len(), 4);
+        assert!(contact.points.iter().all(|point| (point.y - 4.9).abs() < 1e-3));
+    }
+
+    #[test]
+    fn triangle_test() {
+        let tri = TriangleCollider::new([0, 0, 0], [4, 0, 0], [0, 0, 4]);
+        let sphere = BoundingSphere::new([1.0, 0.8, 1.0], 1.0);
+        let contact = epa_contact_manifold(&sphere, &tri).unwrap();
+
+        assert!((contact.depth - 0.2).abs() < 1e-2);
+        assert!(vectors_within(contact.normal, -Vector3::Y, 1e-2));
+        assert!(!gjk_intersects(&BoundingSphere::new([1.0, 1.1, 1.0], 1.0), &tri));
+    }
+}
+
+
+///////////////////////////////////////////////////////////////////////////////////////
+////////////////////////////////// collision world ////////////////////////////////////
+///////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod collision_world_tests {
+    use super::*;
+    use std::{rc::Rc, cell::Cell};
+
+    // a 10 by 10 by 10 grid of spheres 2 apart
+    fn sphere_grid(radius: f32) -> (CollisionWorld, Vec<(ColliderHandle, BoundingSphere)>) {
+        let mut world = CollisionWorld::new();
+        let mut spheres = Vec::new();
+        for x in 0..10 {
+            for y in 0..10 {
+                for z in 0..10 {
+                    let sphere = BoundingSphere::new([x as f32 * 2.0, y as f32 * 2.0, z as f32 * 2.0], radius);
+                    spheres.push((world.insert(sphere), sphere));
+                }
+            }
+        }
+        (world, spheres)
+    }
+
+    #[test]
+    fn insert_remove_test() {
+        let mut world = CollisionWorld::new();
+        let a = world.insert(BoundingSphere::new([0, 0, 0], 1.0));
+        let b = world.insert(AABoundingBox::new([5, 5, 5], [6, 6, 6]));
+        assert_eq!(world.len(), 2);
+        assert_eq!(world.get(b).unwrap().bounds(), AABoundingBox::new([5, 5, 5], [6, 6, 6]));
+
+        assert!(world.remove(a).is_some());
+        assert!(world.remove(a).is_none());
+        assert!(!world.contains(a));
+
+        // the space is reused but the old handle still doesn't work
+        let c = world.insert(CapsuleCollider::new([0, 0, 0], [0, 1, 0], 0.5));
+        assert!(world.contains(c));
+        assert!(!world.contains(a));
+        assert!(world.get(a).is_none());
+        assert_eq!(world.handles().count(), 2);
+
+        world.remove(b);
+        world.remove(c);
+        assert!(world.is_empty());
+        assert_eq!(world.tree_height(), 0);
+        assert!(world.raycast([0, 5, 0], [0, -1, 0], None).is_none());
+    }
+
+    #[test]
+    fn balance_test() {
+        // inserting in a line is the worst case for an unbalanced tree
+        let mut world = CollisionWorld::new();
+        for i in 0..1000 {
+            world.insert(BoundingSphere::new([i as f32, 0.0, 0.0], 0.25));
+        }
+        assert!(world.tree_height() < 25);
+    }
+
+    #[test]
+    fn query_test() {
+        let (world, spheres) = sphere_grid(0.5);
+        let area = AABoundingBox::new([1.2, -1.0, 3.0], [6.1, 2.6, 3.6]);
+
+        let mut found = world.query_box(area);
+        found.sort();
+        let mut expected: Vec<ColliderHandle> = spheres.iter().filter(|(_, sphere)| sphere.intersects(&area)).map(|(handle, _)| *handle).collect();
+        expected.sort();
+        assert_eq!(found, expected);
+        assert_eq!(found.len(), 3 * 2);
+
+        let query = BoundingSphere::new([9.0, 9.0, 9.0], 2.0);
+        let found = world.query_sphere(query);
+        assert!(!found.is_empty());
+        for (handle, sphere) in &spheres {
+            // the query checks bounds, so can give more than the true overlaps but never less
+            if sphere.intersects(&query) {assert!(found.contains(handle));}
+        }
+    }
+
+    #[test]
+    fn pairs_test() {
+        let (world, _) = sphere_grid(0.5);
+        assert!(world.overlapping_pairs().is_empty());
+
+        // the boxes around the spheres touch every neighbour, including diagonally
+        let (world, spheres) = sphere_grid(1.0);
+        let pairs = world.overlapping_pairs();
+        assert_eq!(pairs.len(), (28 * 28 * 28 - 10 * 10 * 10) / 2);
+        assert!(pairs.iter().all(|(a, b)| a < b));
+        let (a, b) = (spheres[0].0, spheres[1].0);
+        assert!(pairs.contains(&(a, b)));
+    }
+
+    #[test]
+    fn raycast_test() {
+        let (world, spheres) = sphere_grid(0.5);
+        let hits = world.raycast_all([-5, 0, 0], [1, 0, 0], None);
+        assert_eq!(hits.len(), 10);
+        assert!(hits.windows(2).all(|pair| pair[0].1.hit_distance <= pair[1].1.hit_distance));
+        assert_eq!(hits[0].0, spheres[0].0);
+        assert_eq!(hits[0].1.hit_distance, 4.5);
+
+        assert_eq!(world.raycast_all([-5, 0, 0], [1, 0, 0], Some(8.0)).len(), 2);
+        let (handle, hit) = world.raycast([2, 2, 30], [0, 0, -1], None).unwrap();
+        assert_eq!(handle, spheres[119].0);
+        assert_eq!(hit.hit_distance, 11.5);
+        assert!(world.raycast([1, 1, 30], [0, 0, -1], None).is_none());
+    }
+
+    // a sphere that counts how many times it has been checked against a ray
+    struct CountingSphere(BoundingSphere, Rc<Cell<usize>>);
+
+    impl WorldCollider for CountingSphere {
+        fn bounds(&self) -> AABoundingBox {
+            self.0.bounding_box()
+        }
+
+        fn check_ray_dyn(&self, root_position: Vector3, direction: Vector3, max_distance: Option<f32>) -> Option<RayHitInfo> {
+            self.1.set(self.1.get() + 1);
+            self.0.check_ray(root_position, direction, max_distance)
+        }
+    }
+
+    #[test]
+    fn raycast_nearest_test() {
+        let checks = Rc::new(Cell::new(0));
+        let mut world = CollisionWorld::new();
+        for x in 0..200 {
+            world.insert(CountingSphere(BoundingSphere::new([x as f32 * 2.0, 0.0, 0.0], 0.5), checks.clone()));
+        }
+
+        // only the spheres near the front of the row are checked
+        let (_, hit) = world.raycast([-5, 0, 0], [1, 0, 0], None).unwrap();
+        assert_eq!(hit.hit_distance, 4.5);
+        assert!(checks.get() < 10);
+        checks.set(0);
+        assert_eq!(world.raycast_all([-5, 0, 0], [1, 0, 0], None).len(), 200);
+        assert_eq!(checks.get(), 200);
+
+        // gives the same as the first of all the hits
+        let (world, _) = sphere_grid(0.7);
+        let mut rng = ChaChaRng::seed_from_u64(3);
+        for _ in 0..200 {
+            let origin = Vector3::new(rng.gen_range(-5.0..25.0), rng.gen_range(-5.0..25.0), rng.gen_range(-5.0..25.0));
+            let direction = Vector3::new(rng.gen_range(-1.0..1.0), rng.gen_range(-1.0..1.0), rng.gen_range(-1.0..1.0));
+            let max_distance = if rng.gen_bool(0.5) {Some(rng.gen_range(1.0..20.0))} else {None};
+            let nearest = world.raycast(origin, direction, max_distance);
+            let first = world.raycast_all(origin, direction, max_distance).into_iter().next();
+            assert_eq!(nearest.map(|(_, hit)| hit.hit_distance), first.map(|(_, hit)| hit.hit_distance));
+        }
+    }
+
+    #[test]
+    fn update_test() {
+        let mut world = CollisionWorld::new();
+        let moving = world.insert(BoundingSphere::new([0, 0, 0], 1.0));
+        let still = world.insert(BoundingSphere::new([10, 0, 0], 1.0));
+        assert!(world.overlapping_pairs().is_empty());
+
+        // small moves stay within the margin
+        assert!(world.update(moving, BoundingSphere::new([0.05, 0.0, 0.0], 1.0)));
+        assert_eq!(world.get(moving).unwrap().bounds().min_corner.x, -0.95);
+
+        for i in 1..=9 {
+            world.update(moving, BoundingSphere::new([i as f32, 0.0, 0.0], 1.0));
+        }
+        assert_eq!(world.overlapping_pairs(), vec![(moving, still)]);
+        assert_eq!(world.raycast([20, 0, 0], [-1, 0, 0], None).unwrap().0, still);
+        assert_eq!(world.query_box(AABoundingBox::new([7.5, -1.0, -1.0], [8.5, 1.0, 1.0])), vec![moving]);
+
+        world.remove(moving);
+        assert!(!world.update(moving, BoundingSphere::new([0, 0, 0], 1.0)));
+    }
+}
+
+
+///////////////////////////////////////////////////////////////////////////////////////
+////////////////////////////////// ray ////////////////////////////////////////////////
+///////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod ray_tests {
+    use super::*;
+
+    // a closed box made of 12 triangles facing outwards
+    fn cube_mesh() -> MeshCollider {
+        let vertices = AABoundingBox::new([-1, -1, -1], [1, 1, 1]).corners().to_vec();
+        MeshCollider::new(vertices, vec![
+            0, 2, 1, 1, 2, 3, // -z
+            4, 5, 6, 5, 7, 6, // +z
+            0, 1, 4, 1, 5, 4, // -y
+            2, 6, 3, 3, 6, 7, // +y
+            0, 4, 2, 2, 4, 6, // -x
+            1, 3, 5, 3, 7, 5, // +x
+        ])
+    }
+
+    #[test]
+    fn ray_test() {
+        let ray = Ray::new([1, 2, 3], [0, 0, 5]);
+        assert_eq!(ray.direction, Vector3::Z);
+        assert_eq!(ray.at(2.0), Vector3::new(1.0, 2.0, 5.0));
+        assert_eq!(ray.max_distance(), None);
+        assert_eq!(ray.inverse_direction.z, 1.0);
+
+        let ranged = Ray::with_range([0, 0, 0], [1, 0, 0], 1.0, 4.0);
+        assert_eq!(ranged.max_distance(), Some(4.0));
+        assert!(ranged.contains_distance(2.5));
+        assert!(!ranged.contains_distance(0.5));
+    }
+
+    #[test]
+    fn box_distance_test() {
+        let bounds = AABoundingBox::new([2, -1, -1], [4, 1, 1]);
+        assert_eq!(Ray::new([0, 0, 0], [1, 0, 0]).box_distance(&bounds), Some(2.0));
+        assert_eq!(Ray::new([3, 0, 0], [1, 0, 0]).box_distance(&bounds), Some(0.0));
+        assert_eq!(Ray::with_range([0, 0, 0], [1, 0, 0], 2.5, 10.0).box_distance(&bounds), Some(2.5));
+        assert!(Ray::with_range([0, 0, 0], [1, 0, 0], 0.0, 1.5).box_distance(&bounds).is_none());
+        assert!(Ray::new([0, 0, 0], [-1, 0, 0]).box_distance(&bounds).is_none());
+        assert!(Ray::new([0, 2, 0], [1, 0, 0]).box_distance(&bounds).is_none());
+    }
+
+    #[test]
+    fn cast_ray_test() {
+        let sphere = BoundingSphere::new([0, 0, 0], 1.0);
+        let hit = sphere.cast_ray(&Ray::new([-5, 0, 0], [1, 0, 0])).unwrap();
+        assert_eq!(hit.hit_distance, 4.0);
+
+        // starting the range past the near side puts the start inside
+        let hit = sphere.cast_ray(&Ray::with_range([-5, 0, 0], [1, 0, 0], 5.0, 10.0)).unwrap();
+        assert_eq!(hit.hit_distance, 5.0);
+        assert!(sphere.cast_ray(&Ray::with_range([-5, 0, 0], [1, 0, 0], 0.0, 3.0)).is_none());
+        assert_eq!(sphere.cast_all(&Ray::new([-5, 0, 0], [1, 0, 0])).len(), 1);
+    }
+
+    #[test]
+    fn mesh_cast_all_test() {
+        let mesh = cube_mesh();
+        let hits = mesh.cast_all(&Ray::new([-5.0, 0.2, 0.3], [1, 0, 0]));
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].hit_distance, 4.0);
+        assert_eq!(hits[1].hit_distance, 6.0);
+
+        assert_eq!(mesh.cast_all(&Ray::with_range([-5.0, 0.2, 0.3], [1, 0, 0], 5.0, 10.0)).len(), 1);
+        assert_eq!(mesh.cast_ray(&Ray::with_range([-5.0, 0.2, 0.3], [1, 0, 0], 5.0, 10.0)).unwrap().hit_distance, 6.0);
+        assert!(mesh.cast_all(&Ray::new([-5.0, 1.2, 0.3], [1, 0, 0])).is_empty());
+    }
+
+    #[test]
+    fn back_face_culling_test() {
+        let mut tri = TriangleCollider::new([0, 0, 0], [0, 0, 1], [1, 0, 0]);
+        assert_eq!(tri.get_normal(), Vector3::Y);
+        assert!(tri.check_ray([0.2, -5.0, 0.2], [0, 1, 0], None).is_some());
+
+        tri.set_back_face_culling(true);
+        assert!(tri.check_ray([0.2, -5.0, 0.2], [0, 1, 0], None).is_none());
+        assert!(tri.check_ray([0.2, 5.0, 0.2], [0, -1, 0], None).is_some());
+
+        // with culling the far wall is seen from behind, so only the near wall is hit and nothing is hit from inside
+        let mut mesh = cube_mesh();
+        mesh.set_back_face_culling(true);
+        let hits = mesh.cast_all(&Ray::new([-5.0, 0.2, 0.3], [1, 0, 0]));
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].hit_distance, 4.0);
+        assert!(mesh.check_ray([0.0, 0.2, 0.3], [1, 0, 0], None).is_none());
+    }
+}
+
+
+///////////////////////////////////////////////////////////////////////////////////////
+////////////////////////////////// shape casts ////////////////////////////////////////
+///////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod shape_cast_tests {
+    use super::*;
+
+    fn close(a: f32, b: f32) -> bool {
+        (a - b).abs() < 1e-4
+    }
+
+    #[test]
+    fn sphere_sphere_test() {
+        let target = BoundingSphere::new([0, 0, 0], 1.0);
+        let hit = target.sphere_cast(BoundingSphere::new([-5, 0, 0], 1.0), [1, 0, 0], None).unwrap();
+        assert!(close(hit.hit_distance, 3.0));
+        assert!(vectors_close(hit.hit_normal, -Vector3::X));
+        assert!(vectors_close(hit.hit_position, -Vector3::X));
+        assert!(vectors_close(hit.shape_position, Vector3::new(-2.0, 0.0, 0.0)));
+
+        assert!(target.sphere_cast(BoundingSphere::new([-5, 0, 0], 1.0), [1, 0, 0], Some(2.0)).is_none());
+        assert!(target.sphere_cast(BoundingSphere::new([-5, 0, 0], 1.0), [-1, 0, 0], None).is_none());
+        assert!(target.sphere_cast(BoundingSphere::new([-5.0, 2.1, 0.0], 1.0), [1, 0, 0], None).is_none());
+
+        let touching = target.sphere_cast(BoundingSphere::new([1.5, 0.0, 0.0], 1.0), [1, 0, 0], None).unwrap();
+        assert_eq!(touching.hit_distance, 0.0);
+        assert_eq!(touching.hit_normal, Vector3::ZERO);
+    }
+
+    #[test]
+    fn sphere_box_test() {
+        let target = AABoundingBox::new([-1, -1, -1], [1, 1, 1]);
+        let hit = target.sphere_cast(BoundingSphere::new([-5.0, 0.2, 0.0], 0.5), [1, 0, 0], None).unwrap();
+        assert!(close(hit.hit_distance, 3.5));
+        assert!(vectors_close(hit.hit_normal, -Vector3::X));
+        assert!(vectors_close(hit.hit_position, Vector3::new(-1.0, 0.2, 0.0)));
+
+        // over the top of the face, only touching the edge
+        let hit = target.sphere_cast(BoundingSphere::new([-5.0, 1.3, 0.0], 0.5), [1, 0, 0], None).unwrap();
+        assert!(close(hit.hit_distance, 3.6));
+        assert!(vectors_close(hit.hit_normal, Vector3::new(-0.8, 0.6, 0.0)));
+        assert!(vectors_close(hit.hit_position, Vector3::new(-1.0, 1.0, 0.0)));
+
+        // a ray grown by the radius would hit the corner of this but the sphere misses
+        assert!(target.sphere_cast(BoundingSphere::new([-5.0, 1.4, 1.4], 0.5), [1, 0, 0], None).is_none());
+        assert!(target.sphere_cast(BoundingSphere::new([-5.0, 0.2, 0.0], 0.5), [1, 0, 0], Some(3.0)).is_none());
+        assert_eq!(target.sphere_cast(BoundingSphere::new([-1.2, 0.0, 0.0], 0.5), [0, 1, 0], None).unwrap().hit_distance, 0.0);
+    }
+
+    #[test]
+    fn sphere_triangle_test() {
+        let tri = TriangleCollider::new([0, 0, 0], [0, 1, 0], [0, 0, 1]);
+        let hit = tri.sphere_cast(BoundingSphere::new([-5.0, 0.2, 0.3], 0.25), [1, 0, 0], None).unwrap();
+        assert!(close(hit.hit_distance, 4.75));
+        assert!(vectors_close(hit.hit_normal, -Vector3::X));
+        assert!(vectors_close(hit.hit_position, Vector3::new(0.0, 0.2, 0.3)));
+
+        // the centre passes beside the triangle so a ray would miss, but the sphere clips the edge
+        let centre = Vector3::new(-5.0, -0.2, 0.3);
+        assert!(tri.check_ray(centre, [1, 0, 0], None).is_none());
+        let hit = tri.sphere_cast(BoundingSphere::new(centre, 0.25), [1, 0, 0], None).unwrap();
+        assert!(close(hit.hit_distance, 4.85));
+        assert!(vectors_close(hit.hit_normal, Vector3::new(-0.6, -0.8, 0.0)));
+        assert!(vectors_close(hit.hit_position, Vector3::new(0.0, 0.0, 0.3)));
+
+        // from the other side the normal faces the other way
+        let hit = tri.sphere_cast(BoundingSphere::new([5.0, 0.2, 0.3], 0.25), [-1, 0, 0], None).unwrap();
+        assert!(vectors_close(hit.hit_normal, Vector3::X));
+    }
+
+    #[test]
+    fn box_cast_test() {
+        let moving = AABoundingBox::new([-5.5, -0.5, -0.5], [-4.5, 0.5, 0.5]);
+
+        let hit = AABoundingBox::new([-1, -1, -1], [1, 1, 1]).box_cast(moving, [1, 0, 0], None).unwrap();
+        assert!(close(hit.hit_distance, 3.5));
+        assert!(vectors_close(hit.hit_normal, -Vector3::X));
+        assert!(vectors_close(hit.shape_position, Vector3::new(-1.5, 0.0, 0.0)));
+        assert!(close(hit.hit_position.x, -1.0));
+
+        let hit = BoundingSphere::new([0, 0, 0], 1.0).box_cast(moving, [1, 0, 0], None).unwrap();
+        assert!(close(hit.hit_distance, 3.5));
+        assert!(vectors_close(hit.hit_normal, -Vector3::X));
+        assert!(vectors_close(hit.hit_position, -Vector3::X));
+
+        let tri = TriangleCollider::new([0, 0, 0], [0, 1, 0], [0, 0, 1]);
+        let hit = tri.box_cast(moving, [1, 0, 0], None).unwrap();
+        assert!(close(hit.hit_distance, 4.5));
+        assert!(vectors_close(hit.hit_normal, -Vector3::X));
+        // a box under the sloped edge misses, even though its bounds overlap the triangle's
+        let under = AABoundingBox::new([-5.5, 0.6, 0.6], [-5.3, 0.8, 0.8]);
+        assert!(tri.box_cast(under, [1, 0, 0], None).is_none());
+        assert!(tri.box_cast(moving, [1, 0, 0], Some(4.0)).is_none());
+    }
+
+    #[test]
+    fn plane_test() {
+        let plane = PlaneCollider::new([0, 0, 0], [4, 4]);
+        let hit = plane.sphere_cast(BoundingSphere::new([1, 5, 1], 0.5), [0, -1, 0], None).unwrap();
+        assert!(close(hit.hit_distance, 4.5));
+        assert!(vectors_close(hit.hit_normal, Vector3::Y));
+
+        let hit = plane.box_cast(AABoundingBox::new([1, 4, 1], [2, 5, 2]), [0, -1, 0], None).unwrap();
+        assert!(close(hit.hit_distance, 4.0));
+        assert!(vectors_close(hit.hit_normal, Vector3::Y));
+        assert!(plane.sphere_cast(BoundingSphere::new([-1, 5, 1], 0.5), [0, -1, 0], None).is_none());
+    }
+
+    #[test]
+    fn mesh_test() {
+        // a thin wall that a fast sphere would step straight over
+        let wall = MeshCollider::new(
+            vec![[0, -2, -2].into(), [0, 2, -2].into(), [0, -2, 2].into(), [0, 2, 2].into()],
+            vec![0, 1, 2, 2, 1, 3]
+        );
+        let hit = wall.sphere_cast(BoundingSphere::new([-5, 0, 0], 0.5), [1, 0, 0], Some(10.0)).unwrap();
+        assert!(close(hit.hit_distance, 4.5));
+        assert!(vectors_close(hit.hit_normal, -Vector3::X));
+
+        let hit = wall.box_cast(AABoundingBox::new([-6, -1, -1], [-5, 1, 1]), [1, 0, 0], None).unwrap();
+        assert!(close(hit.hit_distance, 5.0));
+        assert!(wall.sphere_cast(BoundingSphere::new([-5, 0, 0], 0.5), [1, 0, 0], Some(4.0)).is_none());
+        assert!(wall.sphere_cast(BoundingSphere::new([-5, 3, 0], 0.5), [1, 0, 0], None).is_none());
+    }
+}
+
+
+///////////////////////////////////////////////////////////////////////////////////////
+////////////////////////////////// point queries //////////////////////////////////////
+///////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod point_query_tests {
+    use super::*;
+
+    // works for any collider through the trait
+    fn distance(shape: &impl PointQuery, point: Vector3) -> f32 {
+        shape.distance_to_point(point)
+    }
+
+    #[test]
+    fn triangle_regions_test() {
+        let tri = TriangleCollider::new([0, 0, 0], [2, 0, 0], [0, 0, 2]);
+        // the face, each vertex and each edge
+        assert!(vectors_close(PointQuery::closest_point(&tri, [0.5, 3.0, 0.5]), Vector3::new(0.5, 0.0, 0.5)));
+        assert!(vectors_close(PointQuery::closest_point(&tri, [-1, 0, -1]), Vector3::ZERO));
+        assert!(vectors_close(PointQuery::closest_point(&tri, [3, 1, -1]), Vector3::new(2.0, 0.0, 0.0)));
+        assert!(vectors_close(PointQuery::closest_point(&tri, [1, 0, -1]), Vector3::new(1.0, 0.0, 0.0)));
+        assert!(vectors_close(PointQuery::closest_point(&tri, [2, 0, 2]), Vector3::new(1.0, 0.0, 1.0)));
+
+        assert_eq!(distance(&tri, Vector3::new(0.5, 3.0, 0.5)), 3.0);
+        assert!(PointQuery::contains_point(&tri, [0.5, 0.0, 0.5]));
+        assert!(!PointQuery::contains_point(&tri, [0.5, 0.1, 0.5]));
+    }
+
+    #[test]
+    fn solid_test() {
+        let bounds = AABoundingBox::new([-1, -1, -1], [1, 1, 1]);
+        assert_eq!(distance(&bounds, Vector3::new(4.0, 5.0, 0.0)), 5.0);
+        assert_eq!(distance(&bounds, Vector3::new(0.5, 0.0, 0.0)), 0.0);
+
+        let sphere = BoundingSphere::new([1, 0, 0], 2.0);
+        assert!(vectors_close(PointQuery::closest_point(&sphere, [1, 5, 0]), Vector3::new(1.0, 2.0, 0.0)));
+        assert_eq!(distance(&sphere, Vector3::new(1.0, 5.0, 0.0)), 3.0);
+        assert!(PointQuery::contains_point(&sphere, [2, 1, 0]));
+        assert!(!PointQuery::contains_point(&sphere, [3, 1, 0]));
+
+        let capsule = CapsuleCollider::new([0, 0, 0], [0, 4, 0], 1.0);
+        assert!((distance(&capsule, Vector3::new(3.0, 2.0, 0.0)) - 2.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn plane_test() {
+        let plane = Plane::new([0, 1, 0], 2.0);
+        assert_eq!(PointQuery::closest_point(&plane, [3, 7, 1]), Vector3::new(3.0, 2.0, 1.0));
+        assert_eq!(distance(&plane, Vector3::new(3.0, -1.0, 1.0)), 3.0);
+        assert!(PointQuery::contains_point(&plane, [5, 2, 5]));
+
+        let bounded = PlaneCollider::new([0, 0, 0], [2, 2]);
+        assert_eq!(PointQuery::closest_point(&bounded, [3, 4, 1]), Vector3::new(2.0, 0.0, 1.0));
+        assert_eq!(distance(&bounded, Vector3::new(1.0, -3.0, 1.0)), 3.0);
+        assert!(PointQuery::contains_point(&bounded, [1, 0, 1]));
+        assert!(!PointQuery::contains_point(&bounded, [3, 0, 1]));
+    }
+
+    #[test]
+    fn mesh_test() {
+        // a pyramid with a square base
+        let mesh = MeshCollider::new(
+            vec![[-1, 0, -1].into(), [1, 0, -1].into(), [1, 0, 1].into(), [-1, 0, 1].into(), [0, 2, 0].into()],
+            vec![0, 1, 2, 0, 2, 3, 0, 4, 1, 1, 4, 2, 2, 4, 3, 3, 4, 0]
+        );
+        assert!(vectors_close(PointQuery::closest_point(&mesh, [0, 5, 0]), Vector3::new(0.0, 2.0, 0.0)));
+        assert!(vectors_close(PointQuery::closest_point(&mesh, [0.2, -3.0, 0.3]), Vector3::new(0.2, 0.0, 0.3)));
+        assert!((distance(&mesh, Vector3::new(5.0, 0.0, 0.0)) - 4.0).abs() < 1e-5);
+        // from inside the nearest point is on the nearest face
+        assert!((distance(&mesh, Vector3::new(0.0, 0.1, 0.0)) - 0.1).abs() < 1e-5);
+        assert!(PointQuery::contains_point(&mesh, [0.5, 1.0, 0.0]));
+        assert!(!PointQuery::contains_point(&mesh, [0.0, 1.0, 0.0]));
+
+        let empty = MeshCollider::new(Vec::new(), Vec::new());
+        assert_eq!(PointQuery::closest_point(&empty, [1, 2, 3]), Vector3::new(1.0, 2.0, 3.0));
+
+        // the hierarchy finds the same point as checking every triangle
+        let vertices: Vec<Vector3> = (0..400).map(|i| Vector3::new((i % 20) as f32, ((i * 7) % 5) as f32 * 0.3, (i / 20) as f32)).collect();
+        let mut indices = Vec::new();
+        for x in 0..19 {
+            for z in 0..19 {
+                let i = z * 20 + x;
+                indices.extend([i, i + 20, i + 1, i + 1, i + 20, i + 21]);
+            }
+        }
+        let terrain = MeshCollider::new(vertices, indices);
+        for i in 0..50 {
+            let point = Vector3::new((i as f32 * 1.37) % 22.0 - 1.0, (i as f32 * 0.7).sin() * 4.0, (i as f32 * 2.11) % 22.0 - 1.0);
+            let linear = terrain.get_triangles().iter().map(|tri| (tri.closest_point(point) - point).magnitude()).fold(f32::INFINITY, f32::min);
+            assert!((distance(&terrain, point) - linear).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn segment_segment_test() {
+        // crossing over each other
+        let (a, b) = closest_points_segment_segment([-1, 0, 0], [1, 0, 0], [0, 1, -1], [0, 1, 1]);
+        assert!(vectors_close(a, Vector3::ZERO));
+        assert!(vectors_close(b, Vector3::Y));
+
+        // past the end of one of them
+        let (a, b) = closest_points_segment_segment([-1, 0, 0], [1, 0, 0], [3, 1, -1], [3, 1, 1]);
+        assert!(vectors_close(a, Vector3::X));
+        assert!(vectors_close(b, Vector3::new(3.0, 1.0, 0.0)));
+
+        // parallel and overlapping
+        let (a, b) = closest_points_segment_segment([0, 0, 0], [2, 0, 0], [1, 1, 0], [3, 1, 0]);
+        assert!(((a - b).magnitude() - 1.0).abs() < 1e-5);
+
+        // one is a point
+        let (a, b) = closest_points_segment_segment([1, 2, 0], [1, 2, 0], [0, 0, 0], [2, 0, 0]);
+        assert!(vectors_close(a, Vector3::new(1.0, 2.0, 0.0)));
+        assert!(vectors_close(b, Vector3::X));
+    }
+
+    #[test]
+    fn segment_triangle_test() {
+        let tri = TriangleCollider::new([0, 0, 0], [2, 0, 0], [0, 0, 2]);
+
+        // going through the face
+        let (a, b) = closest_points_segment_triangle([0.5, -1.0, 0.5], [0.5, 1.0, 0.5], &tri);
+        assert!(vectors_close(a, Vector3::new(0.5, 0.0, 0.5)));
+        assert!(vectors_close(a, b));
+
+        // an end above the face
+        let (a, b) = closest_points_segment_triangle([0.5, 1.0, 0.5], [0.5, 3.0, 0.5], &tri);
+        assert!(vectors_close(a, Vector3::new(0.5, 1.0, 0.5)));
+        assert!(vectors_close(b, Vector3::new(0.5, 0.0, 0.5)));
+
+        // passing by the long edge
+        let (a, b) = closest_points_segment_triangle([2, -1, 2], [2, 1, 2], &tri);
+        assert!(vectors_close(a, Vector3::new(2.0, 0.0, 2.0)));
+        assert!(vectors_close(b, Vector3::new(1.0, 0.0, 1.0)));
+    }
+
+    #[test]
+    fn segment_triangle_culling_test() {
+        // back face culling is only for rays, the segment still goes through from either side
+        let mut tri = TriangleCollider::new([0, 0, 0], [2, 0, 0], [0, 0, 2]);
+        tri.set_back_face_culling(true);
+        for (start, end) in [([0.5, -1.0, 0.5], [0.5, 1.0, 0.5]), ([0.5, 1.0, 0.5], [0.5, -1.0, 0.5])] {
+            let (a, b) = closest_points_segment_triangle(start, end, &tri);
+            assert!(vectors_close(a, Vector3::new(0.5, 0.0, 0.5)));
+            assert!(vectors_close(a, b));
+        }
+
+        // slanted through the face, and ending exactly on it
+        let (a, b) = closest_points_segment_triangle([0.0, 1.0, 0.0], [1.0, -1.0, 1.0], &tri);
+        assert!(vectors_close(a, Vector3::new(0.5, 0.0, 0.5)) && vectors_close(a, b));
+        let (a, b) = closest_points_segment_triangle([0.25, 2.0, 0.25], [0.25, 0.0, 0.25], &tri);
+        assert!(vectors_close(a, Vector3::new(0.25, 0.0, 0.25)) && vectors_close(a, b));
+    }
+}
@@ -1,6 +1,14 @@
 use rust_maths::*;
 use std::f32::consts::PI;
 
+fn vectors_close(a: Vector3, b: Vector3) -> bool {
+    (a - b).magnitude() < 1e-4
+}
+
+fn slices_close(a: &[f32], b: &[f32]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).all(|(a, b)| (a - b).abs() < 1e-4)
+}
+
 ///////////////////////////////////////////////////////////////////////////////////////
 ////////////////////////////////// Matrix 3 ///////////////////////////////////////////
 ///////////////////////////////////////////////////////////////////////////////////////
@@ -146,6 +154,90 @@ mod matrix_tests {
 
 
 
+    #[test]
+    fn matrix3_symmetric_eigen_test() {
+        let mat = Matrix3::new(
+            2.0, -1.0, 0.0,
+            -1.0, 2.0, -1.0,
+            0.0, -1.0, 2.0
+        );
+        let (values, vectors) = mat.symmetric_eigen();
+        let expected = Vector3::new(2.0 - 2_f32.sqrt(), 2.0, 2.0 + 2_f32.sqrt());
+
+        assert!((values - expected).magnitude() < 1e-4);
+        for (value, vector) in [(values.x, vectors.c0()), (values.y, vectors.c1()), (values.z, vectors.c2())] {
+            assert!((mat * vector - vector * value).magnitude() < 1e-4);
+            assert!((vector.magnitude() - 1.0).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn covariance_principal_axis_test() {
+        let direction = Vector3::new(1.0, 2.0, 2.0) / 3.0;
+        let points: Vec<Vector3> = (-5..=5).map(|i| Vector3::new(1.0, 1.0, 1.0) + direction * i as f32).collect();
+        let (mean, covariance) = Matrix3::covariance(&points);
+        let (values, vectors) = covariance.symmetric_eigen();
+
+        assert!((mean - Vector3::ONE).magnitude() < 1e-4);
+        assert!(values.x.abs() < 1e-4 && values.y.abs() < 1e-4);
+        assert!((vectors.c2().dot(direction).abs() - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn matrix3_svd_test() {
+        let mat = Matrix3::new(
+            1.0, 2.0, 0.0,
+            0.0, 1.0, -1.0,
+            3.0, 0.0, 2.0
+        );
+        let (u, sigma, v) = mat.svd();
+        let product = u * Matrix3::new(sigma.x, 0.0, 0.0, 0.0, sigma.y, 0.0, 0.0, 0.0, sigma.z) * v.transposed();
+
+        assert!(sigma.x >= sigma.y && sigma.y >= sigma.z);
+        for (a, b) in [(product.x, mat.x), (product.y, mat.y), (product.z, mat.z)] {
+            assert!((a - b).magnitude() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn polar_decomposition_test() {
+        let rotation = Matrix3::from_euler_angles([0.3, 1.2, -0.4]);
+        let stretch = Matrix3::new(
+            2.0, 0.5, 0.0,
+            0.5, 1.0, 0.0,
+            0.0, 0.0, 3.0
+        );
+        let (found_rotation, found_stretch) = (rotation * stretch).polar_decomposition();
+
+        assert!((found_rotation.determinant() - 1.0).abs() < 1e-4);
+        for (a, b) in [(found_rotation.x, rotation.x), (found_rotation.y, rotation.y), (found_rotation.z, rotation.z)] {
+            assert!((a - b).magnitude() < 1e-4);
+        }
+        for (a, b) in [(found_stretch.x, stretch.x), (found_stretch.y, stretch.y), (found_stretch.z, stretch.z)] {
+            assert!((a - b).magnitude() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn try_inverted_test() {
+        let singular = Matrix3::new(
+            1.0, 2.0, 3.0,
+            2.0, 4.0, 6.0,
+            1.0, 0.0, 1.0
+        );
+        let singular4 = Matrix4::from_nonuniform_scale([1.0, 1.0, 0.0]);
+
+        assert!(singular.try_inverted(1e-6).is_none());
+        assert!(singular4.try_inverted(1e-6).is_none());
+        assert!(Matrix2::new(1.0, 2.0, 2.0, 4.0).try_inverted(1e-6).is_none());
+        assert!(Matrix3::from_angle_x(0.5).try_inverted(1e-6).is_some());
+        assert!(Matrix4::from_translation([1, 2, 3]).try_inverted(1e-6).is_some());
+
+        // the pseudo inverse still gives something sensible
+        let pseudo = singular4.pseudo_inverse();
+        assert_eq!(pseudo.transform_point([2, 3, 4]), Vector3::new(2.0, 3.0, 0.0));
+    }
+
     #[test]
     fn matrix_nm_tests() {
         let a = MaxtrixNM::new_from_items(2, 2, vec![6.0, -2.0, -3.0, 5.0]);
@@ -163,4 +255,436 @@ mod matrix_tests {
         // assert!(false)
     }
 
-}
\ No newline at end of file
+}
+
+///////////////////////////////////////////////////////////////////////////////////////
+////////////////////////////////// Matrix 4 ///////////////////////////////////////////
+///////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod matrix_four_tests {
+    use super::*;
+
+    #[test]
+    fn translation_test() {
+        let mat = Matrix4::from_translation([1.0, -2.0, 3.0]);
+
+        assert_eq!(mat.transform_point([1, 1, 1]), Vector3::new(2.0, -1.0, 4.0));
+        assert_eq!(mat.transform_vector([1, 1, 1]), Vector3::ONE);
+    }
+
+    #[test]
+    fn rotation_matches_matrix3_test() {
+        let rot = Matrix3::from_angle_and_axis(0.9, [1, 2, 3]);
+        let point = Vector3::new(4.0, -1.0, 0.5);
+
+        assert!(vectors_close(Matrix4::from_rotation(rot).transform_point(point), rot * point));
+    }
+
+    #[test]
+    fn composition_order_test() {
+        let scale = Matrix4::from_nonuniform_scale([2.0, 1.0, 1.0]);
+        let rotation = Matrix4::from_rotation(Matrix3::from_angle_z(PI / 2.0));
+        let translation = Matrix4::from_translation([0.0, 0.0, 5.0]);
+
+        // scales, then rotates, then translates
+        let transform = scale * rotation * translation;
+        assert!(vectors_close(transform.transform_point([1, 0, 0]), Vector3::new(0.0, 2.0, 5.0)));
+    }
+
+    #[test]
+    fn perspective_depth_test() {
+        let (near, far) = (0.5, 100.0);
+        let gl = Matrix4::persective_matrix(PI / 2.0, 1.0, near, far);
+        let reversed = Matrix4::reversed_z_perspective(PI / 2.0, 1.0, near, far, Handedness::Right);
+        let reversed_lh = Matrix4::reversed_z_perspective(PI / 2.0, 1.0, near, far, Handedness::Left);
+
+        assert!((gl.transform_point([0.0, 0.0, -near]).z + 1.0).abs() < 1e-4);
+        assert!((gl.transform_point([0.0, 0.0, -far]).z - 1.0).abs() < 1e-4);
+        assert!((reversed.transform_point([0.0, 0.0, -near]).z - 1.0).abs() < 1e-4);
+        assert!(reversed.transform_point([0.0, 0.0, -far]).z.abs() < 1e-4);
+        assert!((reversed_lh.transform_point([0.0, 0.0, near]).z - 1.0).abs() < 1e-4);
+        assert!(reversed_lh.transform_point([0.0, 0.0, far]).z.abs() < 1e-4);
+    }
+
+    #[test]
+    fn infinite_perspective_test() {
+        let near = 0.1;
+        let gl = Matrix4::infinite_perspective(PI / 3.0, 1.5, near, Handedness::Right, DepthRange::NegativeOneToOne);
+        let zo = Matrix4::infinite_perspective(PI / 3.0, 1.5, near, Handedness::Left, DepthRange::ZeroToOne);
+
+        assert!((gl.transform_point([0.0, 0.0, -near]).z + 1.0).abs() < 1e-4);
+        assert!(gl.transform_point([0.0, 0.0, -1e6]).z < 1.0);
+        assert!(zo.transform_point([0.0, 0.0, near]).z.abs() < 1e-4);
+        assert!((zo.transform_point([0.0, 0.0, 1e6]).z - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn orthographic_test() {
+        let gl = Matrix4::orthographic(-2.0, 2.0, -1.0, 1.0, 1.0, 11.0, Handedness::Right, DepthRange::NegativeOneToOne);
+        let zo = Matrix4::orthographic(0.0, 4.0, 0.0, 2.0, 1.0, 11.0, Handedness::Left, DepthRange::ZeroToOne);
+
+        assert!(vectors_close(gl.transform_point([2.0, -1.0, -1.0]), Vector3::new(1.0, -1.0, -1.0)));
+        assert!(vectors_close(gl.transform_point([0.0, 0.0, -11.0]), Vector3::new(0.0, 0.0, 1.0)));
+        assert!(vectors_close(zo.transform_point([0.0, 2.0, 1.0]), Vector3::new(-1.0, 1.0, 0.0)));
+        assert!(vectors_close(zo.transform_point([2.0, 1.0, 6.0]), Vector3::new(0.0, 0.0, 0.5)));
+    }
+
+    #[test]
+    fn look_at_test() {
+        let eye = Vector3::new(0.0, 0.0, 5.0);
+        let rh = Matrix4::look_at_rh(eye, Vector3::ZERO, Vector3::Y);
+        let lh = Matrix4::look_at_lh(eye, Vector3::ZERO, Vector3::Y);
+
+        assert!(vectors_close(rh.transform_point(eye), Vector3::ZERO));
+        assert!(vectors_close(rh.transform_point(Vector3::ZERO), Vector3::new(0.0, 0.0, -5.0)));
+        assert!(vectors_close(rh.transform_point([0, 1, 5]), Vector3::Y));
+        assert!(vectors_close(lh.transform_point(Vector3::ZERO), Vector3::new(0.0, 0.0, 5.0)));
+        assert!(vectors_close(lh.transform_point([0, 1, 5]), Vector3::Y));
+    }
+    #[test]
+    fn decompose_test() {
+        let translation = Vector3::new(3.0, -4.0, 10.0);
+        let angles = Vector3::new(0.3, -0.2, 1.1);
+        let scale = Vector3::new(2.0, 0.5, 3.0);
+        let transform = Matrix4::from_nonuniform_scale(scale) * Matrix4::from_rotation(Matrix3::from_euler_angles(angles)) * Matrix4::from_translation(translation);
+
+        let parts = transform.decompose().unwrap();
+        assert!(vectors_close(parts.translation, translation));
+        assert!(vectors_close(parts.scale, scale));
+        assert!(vectors_close(parts.euler_angles, angles));
+        assert!(!parts.is_reflection);
+    }
+
+    #[test]
+    fn decompose_reflection_test() {
+        let rotation = Matrix3::from_angle_y(0.6);
+        let transform = Matrix4::from_nonuniform_scale([1.0, -2.0, 1.0]) * Matrix4::from_rotation(rotation);
+
+        let parts = transform.decompose().unwrap();
+        assert!(parts.is_reflection);
+        assert!(parts.scale.x < 0.0);
+        assert!((parts.rotation.determinant() - 1.0).abs() < 1e-4);
+        assert!(vectors_close(parts.scale, Vector3::new(-1.0, 2.0, 1.0)));
+    }
+
+    #[test]
+    fn recompose_round_trip_test() {
+        let transforms = [
+            Matrix4::from_nonuniform_scale([1.5, 2.0, 0.25]) * Matrix4::from_rotation(Matrix3::from_angle_and_axis(2.0, [1, -1, 2])) * Matrix4::from_translation([7, 8, 9]),
+            Matrix4::from_nonuniform_scale([-1.0, 1.0, 1.0]) * Matrix4::from_translation([1, 0, 0]),
+            Matrix4::from_nonuniform_scale([-3.0, -1.0, -2.0]) * Matrix4::from_rotation(Matrix3::from_angle_x(-0.4)),
+        ];
+
+        for transform in transforms {
+            let recomposed = Matrix4::recompose(&transform.decompose().unwrap());
+            for point in [Vector3::ZERO, Vector3::X, Vector3::new(-2.0, 5.0, 1.0)] {
+                assert!(vectors_close(recomposed.transform_point(point), transform.transform_point(point)));
+            }
+        }
+    }
+
+    #[test]
+    fn decompose_projection_test() {
+        assert!(Matrix4::persective_matrix(PI / 2.0, 1.0, 0.1, 10.0).decompose().is_none());
+        assert!(Matrix4::from_nonuniform_scale([1.0, 0.0, 1.0]).decompose().is_none());
+    }
+}
+
+
+///////////////////////////////////////////////////////////////////////////////////////
+////////////////////////////////// Matrix N M /////////////////////////////////////////
+///////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod matrix_n_m_tests {
+    use super::*;
+
+    #[test]
+    fn transpose_test() {
+        let mat = MaxtrixNM::new_from_items(3, 2, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        let transposed = mat.transpose();
+
+        assert_eq!((transposed.width, transposed.height), (2, 3));
+        assert_eq!(transposed.get_row(2).unwrap(), vec![3.0, 6.0]);
+    }
+
+    #[test]
+    fn determinant_test() {
+        let mat = MaxtrixNM::new_from_items(4, 4, vec![
+            1.0, 2.0, 3.0, 4.0,
+            6.0, 7.0, 3.0, 4.0,
+            3.0, 2.0, 7.0, 9.0,
+            10.0, 11.0, 5.0, 4.0
+        ]);
+
+        assert!((mat.determinant().unwrap() - 130.0).abs() < 1e-3);
+        assert_eq!(MaxtrixNM::new_empty(3, 2).determinant().unwrap_err(), MatrixArithmeticError::NotSquare);
+    }
+
+    #[test]
+    fn inverse_test() {
+        let mat = MaxtrixNM::new_from_items(3, 3, vec![
+            0.0, 2.0, 1.0,
+            1.0, 1.0, 0.0,
+            3.0, -1.0, 4.0
+        ]);
+        let product = (mat.inverse().unwrap() * mat).unwrap();
+        let identity = MaxtrixNM::new_identity(3);
+
+        for row in 0..3 {
+            assert!(slices_close(&product.get_row(row).unwrap(), &identity.get_row(row).unwrap()));
+        }
+    }
+
+    #[test]
+    fn solve_test() {
+        // 2x + y - z = 8, -3x - y + 2z = -11, -2x + y + 2z = -3 has the solution (2, 3, -1)
+        let mat = MaxtrixNM::new_from_items(3, 3, vec![
+            2.0, 1.0, -1.0,
+            -3.0, -1.0, 2.0,
+            -2.0, 1.0, 2.0
+        ]);
+
+        assert!(slices_close(&mat.solve(&[8.0, -11.0, -3.0]).unwrap(), &[2.0, 3.0, -1.0]));
+        assert_eq!(mat.solve(&[1.0, 2.0]).unwrap_err(), MatrixArithmeticError::DimensionMismatch);
+    }
+
+    #[test]
+    fn lu_test() {
+        let mat = MaxtrixNM::new_from_items(3, 3, vec![
+            1.0, 2.0, 3.0,
+            4.0, 5.0, 6.0,
+            7.0, 8.0, 10.0
+        ]);
+        let lu = mat.lu().unwrap();
+        let product = (lu.l.clone() * lu.u.clone()).unwrap();
+
+        for row in 0..3 {
+            assert!(slices_close(&product.get_row(row).unwrap(), &mat.get_row(lu.permutation[row]).unwrap()));
+        }
+        assert!((lu.determinant() + 3.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn singular_test() {
+        let mat = MaxtrixNM::new_from_items(3, 3, vec![
+            1.0, 2.0, 3.0,
+            2.0, 4.0, 6.0,
+            1.0, 0.0, 1.0
+        ]);
+
+        assert_eq!(mat.solve(&[1.0, 2.0, 3.0]).unwrap_err(), MatrixArithmeticError::Singular);
+        assert_eq!(mat.inverse().unwrap_err(), MatrixArithmeticError::Singular);
+        assert!(mat.determinant().unwrap().abs() < 1e-5);
+    }
+
+    #[test]
+    fn rank_and_trace_test() {
+        let mat = MaxtrixNM::new_from_items(3, 3, vec![
+            1.0, 2.0, 3.0,
+            2.0, 4.0, 6.0,
+            1.0, 0.0, 1.0
+        ]);
+        let wide = MaxtrixNM::new_from_items(4, 2, vec![1.0, 0.0, 2.0, 0.0, 0.0, 1.0, 0.0, 3.0]);
+
+        assert_eq!(mat.rank(), 2);
+        assert_eq!(wide.rank(), 2);
+        assert_eq!(MaxtrixNM::new_identity(5).rank(), 5);
+        assert_eq!(MaxtrixNM::new_empty(3, 3).rank(), 0);
+        assert_eq!(mat.trace().unwrap(), 6.0);
+        assert_eq!(wide.trace().unwrap_err(), MatrixArithmeticError::NotSquare);
+    }
+    #[test]
+    fn qr_test() {
+        let mat = MaxtrixNM::new_from_items(3, 4, vec![
+            1.0, -1.0, 4.0,
+            1.0, 4.0, -2.0,
+            1.0, 4.0, 2.0,
+            1.0, -1.0, 0.0
+        ]);
+        let QRDecomposition {q, r} = mat.qr();
+        let product = (q.clone() * r.clone()).unwrap();
+        let orthogonal = (q.transpose() * q).unwrap();
+        let identity = MaxtrixNM::new_identity(4);
+
+        for row in 0..4 {
+            assert!(slices_close(&product.get_row(row).unwrap(), &mat.get_row(row).unwrap()));
+            assert!(slices_close(&orthogonal.get_row(row).unwrap(), &identity.get_row(row).unwrap()));
+            for col in 0..row.min(3) {
+                assert_eq!(r.get_element(col, row).unwrap(), 0.0);
+            }
+        }
+    }
+
+    #[test]
+    fn exact_polynomial_fit_test() {
+        // y = 2 - 3x + 0.5x^2
+        let xs = [-2.0, -1.0, 0.0, 1.0, 2.0, 3.0];
+        let ys: Vec<f32> = xs.iter().map(|x| 2.0 - 3.0 * x + 0.5 * x * x).collect();
+        let fit = polynomial_fit(&xs, &ys, 2).unwrap();
+
+        assert!(slices_close(&fit.solution, &[2.0, -3.0, 0.5]));
+        assert!(fit.residual_norm < 1e-4);
+    }
+
+    #[test]
+    fn line_fit_test() {
+        // the best line through (0, 1), (1, 3), (2, 4), (3, 4) is y = 1.5 + x
+        let fit = polynomial_fit(&[0.0, 1.0, 2.0, 3.0], &[1.0, 3.0, 4.0, 4.0], 1).unwrap();
+
+        assert!(slices_close(&fit.solution, &[1.5, 1.0]));
+        assert!((fit.residual_norm - 1.0).abs() < 1e-4);
+        assert!(fit.condition_estimate >= 1.0);
+    }
+
+    #[test]
+    fn underdetermined_test() {
+        // x + y + z = 3 has the smallest solution (1, 1, 1)
+        let mat = MaxtrixNM::new_from_items(3, 1, vec![1.0, 1.0, 1.0]);
+        let result = least_squares(&mat, &[3.0]).unwrap();
+
+        assert!(slices_close(&result.solution, &[1.0, 1.0, 1.0]));
+        assert!(result.residual_norm < 1e-4);
+    }
+
+    #[test]
+    fn rank_deficient_least_squares_test() {
+        let mat = MaxtrixNM::new_from_items(2, 3, vec![1.0, 2.0, 2.0, 4.0, 3.0, 6.0]);
+
+        assert_eq!(least_squares(&mat, &[1.0, 2.0, 3.0]).unwrap_err(), MatrixArithmeticError::Singular);
+        assert_eq!(least_squares(&mat, &[1.0, 2.0]).unwrap_err(), MatrixArithmeticError::DimensionMismatch);
+    }
+    #[test]
+    fn symmetric_eigen_test() {
+        let mat = MaxtrixNM::new_from_items(4, 4, vec![
+            4.0, 1.0, -2.0, 2.0,
+            1.0, 2.0, 0.0, 1.0,
+            -2.0, 0.0, 3.0, -2.0,
+            2.0, 1.0, -2.0, -1.0
+        ]);
+        let eigen = mat.symmetric_eigen().unwrap();
+
+        for i in 0..4 {
+            let vector = eigen.eigenvectors.get_column(i).unwrap();
+            let scaled: Vec<f32> = vector.iter().map(|val| val * eigen.eigenvalues[i]).collect();
+            assert!(slices_close(&mat.mul_vector(&vector).unwrap(), &scaled));
+            if i > 0 {
+                assert!(eigen.eigenvalues[i - 1] <= eigen.eigenvalues[i]);
+            }
+        }
+
+        let orthogonal = (eigen.eigenvectors.transpose() * eigen.eigenvectors.clone()).unwrap();
+        let identity = MaxtrixNM::new_identity(4);
+        for row in 0..4 {
+            assert!(slices_close(&orthogonal.get_row(row).unwrap(), &identity.get_row(row).unwrap()));
+        }
+        assert!((eigen.eigenvalues.iter().sum::<f32>() - mat.trace().unwrap()).abs() < 1e-4);
+    }
+    #[test]
+    fn svd_test() {
+        for mat in [
+            MaxtrixNM::new_from_items(3, 4, vec![2.0, 0.0, 1.0, -1.0, 3.0, 0.5, 4.0, 1.0, -2.0, 0.0, 0.0, 1.0]),
+            MaxtrixNM::new_from_items(4, 2, vec![1.0, 2.0, 3.0, 4.0, -1.0, 0.0, 2.0, 5.0]),
+        ] {
+            let svd = mat.svd();
+            let mut sigma = MaxtrixNM::new_empty(svd.singular_values.len(), svd.singular_values.len());
+            for (i, val) in svd.singular_values.iter().enumerate() {
+                sigma.matrix[i][i] = *val;
+                if i > 0 {
+                    assert!(svd.singular_values[i - 1] >= *val);
+                }
+            }
+            let product = ((svd.u.clone() * sigma).unwrap() * svd.v.transpose()).unwrap();
+            for row in 0..mat.height {
+                assert!(slices_close(&product.get_row(row).unwrap(), &mat.get_row(row).unwrap()));
+            }
+        }
+    }
+
+    #[test]
+    fn pseudo_inverse_test() {
+        // for a full column rank matrix the pseudo inverse is a left inverse
+        let tall = MaxtrixNM::new_from_items(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 7.0]);
+        let product = (tall.pseudo_inverse() * tall.clone()).unwrap();
+        let identity = MaxtrixNM::new_identity(2);
+        for row in 0..2 {
+            assert!(slices_close(&product.get_row(row).unwrap(), &identity.get_row(row).unwrap()));
+        }
+
+        // A A+ A = A even for singular matrices
+        let singular = MaxtrixNM::new_from_items(3, 3, vec![1.0, 2.0, 3.0, 2.0, 4.0, 6.0, 1.0, 0.0, 1.0]);
+        let round_trip = ((singular.clone() * singular.pseudo_inverse()).unwrap() * singular.clone()).unwrap();
+        for row in 0..3 {
+            assert!(slices_close(&round_trip.get_row(row).unwrap(), &singular.get_row(row).unwrap()));
+        }
+    }
+}
+
+
+
+///////////////////////////////////////////////////////////////////////////////////////
+////////////////////////////////// Matrix R C /////////////////////////////////////////
+///////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod matrix_r_c_tests {
+    use super::*;
+
+    #[test]
+    fn multiplication_test() {
+        let a = Matrix::new([
+            [1.0, 2.0, 3.0],
+            [4.0, 5.0, 6.0],
+        ]);
+        let b = Matrix::new([
+            [1.0, 0.0],
+            [0.0, 1.0],
+            [2.0, -1.0],
+        ]);
+        let product: Matrix<2, 2> = a * b;
+
+        assert_eq!(product, Matrix::new([[7.0, -1.0], [16.0, -1.0]]));
+        assert_eq!(a * VectorN::new([1.0, 1.0, 1.0]), VectorN::new([6.0, 15.0]));
+        assert_eq!(a.transposed().column(1), a.row(1));
+        assert_eq!(Matrix::<3, 3>::identity() * b, b);
+        assert_eq!(Matrix::from_columns([VectorN::new([1.0, 4.0]), VectorN::new([2.0, 5.0]), VectorN::new([3.0, 6.0])]), a);
+    }
+
+    #[test]
+    fn square_test() {
+        let mat = Matrix::new([
+            [2.0, 0.0, 1.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0, 3.0],
+            [1.0, 0.0, 2.0, 0.0, 0.0],
+            [0.0, 0.0, 0.0, 4.0, 0.0],
+            [0.0, 0.0, 0.0, 0.0, 1.0],
+        ]);
+
+        assert_eq!(mat.trace(), 10.0);
+        assert!((mat.determinant() - 12.0).abs() < 1e-4);
+        let product = mat * mat.inverse().unwrap();
+        for row in 0..5 {
+            assert!(slices_close(&product.rows[row], &Matrix::<5, 5>::identity().rows[row]));
+        }
+        assert!(Matrix::<3, 3>::ZERO.inverse().is_err());
+    }
+
+    #[test]
+    fn conversion_test() {
+        let mat3 = Matrix3::from_euler_angles([0.3, -0.2, 1.1]);
+        let generic = Matrix::from(mat3);
+        let vec = Vector3::new(1.0, 2.0, 3.0);
+
+        assert_eq!(Vector3::from(generic * VectorN::from(vec)), mat3 * vec);
+        assert_eq!(Matrix3::from(generic), mat3);
+
+        let mat4 = Matrix4::from_translation([1, 2, 3]);
+        assert_eq!(Matrix4::from(Matrix::from(mat4)), mat4);
+        assert_eq!(Matrix2::from(Matrix::<2, 2>::identity()), Matrix2::IDENTITY);
+
+        let dynamic = MaxtrixNM::from(generic);
+        assert_eq!(Matrix::<3, 3>::try_from(dynamic.clone()).unwrap(), generic);
+        assert!(Matrix::<2, 3>::try_from(dynamic).is_err());
+    }
+}
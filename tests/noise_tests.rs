@@ -0,0 +1,550 @@
+use rust_maths::*;
+
+// points spread evenly over -range..range on each axis, seeded so every run sees the same ones
+fn sample_points<const N: usize>(seed: u64, count: usize, range: f32) -> Vec<[f32; N]> {
+    let mut rng = ChaChaRng::seed_from_u64(seed);
+    (0..count).map(|_| [0; N].map(|_| rng.gen_range(-range..range))).collect()
+}
+
+
+///////////////////////////////////////////////////////////////////////////////////////
+////////////////////////////////// simplex ////////////////////////////////////////////
+///////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod simplex_noise_tests {
+    use super::*;
+
+    #[test]
+    fn default_matches_functions_test() {
+        let noise = SimplexNoise::default();
+        assert_eq!(noise.get_seed(), None);
+        for [x, y, z, _] in sample_points(42, 100, 500.0) {
+            assert_eq!(noise.sample2d(x, y), simplex2d(x, y));
+            assert_eq!(noise.sample3d(x, y, z), simplex3d(x, y, z));
+        }
+    }
+
+    #[test]
+    fn seeded_test() {
+        let (a, b, other) = (SimplexNoise::new(1234), SimplexNoise::new(1234), SimplexNoise::new(4321));
+        assert_eq!(a, b);
+        assert_eq!(a.get_seed(), Some(1234));
+
+        let mut differences = 0;
+        for [x, y, z, w] in sample_points(42, 100, 500.0) {
+            assert_eq!(a.sample2d(x, y), b.sample2d(x, y));
+            assert_eq!(a.sample3d(x, y, z), b.sample3d(x, y, z));
+            assert_eq!(a.sample4d(x, y, z, w), b.sample4d(x, y, z, w));
+            if a.sample3d(x, y, z) != other.sample3d(x, y, z) {differences += 1;}
+        }
+        assert!(differences > 90);
+    }
+
+    #[test]
+    fn range_test() {
+        let noise = SimplexNoise::new(99);
+        let (mut min, mut max) = (f32::MAX, f32::MIN);
+        for [x, y, z, w] in sample_points(42, 5000, 500.0) {
+            for val in [noise.sample2d(x, y), noise.sample3d(x, y, z), noise.sample4d(x, y, z, w)] {
+                assert!((-1.0..=1.0).contains(&val));
+                min = min.min(val);
+                max = max.max(val);
+            }
+        }
+        // the whole range is actually used
+        assert!(min < -0.5 && max > 0.5);
+    }
+
+    #[test]
+    fn lattice_test() {
+        // the noise is 0 at the corners of the grid
+        let noise = SimplexNoise::new(7);
+        assert_eq!(noise.sample2d(0.0, 0.0), 0.0);
+        assert_eq!(noise.sample4d(0.0, 0.0, 0.0, 0.0), 0.0);
+    }
+}
+
+
+///////////////////////////////////////////////////////////////////////////////////////
+////////////////////////////////// simplex 4d /////////////////////////////////////////
+///////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod simplex_4d_tests {
+    use super::*;
+
+    #[test]
+    fn range_test() {
+        let (mut min, mut max) = (f32::MAX, f32::MIN);
+        for [x, y, z, w] in sample_points(17, 20000, 500.0) {
+            let val = simplex4d(x, y, z, w);
+            assert!((-1.0..=1.0).contains(&val));
+            min = min.min(val);
+            max = max.max(val);
+        }
+        assert!(min < -0.6 && max > 0.6);
+        assert_eq!(simplex4d(3.0, 3.0, 3.0, 3.0) - SimplexNoise::default().sample4d(3.0, 3.0, 3.0, 3.0), 0.0);
+    }
+
+    #[test]
+    fn continuity_test() {
+        // a small step along any axis only makes a small change, even across simplex boundaries
+        let step = 1e-3;
+        for [x, y, z, w] in sample_points(17, 2000, 500.0) {
+            let val = simplex4d(x, y, z, w);
+            for offset in [Vector4::X, Vector4::Y, Vector4::Z, Vector4::W] {
+                let moved = Vector4::new(x, y, z, w) + offset * step;
+                assert!((simplex4d(moved.x, moved.y, moved.z, moved.w) - val).abs() < 0.05);
+            }
+        }
+    }
+
+    #[test]
+    fn time_axis_test() {
+        // moving along w animates the 3d noise smoothly without repeating it
+        let (x, y, z) = (12.3, -4.5, 6.7);
+        let frames: Vec<f32> = (0..100).map(|frame| simplex4d(x, y, z, frame as f32 * 0.01)).collect();
+        assert!(frames.windows(2).all(|pair| (pair[0] - pair[1]).abs() < 0.05));
+        assert!(frames.iter().any(|val| (val - frames[0]).abs() > 1e-3));
+    }
+
+    #[test]
+    fn looping_test() {
+        let noise = SimplexNoise::new(5);
+        for [x, y, _, _] in sample_points(17, 200, 500.0) {
+            let (x, y) = (x * 0.1, y * 0.1);
+            let val = looping_simplex2d(x, y, 8.0, 5.0);
+            assert!((looping_simplex2d(x + 8.0, y, 8.0, 5.0) - val).abs() < 1e-3);
+            assert!((looping_simplex2d(x, y - 5.0, 8.0, 5.0) - val).abs() < 1e-3);
+            assert!((looping_simplex2d(x + 0.01, y, 8.0, 5.0) - val).abs() < 0.05);
+
+            let seeded = noise.sample_looping2d(x, y, 8.0, 5.0);
+            assert!((noise.sample_looping2d(x - 16.0, y + 10.0, 8.0, 5.0) - seeded).abs() < 1e-3);
+        }
+    }
+}
+
+
+///////////////////////////////////////////////////////////////////////////////////////
+////////////////////////////////// worley /////////////////////////////////////////////
+///////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod worley_noise_tests {
+    use super::*;
+
+    fn with_metric(seed: u64, metric: DistanceMetric) -> WorleyNoise {
+        let mut noise = WorleyNoise::new(seed);
+        noise.set_distance_metric(metric);
+        noise
+    }
+
+    #[test]
+    fn seeded_test() {
+        let (a, b, other) = (WorleyNoise::new(7), WorleyNoise::new(7), WorleyNoise::new(8));
+        assert_eq!(a.get_seed(), 7);
+        let mut differences = 0;
+        for [x, y, z] in sample_points(9, 200, 100.0) {
+            assert_eq!(a.sample2d(x, y), b.sample2d(x, y));
+            assert_eq!(a.sample3d(x, y, z), b.sample3d(x, y, z));
+            if a.sample2d(x, y).f1 != other.sample2d(x, y).f1 {differences += 1;}
+        }
+        assert!(differences > 190);
+    }
+
+    #[test]
+    fn feature_point_test() {
+        for metric in [DistanceMetric::Euclidean, DistanceMetric::Manhattan, DistanceMetric::Chebyshev] {
+            let noise = with_metric(3, metric);
+            for [x, y, z] in sample_points(9, 500, 50.0) {
+                let sample = noise.sample2d(x, y);
+                let offset = sample.feature_point - Vector2::new(x, y);
+                assert!((metric.distance(&[offset.x, offset.y]) - sample.f1).abs() < 1e-4);
+                assert!(sample.f1 <= sample.f2 && sample.f2_minus_f1() >= 0.0);
+                assert_eq!(sample.cell, [sample.feature_point.x.floor() as i32, sample.feature_point.y.floor() as i32]);
+
+                let sample = noise.sample3d(x, y, z);
+                let offset = sample.feature_point - Vector3::new(x, y, z);
+                assert!((metric.distance(&[offset.x, offset.y, offset.z]) - sample.f1).abs() < 1e-4);
+                assert!(sample.f1 <= sample.f2);
+                assert_eq!(sample.cell, Vector3Int::from(sample.feature_point));
+
+                // the feature point is always the closest to itself
+                let at_feature = noise.sample3d(sample.feature_point.x, sample.feature_point.y, sample.feature_point.z);
+                assert_eq!((at_feature.cell, at_feature.cell_id), (sample.cell, sample.cell_id));
+                assert!(at_feature.f1 < 1e-5);
+                assert!((0.0..1.0).contains(&sample.cell_value()));
+            }
+        }
+    }
+
+    #[test]
+    fn brute_force_test() {
+        // finds the feature points of every cell round the origin by sampling densely, then checks against all of them
+        let noise = WorleyNoise::new(11);
+        let mut features = Vec::new();
+        for i in 0..200 {
+            for j in 0..200 {
+                let sample = noise.sample2d(i as f32 * 0.05 - 5.0, j as f32 * 0.05 - 5.0);
+                if !features.contains(&sample.feature_point) {features.push(sample.feature_point);}
+            }
+        }
+        assert!(features.len() >= 90);
+
+        for metric in [DistanceMetric::Euclidean, DistanceMetric::Manhattan, DistanceMetric::Chebyshev] {
+            let noise = with_metric(11, metric);
+            for [x, y, _] in sample_points(9, 300, 2.5) {
+                let mut distances: Vec<f32> = features.iter()
+                    .map(|feature| metric.distance(&[feature.x - x, feature.y - y]))
+                    .collect();
+                distances.sort_by(f32::total_cmp);
+
+                let sample = noise.sample2d(x, y);
+                assert!((sample.f1 - distances[0]).abs() < 1e-5);
+                assert!((sample.f2 - distances[1]).abs() < 1e-5);
+            }
+        }
+    }
+
+    #[test]
+    fn metric_test() {
+        // for any offset chebyshev <= euclidean <= manhattan, so the closest distances keep that order
+        let noises = [DistanceMetric::Chebyshev, DistanceMetric::Euclidean, DistanceMetric::Manhattan].map(|metric| with_metric(5, metric));
+        for [x, y, z] in sample_points(9, 300, 20.0) {
+            let samples = noises.map(|noise| noise.sample3d(x, y, z));
+            assert!(samples[0].f1 <= samples[1].f1 + 1e-6 && samples[1].f1 <= samples[2].f1 + 1e-6);
+            assert!(samples[0].f2 <= samples[1].f2 + 1e-6 && samples[1].f2 <= samples[2].f2 + 1e-6);
+        }
+        assert_eq!(DistanceMetric::default(), DistanceMetric::Euclidean);
+        assert_eq!(DistanceMetric::Manhattan.distance(&[3.0, -4.0]), 7.0);
+        assert_eq!(DistanceMetric::Chebyshev.distance(&[3.0, -4.0]), 4.0);
+        assert_eq!(DistanceMetric::Euclidean.distance(&[3.0, -4.0]), 5.0);
+    }
+
+    #[test]
+    fn jitter_test() {
+        let mut noise = WorleyNoise::new(21);
+        noise.set_jitter(2.0);
+        assert_eq!(noise.get_jitter(), 1.0);
+
+        // with no jitter every feature point is in the centre of its cell, so the cells are a regular grid
+        noise.set_jitter(0.0);
+        for [x, y, z] in sample_points(9, 300, 30.0) {
+            let sample = noise.sample3d(x, y, z);
+            assert_eq!(sample.feature_point, Vector3::new(x.floor(), y.floor(), z.floor()) + Vector3::ONE * 0.5);
+        }
+        let sample = noise.sample2d(2.5, -3.5);
+        assert_eq!((sample.f1, sample.f2), (0.0, 1.0));
+        assert_eq!(noise.sample2d(3.0, 0.2).f2_minus_f1(), 0.0);
+
+        // with a little jitter the feature points stay near the centre
+        noise.set_jitter(0.25);
+        for [x, y, _] in sample_points(9, 300, 30.0) {
+            let sample = noise.sample2d(x, y);
+            let centre = Vector2::new(sample.cell[0] as f32, sample.cell[1] as f32) + Vector2::new(0.5, 0.5);
+            let offset = sample.feature_point - centre;
+            assert!(offset.x.abs() <= 0.125 && offset.y.abs() <= 0.125);
+        }
+    }
+}
+
+
+///////////////////////////////////////////////////////////////////////////////////////
+////////////////////////////////// fractal ////////////////////////////////////////////
+///////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod fractal_noise_tests {
+    use super::*;
+
+    const TYPES: [FractalType; 4] = [FractalType::Fbm, FractalType::RidgedMulti, FractalType::Billow, FractalType::Turbulence];
+
+    #[test]
+    fn single_octave_test() {
+        let mut fractal = Fractal::fbm(SimplexNoise::new(2));
+        fractal.set_octaves(0);
+        assert_eq!(fractal.get_octaves(), 1);
+        for [x, y, z] in sample_points(31, 100, 100.0) {
+            assert_eq!(fractal.sample2d(x, y), fractal.get_base().sample2d(x, y));
+            assert_eq!(fractal.sample3d(x, y, z), SimplexNoise::new(2).sample3d(x, y, z));
+        }
+    }
+
+    #[test]
+    fn constant_test() {
+        let mut expected = [0.5, 0.0, 0.0, 0.5];
+        // ridged: signals of 0.25 then 0.25 * 0.5, normalised by 1.5 and remapped from 0..1 to -1..1
+        expected[1] = (0.25 + 0.5 * 0.125) / 1.5 * 2.0 - 1.0;
+
+        for (fractal_type, expected) in TYPES.into_iter().zip(expected) {
+            // the same value everywhere, so the result of layering it can be worked out by hand
+            let mut fractal = Fractal::new(Constant(0.5), fractal_type);
+            fractal.set_octaves(2);
+            assert!((fractal.sample2d(1.0, 2.0) - expected).abs() < 1e-6);
+            assert!((fractal.sample3d(1.0, 2.0, 3.0) - expected).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn range_test() {
+        for fractal_type in TYPES {
+            let fractal = Fractal::new(SimplexNoise::default(), fractal_type);
+            let low = if fractal_type == FractalType::Turbulence {0.0} else {-1.0};
+            let (mut min, mut max) = (f32::MAX, f32::MIN);
+            for [x, y, z] in sample_points(31, 2000, 100.0) {
+                for val in [fractal.sample2d(x, y), fractal.sample3d(x, y, z)] {
+                    assert!((low..=1.0).contains(&val));
+                    min = min.min(val);
+                    max = max.max(val);
+                }
+            }
+            // actually uses a good part of the range
+            assert!(max - min > 0.5);
+        }
+    }
+
+    #[test]
+    fn settings_test() {
+        let base = SimplexNoise::new(4);
+        let mut fractal = Fractal::fbm(&base);
+        assert_eq!((fractal.get_octaves(), fractal.get_lacunarity(), fractal.get_gain(), fractal.get_seed()), (6, 2.0, 0.5, 0));
+
+        // with no gain only the first octave counts
+        fractal.set_gain(0.0);
+        assert_eq!(fractal.sample2d(3.3, 4.4), base.sample2d(3.3, 4.4));
+
+        fractal.set_gain(0.6);
+        fractal.set_lacunarity(2.5);
+        let before: Vec<f32> = sample_points(31, 50, 100.0).iter().map(|[x, y, z]| fractal.sample3d(*x, *y, *z)).collect();
+        let mut same_seed = fractal.clone();
+        same_seed.set_seed(0);
+        fractal.set_seed(99);
+        let mut differences = 0;
+        for ([x, y, z], before) in sample_points(31, 50, 100.0).into_iter().zip(before) {
+            assert_eq!(same_seed.sample3d(x, y, z), before);
+            if fractal.sample3d(x, y, z) != before {differences += 1;}
+        }
+        assert!(differences > 45);
+
+        fractal.set_fractal_type(FractalType::Billow);
+        assert_eq!(fractal.get_fractal_type(), FractalType::Billow);
+    }
+
+    #[test]
+    fn worley_base_test() {
+        let mut manhattan = WorleyNoise::new(6);
+        manhattan.set_distance_metric(DistanceMetric::Manhattan);
+        for base in [WorleyNoise::new(6), manhattan] {
+            let (mut min, mut max) = (f32::MAX, f32::MIN);
+            for [x, y, z] in sample_points(31, 300, 100.0) {
+                for val in [NoiseFn::sample2d(&base, x, y), NoiseFn::sample3d(&base, x, y, z)] {
+                    assert!((-1.0..=1.0).contains(&val));
+                    min = min.min(val);
+                    max = max.max(val);
+                }
+            }
+            // worley noise isn't centred on 0 like simplex, but it should still cross it
+            assert!(min < -0.5 && max > 0.0);
+
+            for fractal_type in TYPES {
+                let fractal = Fractal::new(Box::new(base) as Box<dyn NoiseFn>, fractal_type);
+                let low = if fractal_type == FractalType::Turbulence {0.0} else {-1.0};
+                for [x, y, z] in sample_points(31, 300, 100.0) {
+                    assert!((low..=1.0).contains(&fractal.sample2d(x, y)));
+                    assert!((low..=1.0).contains(&fractal.sample3d(x, y, z)));
+                }
+            }
+        }
+
+        // fractals are noise too, so they can be layered again
+        let nested = Fractal::billow(Fractal::fbm(SimplexNoise::default()));
+        assert!((-1.0..=1.0).contains(&nested.sample2d(0.3, 0.7)));
+    }
+}
+
+
+///////////////////////////////////////////////////////////////////////////////////////
+////////////////////////////////// combinators ////////////////////////////////////////
+///////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod noise_combinator_tests {
+    use super::*;
+    use std::cell::Cell;
+
+    // counts how many times it has been sampled
+    struct CountingNoise(Cell<usize>);
+
+    impl NoiseFn for CountingNoise {
+        fn sample2d(&self, x: f32, y: f32) -> f32 {
+            self.0.set(self.0.get() + 1);
+            x + y
+        }
+        fn sample3d(&self, x: f32, y: f32, z: f32) -> f32 {
+            self.0.set(self.0.get() + 1);
+            x + y + z
+        }
+    }
+
+    // fails the test if it is ever sampled
+    struct UnusedNoise;
+
+    impl NoiseFn for UnusedNoise {
+        fn sample2d(&self, _x: f32, _y: f32) -> f32 {panic!("sampled noise that wasn't needed")}
+        fn sample3d(&self, _x: f32, _y: f32, _z: f32) -> f32 {panic!("sampled noise that wasn't needed")}
+    }
+
+    #[test]
+    fn arithmetic_test() {
+        let (a, b) = (SimplexNoise::new(1), SimplexNoise::new(2));
+        let added = (&a).add(&b);
+        let multiplied = (&a).multiply(&b);
+        let scaled = (&a).scale_bias(0.5, 0.5);
+        let clamped = (&a).clamp(-0.2, 0.3);
+        for [x, y, z] in sample_points(77, 100, 50.0) {
+            assert_eq!(added.sample2d(x, y), a.sample2d(x, y) + b.sample2d(x, y));
+            assert_eq!(multiplied.sample3d(x, y, z), a.sample3d(x, y, z) * b.sample3d(x, y, z));
+            assert_eq!(scaled.sample3d(x, y, z), a.sample3d(x, y, z) * 0.5 + 0.5);
+            assert_eq!(clamped.sample2d(x, y), a.sample2d(x, y).clamp(-0.2, 0.3));
+        }
+        assert_eq!(Constant(2.0).add(Constant(3.0)).multiply(Constant(-1.0)).sample2d(0.0, 0.0), -5.0);
+    }
+
+    #[test]
+    fn curve_test() {
+        let mut curve = Curve::new(Constant(0.0), vec![(1.0, 10.0), (-1.0, 0.0)]);
+        assert_eq!(curve.get_control_points(), &vec![(-1.0, 0.0), (1.0, 10.0)]);
+        assert_eq!(curve.sample2d(0.0, 0.0), 5.0);
+        assert_eq!(curve.remap(-3.0), 0.0);
+        assert_eq!(curve.remap(3.0), 10.0);
+
+        curve.add_control_point(0.0, 2.0);
+        assert_eq!(curve.get_control_points()[1], (0.0, 2.0));
+        assert_eq!(curve.sample3d(1.0, 2.0, 3.0), 2.0);
+        assert_eq!(curve.remap(0.5), 6.0);
+
+        curve.interp_fn = Some(|t| t * t);
+        assert_eq!(curve.remap(0.5), 4.0);
+
+        assert_eq!(Constant(0.7).curve(Vec::new()).sample2d(0.0, 0.0), 0.7);
+    }
+
+    #[test]
+    fn blend_test() {
+        let blend = |control| Constant(2.0).blend(Constant(4.0), Constant(control));
+        assert_eq!(blend(-1.0).sample2d(0.0, 0.0), 2.0);
+        assert_eq!(blend(1.0).sample3d(0.0, 0.0, 0.0), 4.0);
+        assert_eq!(blend(0.0).sample2d(0.0, 0.0), 3.0);
+        // past the ends of the control range it stays at the end
+        assert_eq!(blend(5.0).sample2d(0.0, 0.0), 4.0);
+
+        let mut eased = blend(0.0);
+        eased.interp_fn = Some(|t| t * t);
+        assert_eq!(eased.sample2d(0.0, 0.0), 2.5);
+    }
+
+    #[test]
+    fn select_test() {
+        let control = SimplexNoise::new(3);
+        let mut select = Constant(-1.0).select(Constant(1.0), &control, 0.2);
+        for [x, y, _] in sample_points(77, 200, 50.0) {
+            let expected = if control.sample2d(x, y) < 0.2 {-1.0} else {1.0};
+            assert_eq!(select.sample2d(x, y), expected);
+        }
+
+        // blended within the falloff either side of the threshold
+        select.falloff = 0.1;
+        let with_control = |control| Select {
+            low: Constant(-1.0),
+            high: Constant(1.0),
+            control: Constant(control),
+            threshold: select.threshold,
+            falloff: select.falloff,
+            interp_fn: None,
+        };
+        assert_eq!(with_control(0.2).sample2d(0.0, 0.0), 0.0);
+        assert!((with_control(0.25).sample3d(0.0, 0.0, 0.0) - 0.5).abs() < 1e-6);
+        assert_eq!(with_control(0.31).sample2d(0.0, 0.0), 1.0);
+        assert_eq!(with_control(0.09).sample2d(0.0, 0.0), -1.0);
+
+        // the side that isn't picked isn't sampled
+        assert_eq!(UnusedNoise.select(Constant(1.0), Constant(0.5), 0.0).sample2d(0.0, 0.0), 1.0);
+        assert_eq!(Constant(1.0).select(UnusedNoise, Constant(-0.5), 0.0).sample3d(0.0, 0.0, 0.0), 1.0);
+    }
+
+    #[test]
+    fn domain_turbulence_test() {
+        let source = SimplexNoise::new(5);
+        let still = (&source).domain_turbulence(SimplexNoise::new(6), 0.0);
+        let warped = (&source).domain_turbulence(SimplexNoise::new(6), 0.5);
+        let mut differences = 0;
+        for [x, y, z] in sample_points(77, 100, 50.0) {
+            assert_eq!(still.sample2d(x, y), source.sample2d(x, y));
+            assert_eq!(still.sample3d(x, y, z), source.sample3d(x, y, z));
+            if warped.sample3d(x, y, z) != source.sample3d(x, y, z) {differences += 1;}
+            // the warping is smooth so the noise stays continuous
+            assert!((warped.sample2d(x + 1e-3, y) - warped.sample2d(x, y)).abs() < 0.05);
+        }
+        assert!(differences > 90);
+
+        // a constant displacement just moves the noise
+        let shifted = (&source).domain_turbulence(Constant(1.0), 2.0);
+        assert_eq!(shifted.sample2d(0.3, 0.4), source.sample2d(2.3, 2.4));
+    }
+
+    #[test]
+    fn cache_test() {
+        let cache = CountingNoise(Cell::new(0)).cached();
+        assert_eq!(cache.sample2d(1.0, 2.0), 3.0);
+        assert_eq!(cache.sample2d(1.0, 2.0), 3.0);
+        assert_eq!(cache.source.0.get(), 1);
+
+        assert_eq!(cache.sample3d(1.0, 2.0, 3.0), 6.0);
+        assert_eq!(cache.sample2d(1.0, 2.0), 3.0);
+        assert_eq!(cache.sample3d(1.0, 2.0, 3.0), 6.0);
+        assert_eq!(cache.source.0.get(), 2);
+
+        assert_eq!(cache.sample2d(2.0, 2.0), 4.0);
+        assert_eq!(cache.source.0.get(), 3);
+    }
+
+    #[test]
+    fn closure_test() {
+        let seeded = SimplexNoise::new(8);
+        let scale = 0.25;
+        let noise = FnNoise::new(
+            |x, y| seeded.sample2d(x * scale, y * scale),
+            |x, y, z| seeded.sample3d(x * scale, y * scale, z * scale),
+        );
+        let other = SimplexNoise::new(9);
+        for [x, y, z] in sample_points(77, 50, 50.0) {
+            assert_eq!(noise.sample2d(x, y), seeded.sample2d(x * 0.25, y * 0.25));
+            assert_eq!(noise.sample3d(x, y, z), seeded.sample3d(x * 0.25, y * 0.25, z * 0.25));
+
+            // the selectors take closures too, so seeded noise can be used directly
+            let selector = |x, y| (other.sample2d(x, y) + 1.0) * 0.5;
+            let selected = selector_noise_2d(x, y, |x, y| noise.sample2d(x, y), |_, _| 1.0, selector, None);
+            assert!((selected - lerp(noise.sample2d(x, y), 1.0, selector(x, y))).abs() < 1e-6);
+            assert_eq!(selector_noise_3d(x, y, z, simplex3d, simplex3d, |_, _, _| 0.5, None), simplex3d(x, y, z));
+        }
+    }
+
+    #[test]
+    fn graph_test() {
+        // mountains where the control is high, gentle hills elsewhere, with the result warped and kept between -1 and 1
+        let mountains = Fractal::ridged_multi(SimplexNoise::new(10));
+        let hills = SimplexNoise::new(11).scale_bias(0.25, -0.5);
+        let graph = hills
+            .blend(mountains, SimplexNoise::new(12).cached())
+            .domain_turbulence(Fractal::fbm(SimplexNoise::new(13)), 0.3)
+            .clamp(-1.0, 1.0);
+
+        let boxed: Box<dyn NoiseFn> = Box::new(graph.clone());
+        for [x, y, z] in sample_points(77, 200, 50.0) {
+            let val = graph.sample3d(x, y, z);
+            assert!((-1.0..=1.0).contains(&val));
+            assert_eq!(boxed.sample3d(x, y, z), val);
+        }
+    }
+}
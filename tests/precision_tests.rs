@@ -0,0 +1,113 @@
+use rust_maths::*;
+
+
+///////////////////////////////////////////////////////////////////////////////////////
+////////////////////////////////// Double precision ///////////////////////////////////
+///////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod precision_tests {
+    use super::*;
+
+    #[test]
+    fn large_coordinates_test() {
+        let far = DVector3::new(1.0e8, 0.0, 0.0);
+        let offset = DVector3::new(0.25, 0.5, 0.0);
+
+        assert_eq!((far + offset) - far, offset);
+        // the same thing in single precision loses the offset completely
+        let single = far.to_single();
+        assert_eq!((single + offset.to_single()) - single, Vector3::new(0.0, 0.5, 0.0));
+    }
+
+    #[test]
+    fn conversion_test() {
+        let vec = Vector3::new(1.5, -2.25, 3.0);
+        assert_eq!(DVector3::from(vec).to_single(), vec);
+        assert_eq!(DVector2::new(1.0, 2.0).to_single(), Vector2::new(1.0, 2.0));
+        assert_eq!(DVector4::from(Vector4::W), DVector4::W);
+        assert_eq!(DVector3::new(0.1, 0.0, 0.0).to_single().x, 0.1f32);
+
+        let mat = Matrix4::from_translation([1, 2, 3]) * Matrix4::from_scale(2.0);
+        assert_eq!(DMatrix4::from(mat).to_single(), mat);
+        assert_eq!(DMatrix3::from(Matrix3::IDENTITY), DMatrix3::IDENTITY);
+        assert_eq!(DMatrix2::IDENTITY.to_single(), Matrix2::IDENTITY);
+    }
+
+    #[test]
+    fn matching_api_test() {
+        let single = Matrix4::from_rotation(Matrix3::from_angle_y(0.7)) * Matrix4::from_translation([4, 5, 6]);
+        let double = DMatrix4::from_rotation(DMatrix3::from_angle_y(0.7)) * DMatrix4::from_translation([4, 5, 6]);
+
+        let point = single.transform_point([1, 2, 3]);
+        let double_point = double.transform_point([1, 2, 3]);
+        assert!((point - double_point.to_single()).magnitude() < 1e-5);
+
+        let parts = double.decompose().unwrap();
+        assert!((parts.translation - DVector3::new(4.0, 5.0, 6.0)).magnitude() < 1e-12);
+        assert!((DMatrix4::recompose(&parts).inverted() * double - DMatrix4::IDENTITY).x.magnitude() < 1e-12);
+    }
+
+    #[test]
+    fn decomposition_api_test() {
+        let mat = DMatrix3::new(
+            4.0, 1.0, 0.5,
+            1.0, 3.0, 0.25,
+            0.5, 0.25, 2.0
+        );
+        let (values, vectors) = mat.symmetric_eigen();
+        let (single_values, _) = mat.to_single().symmetric_eigen();
+        assert!((values.to_single() - single_values).magnitude() < 1e-5);
+        assert!((mat * vectors.c0() - vectors.c0() * values.x).magnitude() < 1e-12);
+
+        let points = [DVector3::new(1.0e7, 0.0, 0.0), DVector3::new(1.0e7 + 2.0, 0.0, 0.0), DVector3::new(1.0e7 + 1.0, 1.0, 0.0)];
+        let (mean, covariance) = DMatrix3::covariance(&points);
+        assert!((mean - DVector3::new(1.0e7 + 1.0, 1.0 / 3.0, 0.0)).magnitude() < 1e-9);
+        assert!((covariance.x.x - 2.0 / 3.0).abs() < 1e-9);
+
+        let skewed = DMatrix3::from_angle_z(0.4) * mat;
+        let (u, sigma, v) = skewed.svd();
+        let rebuilt = u * DMatrix3::new(sigma.x, 0.0, 0.0, 0.0, sigma.y, 0.0, 0.0, 0.0, sigma.z) * v.transposed();
+        assert!((rebuilt - skewed).x.magnitude() < 1e-12);
+        let (rotation, stretch) = skewed.polar_decomposition();
+        assert!((rotation * stretch - skewed).y.magnitude() < 1e-12);
+        assert!((rotation.determinant() - 1.0).abs() < 1e-12);
+        assert!((skewed.pseudo_inverse() * skewed - DMatrix3::IDENTITY).z.magnitude() < 1e-12);
+        assert!(DMatrix3::default().try_inverted(1e-12).is_none());
+
+        let transform = DMatrix4::from_translation([1.0e7, 2.0, 3.0]) * DMatrix4::from_scale(2.0);
+        let (_, sigma, _) = transform.svd();
+        let (_, single_sigma, _) = transform.to_single().svd();
+        assert!((sigma.to_single() - single_sigma).magnitude() / single_sigma.x < 1e-5);
+        assert!((transform.pseudo_inverse() * transform - DMatrix4::IDENTITY).w.magnitude() < 1e-6);
+        assert!(transform.try_inverted(1e-12).is_some());
+    }
+
+    #[test]
+    fn anchored_collider_test() {
+        // 10,000km out f32 can only hold whole numbers, so these round half a unit apart to a whole unit apart
+        let centre = DVector3::new(1.0e7 + 0.5, 0.0, 0.0);
+        let point = DVector3::new(1.0e7 + 0.6, 0.0, 0.0);
+        assert!(!BoundingSphere::new(centre.to_single(), 0.25).contains_point(point.to_single()));
+
+        let sphere = AnchoredCollider::new(centre, BoundingSphere::new(Vector3::ZERO, 0.25));
+        assert!(sphere.contains_point(point));
+        assert!(!sphere.contains_point(point + DVector3::X * 0.2));
+        assert!((sphere.closest_point([1.0e7 + 1.5, 0.0, 0.0]) - DVector3::new(1.0e7 + 0.75, 0.0, 0.0)).magnitude() < 1e-6);
+        assert!((sphere.distance_to_point([1.0e7 + 1.5, 0.0, 0.0]) - 0.75).abs() < 1e-6);
+
+        let hit = sphere.check_ray([1.0e7 + 0.5, 0.0, -10.0], DVector3::Z, None).unwrap();
+        assert!((hit.hit_distance - 9.75).abs() < 1e-5);
+        assert!((sphere.to_world(hit.hit_position) - DVector3::new(1.0e7 + 0.5, 0.0, -0.25)).magnitude() < 1e-6);
+        assert!(sphere.check_ray([1.0e7 + 0.8, 0.0, -10.0], DVector3::Z, None).is_none());
+    }
+
+    #[test]
+    fn relative_camera_test() {
+        let camera: DCamera<u8> = DCamera::new(Some([1.0e9, 2.0, 3.0]), Some([0.0, 0.0, 1.0]), None, None, None);
+        let relative = camera.to_relative_camera([1.0e9, 0.0, 0.0]);
+
+        assert_eq!(relative.position, Vector3::new(0.0, 2.0, 3.0));
+        assert_eq!(relative.direction, Vector3::Z);
+    }
+}
@@ -0,0 +1,125 @@
+use rust_maths::*;
+use std::f32::consts::PI;
+
+const TOLERANCE: f32 = 1e-5;
+
+fn vectors_close(a: Vector3, b: Vector3) -> bool {
+    (a - b).magnitude() < TOLERANCE
+}
+
+fn matrices_close(a: Matrix3, b: Matrix3) -> bool {
+    vectors_close(a.x, b.x) && vectors_close(a.y, b.y) && vectors_close(a.z, b.z)
+}
+
+fn rotations_close(a: Quaternion, b: Quaternion) -> bool {
+    // q and -q are the same rotation
+    (a.dot(b).abs() - 1.0).abs() < TOLERANCE
+}
+
+///////////////////////////////////////////////////////////////////////////////////////
+////////////////////////////////// Quaternion /////////////////////////////////////////
+///////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod quaternion_tests {
+    use super::*;
+
+    #[test]
+    fn axis_angle_matches_matrix_test() {
+        let axis = Vector3::new(1.0, 2.0, -0.5);
+        let angle = 1.2;
+
+        let quat = Quaternion::from_angle_and_axis(angle, axis);
+        assert!(matrices_close(quat.to_matrix3(), Matrix3::from_angle_and_axis(angle, axis)));
+    }
+
+    #[test]
+    fn rotate_vector_test() {
+        let quat = Quaternion::from_angle_z(PI / 2.0);
+        assert!(vectors_close(quat * Vector3::X, Vector3::Y));
+        assert!(vectors_close(quat.rotate_vector([0, 0, 1]), Vector3::Z));
+    }
+
+    #[test]
+    fn multiplication_order_test() {
+        let a = Quaternion::from_angle_x(0.7);
+        let b = Quaternion::from_angle_y(-1.3);
+        let point = Vector3::new(0.3, -2.0, 5.0);
+
+        assert!(vectors_close((a * b) * point, a * (b * point)));
+        assert!(matrices_close((a * b).to_matrix3(), a.to_matrix3() * b.to_matrix3()));
+    }
+
+    #[test]
+    fn inverse_test() {
+        let quat = Quaternion::from_euler_angles([0.4, -1.1, 2.5]);
+        assert!(rotations_close(quat * quat.inverse(), Quaternion::IDENTITY));
+        assert!(rotations_close(quat.inverse(), quat.conjugate()));
+
+        let scaled = quat * 3.0;
+        assert!(rotations_close(scaled * scaled.inverse(), Quaternion::IDENTITY));
+    }
+
+    #[test]
+    fn euler_angles_test() {
+        let angles = Vector3::new(0.3, -0.6, 1.9);
+        let quat = Quaternion::from_euler_angles(angles);
+
+        assert!(matrices_close(quat.to_matrix3(), Matrix3::from_euler_angles(angles)));
+        assert!(vectors_close(quat.euler_angles(), angles));
+    }
+
+    #[test]
+    fn matrix_round_trip_test() {
+        // covers each branch of the matrix conversion
+        let rotations = [
+            Quaternion::from_euler_angles([0.1, 0.2, 0.3]),
+            Quaternion::from_angle_x(PI * 0.95),
+            Quaternion::from_angle_y(PI * 0.95),
+            Quaternion::from_angle_z(PI * 0.95),
+            Quaternion::from_angle_and_axis(PI, [1, 1, 0]),
+        ];
+
+        for quat in rotations {
+            assert!(rotations_close(Quaternion::from_matrix3(quat.to_matrix3()), quat));
+            assert!(rotations_close(Quaternion::from(Matrix4::from(quat)), quat));
+        }
+    }
+
+    #[test]
+    fn angle_and_axis_test() {
+        let quat = Quaternion::from_angle_and_axis(0.8, [0, 3, 4]);
+        let (angle, axis) = quat.angle_and_axis();
+
+        assert!((angle - 0.8).abs() < TOLERANCE);
+        assert!(vectors_close(axis, Vector3::new(0.0, 0.6, 0.8)));
+    }
+
+    #[test]
+    fn slerp_test() {
+        let start = Quaternion::IDENTITY;
+        let end = Quaternion::from_angle_y(PI / 2.0);
+
+        assert!(rotations_close(start.slerp(end, 0.0), start));
+        assert!(rotations_close(start.slerp(end, 1.0), end));
+        assert!(rotations_close(start.slerp(end, 0.5), Quaternion::from_angle_y(PI / 4.0)));
+        assert!((start.slerp(end, 0.25).angle_to(start) - PI / 8.0).abs() < TOLERANCE);
+    }
+
+    #[test]
+    fn slerp_shortest_path_test() {
+        let start = Quaternion::from_angle_z(0.1);
+        let end = -Quaternion::from_angle_z(0.3);
+
+        let half = start.slerp(end, 0.5);
+        assert!(rotations_close(half, Quaternion::from_angle_z(0.2)));
+        assert!(rotations_close(start.nlerp(end, 0.5), half));
+    }
+
+    #[test]
+    fn normalise_test() {
+        let mut quat = Quaternion::new(1.0, 2.0, 3.0, 4.0);
+        quat.normalise();
+        assert!((quat.magnitude() - 1.0).abs() < TOLERANCE);
+    }
+}
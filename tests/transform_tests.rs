@@ -0,0 +1,128 @@
+use rust_maths::*;
+use std::f32::consts::PI;
+
+fn vectors_close(a: Vector3, b: Vector3) -> bool {
+    (a - b).magnitude() < 1e-4
+}
+
+///////////////////////////////////////////////////////////////////////////////////////
+////////////////////////////////// Transform //////////////////////////////////////////
+///////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod transform_tests {
+    use super::*;
+
+    #[test]
+    fn matrix_matches_transform_test() {
+        let transform = Transform::new([1.0, 2.0, 3.0], Quaternion::from_euler_angles([0.2, 0.5, -1.0]), [2.0, 1.0, 0.5]);
+        let point = Vector3::new(-1.0, 4.0, 2.0);
+
+        assert!(vectors_close(transform.matrix().transform_point(point), transform.transform_point(point)));
+        assert!(vectors_close(transform.inverse_matrix().transform_point(transform.transform_point(point)), point));
+        assert!(vectors_close(transform.inverse_transform_point(transform.transform_point(point)), point));
+    }
+
+    #[test]
+    fn inverse_test() {
+        let transform = Transform::new([5.0, 0.0, -2.0], Quaternion::from_angle_y(1.0), [3.0; 3]);
+        let point = Vector3::new(1.0, 1.0, 1.0);
+
+        assert!(vectors_close(transform.inverse().transform_point(transform.transform_point(point)), point));
+        assert!(vectors_close((transform * transform.inverse()).transform_point(point), point));
+    }
+
+    #[test]
+    fn look_at_test() {
+        let mut transform = Transform::from_position([0, 0, 5]);
+        transform.look_at([5, 0, 5], Vector3::Y);
+
+        assert!(vectors_close(transform.forward(), Vector3::X));
+        assert!(vectors_close(transform.up(), Vector3::Y));
+
+        let view = transform.inverse_matrix();
+        let look = Matrix4::look_at_rh([0, 0, 5], [5, 0, 5], Vector3::Y);
+        assert!(vectors_close(view.transform_point([3, 1, 2]), look.transform_point([3, 1, 2])));
+    }
+
+    #[test]
+    fn from_matrix_test() {
+        let transform = Transform::new([1.0, -1.0, 0.0], Quaternion::from_angle_x(0.3), [1.0, 2.0, 3.0]);
+        let recovered = Transform::from_matrix(transform.matrix()).unwrap();
+
+        assert!(vectors_close(recovered.position, transform.position));
+        assert!(vectors_close(recovered.scale, transform.scale));
+        assert!(recovered.rotation.angle_to(transform.rotation) < 1e-3);
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////
+////////////////////////////////// Hierarchy //////////////////////////////////////////
+///////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod hierarchy_tests {
+    use super::*;
+
+    #[test]
+    fn parent_child_test() {
+        let mut hierarchy = TransformHierarchy::new();
+        let parent = hierarchy.add_root(Transform::new([10, 0, 0], Quaternion::from_angle_z(PI / 2.0), [2.0; 3]));
+        let child = hierarchy.add_child(parent, Transform::from_position([1, 0, 0])).unwrap();
+
+        assert!(vectors_close(hierarchy.local_to_world_point(child, Vector3::ZERO).unwrap(), Vector3::new(10.0, 2.0, 0.0)));
+        assert!(vectors_close(hierarchy.world_to_local_point(child, [10, 2, 0]).unwrap(), Vector3::ZERO));
+        assert!(vectors_close(hierarchy.world_transform(child).unwrap().scale, Vector3::new(2.0, 2.0, 2.0)));
+    }
+
+    #[test]
+    fn dirty_update_test() {
+        let mut hierarchy = TransformHierarchy::new();
+        let root = hierarchy.add_root(Transform::IDENTITY);
+        let middle = hierarchy.add_child(root, Transform::from_position([0, 1, 0])).unwrap();
+        let leaf = hierarchy.add_child(middle, Transform::from_position([0, 1, 0])).unwrap();
+
+        assert!(vectors_close(hierarchy.local_to_world_point(leaf, Vector3::ZERO).unwrap(), Vector3::new(0.0, 2.0, 0.0)));
+
+        hierarchy.set_local(root, Transform::from_position([5, 0, 0])).unwrap();
+        assert!(vectors_close(hierarchy.local_to_world_point(leaf, Vector3::ZERO).unwrap(), Vector3::new(5.0, 2.0, 0.0)));
+
+        hierarchy.set_local(middle, Transform::from_position([0, 0, 1])).unwrap();
+        hierarchy.update_world_matrices();
+        assert!(vectors_close(hierarchy.local_to_world_point(leaf, Vector3::ZERO).unwrap(), Vector3::new(5.0, 1.0, 1.0)));
+    }
+
+    #[test]
+    fn reparent_test() {
+        let mut hierarchy = TransformHierarchy::new();
+        let a = hierarchy.add_root(Transform::from_position([1, 0, 0]));
+        let b = hierarchy.add_root(Transform::from_position([0, 0, 7]));
+        let child = hierarchy.add_child(a, Transform::from_position([0, 1, 0])).unwrap();
+
+        assert!(hierarchy.set_parent(a, Some(child)).is_err());
+        hierarchy.set_parent(child, Some(b)).unwrap();
+
+        assert_eq!(hierarchy.parent(child), Some(b));
+        assert!(hierarchy.children(a).is_empty());
+        assert!(vectors_close(hierarchy.local_to_world_point(child, Vector3::ZERO).unwrap(), Vector3::new(0.0, 1.0, 7.0)));
+    }
+
+    #[test]
+    fn remove_test() {
+        let mut hierarchy = TransformHierarchy::new();
+        let root = hierarchy.add_root(Transform::IDENTITY);
+        let child = hierarchy.add_child(root, Transform::IDENTITY).unwrap();
+        let grandchild = hierarchy.add_child(child, Transform::IDENTITY).unwrap();
+
+        assert!(hierarchy.remove(child).is_some());
+        assert_eq!(hierarchy.len(), 1);
+        assert!(!hierarchy.contains(grandchild));
+        assert!(hierarchy.children(root).is_empty());
+
+        // reused slots don't make old handles valid again
+        let new = hierarchy.add_root(Transform::IDENTITY);
+        assert!(hierarchy.contains(new));
+        assert!(!hierarchy.contains(child));
+        assert!(hierarchy.world_matrix(child).is_none());
+    }
+}
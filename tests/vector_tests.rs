@@ -42,4 +42,39 @@ mod vector_three_tests {
     fn multiplication_test() {
         assert_eq!(Vector3::new(5.0, 4.0, 3.0) * Vector3::new(3.0, 4.0, 5.0), Vector3::new(15.0, 16.0, 15.0))
     }
-}
\ No newline at end of file
+}
+
+///////////////////////////////////////////////////////////////////////////////////////
+////////////////////////////////// VectorN ////////////////////////////////////////////
+///////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod vector_n_tests {
+    use super::{Vector3, Vector4, VectorN};
+
+    #[test]
+    fn arithmetic_test() {
+        let a = VectorN::new([1.0, 2.0, 3.0, 4.0, 5.0]);
+        let b = VectorN::splat(2.0);
+
+        assert_eq!(a + b, VectorN::new([3.0, 4.0, 5.0, 6.0, 7.0]));
+        assert_eq!(a - b, VectorN::new([-1.0, 0.0, 1.0, 2.0, 3.0]));
+        assert_eq!(a * 2.0, a.component_mul(b));
+        assert_eq!(-a / 2.0, VectorN::new([-0.5, -1.0, -1.5, -2.0, -2.5]));
+        assert_eq!(a.dot(b), 30.0);
+        assert_eq!(a.sum(), 15.0);
+        assert_eq!(a[4], 5.0);
+        assert!((VectorN::<6>::ONE.normalised().magnitude() - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn conversion_test() {
+        let vec = Vector3::new(1.0, -2.0, 3.0);
+        let generic: VectorN<3> = vec.into();
+
+        assert_eq!(generic.dot(vec), vec.dot(vec));
+        assert_eq!(Vector3::from(generic), vec);
+        assert_eq!(Vector4::from(VectorN::unit(3)), Vector4::W);
+        assert_eq!(format!("{}", generic), "[1, -2, 3]");
+    }
+}